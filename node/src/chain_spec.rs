@@ -27,3 +27,15 @@ pub fn local_chain_spec() -> Result<ChainSpec, String> {
 	.with_genesis_config_preset_name(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET)
 	.build())
 }
+
+pub fn fissionmall_demo_chain_spec() -> Result<ChainSpec, String> {
+	Ok(ChainSpec::builder(
+		WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?,
+		None,
+	)
+	.with_name("FissionMall Demo")
+	.with_id("fissionmall_demo")
+	.with_chain_type(ChainType::Development)
+	.with_genesis_config_preset_name(solochain_template_runtime::genesis_config_presets::FISSIONMALL_DEMO_PRESET)
+	.build())
+}
@@ -7,12 +7,218 @@
 
 use std::sync::Arc;
 
-use jsonrpsee::RpcModule;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObjectOwned, RpcModule};
+use pallet_institution::{runtime_api::InstitutionDirectoryApi, InstitutionFilter, InstitutionPage};
 use sc_transaction_pool_api::TransactionPool;
-use solochain_template_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use serde::{Deserialize, Serialize};
+use solochain_template_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Nonce, Runtime};
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+/// Decoded institution record as stored by the runtime.
+pub type InstitutionInfo = pallet_institution::InstitutionInfo<Runtime>;
+
+/// An institution directory entry.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstitutionEntry {
+	/// Institution ID.
+	pub id: Bytes,
+	/// Short name.
+	pub name: Bytes,
+	/// Registered full name.
+	pub full_name: Bytes,
+	/// Status code (0 certified, 1 not certified, 2 deactivated, 3 suspended).
+	pub status: u8,
+	/// Current owner.
+	pub creator: AccountId,
+	/// Block the institution was created at.
+	pub created_date: BlockNumber,
+	/// Parent (headquarters) institution ID, if this is a branch.
+	pub parent: Option<Bytes>,
+}
+
+impl InstitutionEntry {
+	fn new(id: Vec<u8>, info: InstitutionInfo) -> Self {
+		Self {
+			id: id.into(),
+			name: info.institution_name.into_inner().into(),
+			full_name: info.institution_full_name.into_inner().into(),
+			status: info.status.code(),
+			creator: info.creator,
+			created_date: info.created_date,
+			parent: info.parent.map(|link| link.parent_id.into_inner().into()),
+		}
+	}
+}
+
+/// A page of the institution directory.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstitutionDirectoryPage {
+	/// Institutions on this page.
+	pub items: Vec<InstitutionEntry>,
+	/// Cursor to pass for the next page; `None` once the directory is exhausted.
+	pub next_cursor: Option<Bytes>,
+}
+
+impl From<InstitutionPage<InstitutionInfo>> for InstitutionDirectoryPage {
+	fn from(page: InstitutionPage<InstitutionInfo>) -> Self {
+		Self {
+			items: page.items.into_iter().map(|(id, info)| InstitutionEntry::new(id, info)).collect(),
+			next_cursor: page.next_cursor.map(Into::into),
+		}
+	}
+}
+
+/// Institution directory RPC methods, served under the `institution_*` namespace.
+#[rpc(server, namespace = "institution")]
+pub trait InstitutionDirectoryRpc<BlockHash> {
+	/// Look up a single institution.
+	#[method(name = "get")]
+	fn get(&self, institution_id: Bytes, at: Option<BlockHash>) -> RpcResult<Option<InstitutionEntry>>;
+
+	/// Page through all institutions.
+	#[method(name = "list")]
+	fn list(
+		&self,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<InstitutionDirectoryPage>;
+
+	/// Page through institutions with the given status code.
+	#[method(name = "byStatus")]
+	fn by_status(
+		&self,
+		status: u8,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<InstitutionDirectoryPage>;
+
+	/// Page through institutions owned by `creator`.
+	#[method(name = "byCreator")]
+	fn by_creator(
+		&self,
+		creator: AccountId,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<InstitutionDirectoryPage>;
+
+	/// Page through institutions whose name starts with `prefix`.
+	#[method(name = "byNamePrefix")]
+	fn by_name_prefix(
+		&self,
+		prefix: Bytes,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<InstitutionDirectoryPage>;
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Implementation of [`InstitutionDirectoryRpcServer`] backed by the runtime API.
+pub struct InstitutionDirectory<C> {
+	client: Arc<C>,
+}
+
+impl<C> InstitutionDirectory<C> {
+	/// Create a new instance with the given client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> InstitutionDirectory<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: InstitutionDirectoryApi<Block, AccountId, InstitutionInfo>,
+{
+	fn page(
+		&self,
+		filter: InstitutionFilter<AccountId>,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<InstitutionDirectoryPage> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.institution_page(at, filter, cursor.map(|cursor| cursor.0), limit)
+			.map(Into::into)
+			.map_err(runtime_error)
+	}
+}
+
+impl<C> InstitutionDirectoryRpcServer<<Block as BlockT>::Hash> for InstitutionDirectory<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: InstitutionDirectoryApi<Block, AccountId, InstitutionInfo>,
+{
+	fn get(
+		&self,
+		institution_id: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<InstitutionEntry>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let info = self
+			.client
+			.runtime_api()
+			.institution(at, institution_id.0.clone())
+			.map_err(runtime_error)?;
+		Ok(info.map(|info| InstitutionEntry::new(institution_id.0, info)))
+	}
+
+	fn list(
+		&self,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<InstitutionDirectoryPage> {
+		self.page(InstitutionFilter::All, cursor, limit, at)
+	}
+
+	fn by_status(
+		&self,
+		status: u8,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<InstitutionDirectoryPage> {
+		self.page(InstitutionFilter::Status(status), cursor, limit, at)
+	}
+
+	fn by_creator(
+		&self,
+		creator: AccountId,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<InstitutionDirectoryPage> {
+		self.page(InstitutionFilter::Creator(creator), cursor, limit, at)
+	}
+
+	fn by_name_prefix(
+		&self,
+		prefix: Bytes,
+		cursor: Option<Bytes>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<InstitutionDirectoryPage> {
+		self.page(InstitutionFilter::NamePrefix(prefix.0), cursor, limit, at)
+	}
+}
+
+fn runtime_error(err: impl std::fmt::Display) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(RUNTIME_ERROR, "Unable to query the institution directory.", Some(err.to_string()))
+}
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -33,6 +239,7 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: InstitutionDirectoryApi<Block, AccountId, InstitutionInfo>,
 	P: TransactionPool + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
@@ -42,7 +249,8 @@ where
 	let FullDeps { client, pool } = deps;
 
 	module.merge(System::new(client.clone(), pool).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(InstitutionDirectory::new(client).into_rpc())?;
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
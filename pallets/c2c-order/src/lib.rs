@@ -7,6 +7,7 @@ pub use pallet::*;
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Get};
     use frame_system::pallet_prelude::*;
+    use pallet_institution::{CommerceOutcome, InstitutionInspect, InstitutionRole, OnCommerceOutcome};
     use scale_info::TypeInfo;
     use sp_std::prelude::*;
     use sp_std::vec::Vec;
@@ -28,6 +29,15 @@ pub mod pallet {
         /// 机构ID最大长度
         #[pallet::constant]
         type MaxInstitutionIdLength: Get<u32>;
+        
+        /// 机构信息查询
+        type Institutions: InstitutionInspect<Self::AccountId>;
+        
+        /// 机构确认完成或公证裁决时通知信誉模块
+        type OnCommerceOutcome: OnCommerceOutcome;
+        
+        /// 对公证中的订单作出裁决及强制取消滞留订单的特权来源
+        type ArbitrationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
     }
 
     #[pallet::pallet]
@@ -140,6 +150,8 @@ pub mod pallet {
         OrderCompleted(BoundedVec<u8, T::MaxOrderCodeLength>),
         /// 订单进入公证 [订单编码]
         OrderNotarizing(BoundedVec<u8, T::MaxOrderCodeLength>),
+        /// 公证裁决已作出 [订单编码, 机构是否败诉]
+        DisputeResolved(BoundedVec<u8, T::MaxOrderCodeLength>, bool),
     }
 
     #[pallet::error]
@@ -166,6 +178,12 @@ pub mod pallet {
         InvalidDirection,
         /// 无效的金额
         InvalidAmount,
+        /// 机构存在未结束的订单
+        InstitutionHasOpenOrders,
+        /// 机构已停用或暂停营业
+        InstitutionSuspended,
+        /// 机构不存在
+        InstitutionNotFound,
     }
 
     #[pallet::call]
@@ -210,6 +228,10 @@ pub mod pallet {
             let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_code)
                 .map_err(|_| Error::<T>::StringConversionError)?;
             
+            // 机构必须存在且正常营业
+            ensure!(T::Institutions::exists(&bounded_institution_code), Error::<T>::InstitutionNotFound);
+            ensure!(T::Institutions::is_active(&bounded_institution_code), Error::<T>::InstitutionSuspended);
+            
             let current_block = frame_system::Pallet::<T>::block_number();
             
             // 创建订单
@@ -275,9 +297,6 @@ pub mod pallet {
             Orders::<T>::try_mutate(&bounded_order_code, |maybe_order| -> DispatchResult {
                 let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
                 
-                // 检查权限（仅创建者可以更新）
-                ensure!(order.creator == who, Error::<T>::NotAuthorized);
-                
                 let old_status = order.status.clone();
                 
                 // 将 u8 转换为 OrderStatus
@@ -291,6 +310,13 @@ pub mod pallet {
                     _ => return Err(Error::<T>::InvalidStatus.into()),
                 };
                 
+                // 检查权限：完成须由机构订单处理人员确认，其他状态可由订单创建者或机构订单处理人员更新
+                if new_status == OrderStatus::Completed {
+                    Self::ensure_order_clerk(order, &who)?;
+                } else {
+                    Self::ensure_order_operator(order, &who)?;
+                }
+                
                 // 检查状态转换是否有效
                 Self::validate_status_transition(&order.status, &new_status)?;
                 
@@ -314,9 +340,10 @@ pub mod pallet {
                 // 发出事件
                 Self::deposit_event(Event::OrderStatusUpdated(bounded_order_code.clone(), status));
                 
-                // 如果是特殊状态，发出额外事件
+                // 如果是特殊状态，发出额外事件；机构确认完成计入机构信誉
                 match new_status {
                     OrderStatus::Completed => {
+                        T::OnCommerceOutcome::on_commerce_outcome(&order.institution_code, CommerceOutcome::C2cOrderCompleted);
                         Self::deposit_event(Event::OrderCompleted(bounded_order_code.clone()));
                     },
                     OrderStatus::Notarizing => {
@@ -347,8 +374,8 @@ pub mod pallet {
             Orders::<T>::try_mutate(&bounded_order_code, |maybe_order| -> DispatchResult {
                 let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
                 
-                // 检查权限（仅创建者可以取消）
-                ensure!(order.creator == who, Error::<T>::NotAuthorized);
+                // 检查权限（订单创建者或机构订单处理人员）
+                Self::ensure_order_operator(order, &who)?;
                 
                 // 只有待支付或已支付的订单可以取消
                 ensure!(
@@ -442,14 +469,11 @@ pub mod pallet {
             Orders::<T>::try_mutate(&bounded_order_code, |maybe_order| -> DispatchResult {
                 let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
                 
-                // 检查权限
-                ensure!(order.creator == who, Error::<T>::NotAuthorized);
+                // 检查权限（须由机构订单处理人员确认）
+                Self::ensure_order_clerk(order, &who)?;
                 
-                // 只有已发货或公证中的订单可以完成
-                ensure!(
-                    matches!(order.status, OrderStatus::Delivered | OrderStatus::Notarizing),
-                    Error::<T>::InvalidStatusTransition
-                );
+                // 只有已发货的订单可以完成，公证中的订单由公证裁决结束
+                ensure!(order.status == OrderStatus::Delivered, Error::<T>::InvalidStatusTransition);
                 
                 let old_status = order.status.clone();
                 
@@ -469,15 +493,134 @@ pub mod pallet {
                 })?;
                 
                 // 发出事件
+                T::OnCommerceOutcome::on_commerce_outcome(&order.institution_code, CommerceOutcome::C2cOrderCompleted);
                 Self::deposit_event(Event::OrderCompleted(bounded_order_code.clone()));
                 
                 Ok(())
             })
         }
+        
+        /// 对公证中的订单作出裁决：机构败诉时取消订单，否则完成订单，裁决结果计入机构信誉
+        #[pallet::call_index(5)]
+        #[pallet::weight(5_000)]
+        pub fn resolve_dispute(
+            origin: OriginFor<T>,
+            order_code: Vec<u8>,
+            institution_at_fault: bool,
+        ) -> DispatchResult {
+            // 确认调用者为公证裁决来源
+            T::ArbitrationOrigin::ensure_origin(origin)?;
+            
+            // 转换为边界向量
+            let bounded_order_code = BoundedVec::<u8, T::MaxOrderCodeLength>::try_from(order_code)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            // 获取并更新订单
+            Orders::<T>::try_mutate(&bounded_order_code, |maybe_order| -> DispatchResult {
+                let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+                
+                // 只有公证中的订单可以裁决
+                ensure!(order.status == OrderStatus::Notarizing, Error::<T>::InvalidStatusTransition);
+                
+                let (new_status, outcome) = if institution_at_fault {
+                    (OrderStatus::Cancelled, CommerceOutcome::C2cDisputeLost)
+                } else {
+                    (OrderStatus::Completed, CommerceOutcome::C2cOrderCompleted)
+                };
+                
+                // 更新状态
+                order.status = new_status.clone();
+                order.updated_time = frame_system::Pallet::<T>::block_number();
+                
+                // 更新状态订单索引
+                OrdersByStatus::<T>::mutate(OrderStatus::Notarizing, |orders| {
+                    orders.retain(|code| code != &bounded_order_code);
+                });
+                
+                OrdersByStatus::<T>::try_mutate(&new_status, |orders| -> DispatchResult {
+                    orders.try_push(bounded_order_code.clone())
+                        .map_err(|_| Error::<T>::StatusOrderListFull)?;
+                    Ok(())
+                })?;
+                
+                // 发出事件
+                T::OnCommerceOutcome::on_commerce_outcome(&order.institution_code, outcome);
+                Self::deposit_event(Event::DisputeResolved(bounded_order_code.clone(), institution_at_fault));
+                
+                Ok(())
+            })
+        }
+        
+        /// 强制取消未结束的订单，用于清理滞留订单，不影响机构信誉
+        #[pallet::call_index(6)]
+        #[pallet::weight(5_000)]
+        pub fn force_cancel_order(
+            origin: OriginFor<T>,
+            order_code: Vec<u8>,
+        ) -> DispatchResult {
+            // 确认调用者为特权来源
+            T::ArbitrationOrigin::ensure_origin(origin)?;
+            
+            // 转换为边界向量
+            let bounded_order_code = BoundedVec::<u8, T::MaxOrderCodeLength>::try_from(order_code)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            // 获取并更新订单
+            Orders::<T>::try_mutate(&bounded_order_code, |maybe_order| -> DispatchResult {
+                let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+                
+                // 已取消或已完成的订单不能再取消
+                ensure!(
+                    !matches!(order.status, OrderStatus::Cancelled | OrderStatus::Completed),
+                    Error::<T>::InvalidStatusTransition
+                );
+                
+                let old_status = order.status.clone();
+                
+                // 更新状态
+                order.status = OrderStatus::Cancelled;
+                order.updated_time = frame_system::Pallet::<T>::block_number();
+                
+                // 更新状态订单索引
+                OrdersByStatus::<T>::mutate(&old_status, |orders| {
+                    orders.retain(|code| code != &bounded_order_code);
+                });
+                
+                OrdersByStatus::<T>::try_mutate(OrderStatus::Cancelled, |orders| -> DispatchResult {
+                    orders.try_push(bounded_order_code.clone())
+                        .map_err(|_| Error::<T>::StatusOrderListFull)?;
+                    Ok(())
+                })?;
+                
+                // 发出事件
+                Self::deposit_event(Event::OrderCancelled(bounded_order_code.clone()));
+                
+                Ok(())
+            })
+        }
     }
     
     // 辅助函数
     impl<T: Config> Pallet<T> {
+        /// 确认调用者为订单创建者或拥有机构的订单处理权限
+        fn ensure_order_operator(order: &Order<T>, who: &T::AccountId) -> DispatchResult {
+            ensure!(
+                &order.creator == who
+                    || T::Institutions::has_role(&order.institution_code, who, InstitutionRole::OrderClerk),
+                Error::<T>::NotAuthorized
+            );
+            Ok(())
+        }
+        
+        /// 确认调用者拥有机构的订单处理权限
+        fn ensure_order_clerk(order: &Order<T>, who: &T::AccountId) -> DispatchResult {
+            ensure!(
+                T::Institutions::has_role(&order.institution_code, who, InstitutionRole::OrderClerk),
+                Error::<T>::NotAuthorized
+            );
+            Ok(())
+        }
+        
         /// 验证状态转换是否有效
         fn validate_status_transition(from: &OrderStatus, to: &OrderStatus) -> DispatchResult {
             use OrderStatus::*;
@@ -489,9 +632,7 @@ pub mod pallet {
                 (Paid, Delivered) | (Paid, Cancelled) | (Paid, Notarizing) => true,
                 // 已发货可以转换为已完成或公证中
                 (Delivered, Completed) | (Delivered, Notarizing) => true,
-                // 公证中可以转换为已完成或已取消
-                (Notarizing, Completed) | (Notarizing, Cancelled) => true,
-                // 其他转换无效
+                // 公证中的订单只能通过公证裁决结束；其他转换无效
                 _ => false,
             };
             
@@ -499,4 +640,27 @@ pub mod pallet {
             Ok(())
         }
     }
-} 
\ No newline at end of file
+    
+    impl<T: Config> pallet_institution::OnInstitutionRemoved for Pallet<T> {
+        /// 机构存在未完成或未取消的订单时拒绝删除；机构订单处理人员可取消待支付或已支付的订单，
+        /// 其他滞留订单由特权来源强制取消
+        fn can_remove(institution_id: &[u8]) -> DispatchResult {
+            let Ok(bounded_institution_code) = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec()) else {
+                return Ok(());
+            };
+            
+            let has_open_orders = InstitutionOrders::<T>::get(&bounded_institution_code).iter().any(|code| {
+                Orders::<T>::get(code).is_some_and(|order| {
+                    !matches!(order.status, OrderStatus::Cancelled | OrderStatus::Completed)
+                })
+            });
+            ensure!(!has_open_orders, Error::<T>::InstitutionHasOpenOrders);
+            Ok(())
+        }
+        
+        /// 已结束的订单作为买家的历史记录保留
+        fn on_institution_removed(_institution_id: &[u8], _limit: u32) -> u32 {
+            0
+        }
+    }
+}
\ No newline at end of file
@@ -7,6 +7,7 @@ pub use pallet::*;
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Get};
     use frame_system::pallet_prelude::*;
+    use pallet_institution::{InstitutionInspect, InstitutionRole, ReputationInspect};
     use scale_info::TypeInfo;
     use sp_std::prelude::*;
     use sp_std::vec::Vec;
@@ -32,6 +33,16 @@ pub mod pallet {
         /// 分类最大长度
         #[pallet::constant]
         type MaxCategoryLength: Get<u32>;
+        
+        /// 机构信息及成员权限查询
+        type Institutions: InstitutionInspect<Self::AccountId>;
+        
+        /// 机构信誉查询
+        type Reputation: ReputationInspect;
+        
+        /// 上架Token所需的最低机构信誉分
+        #[pallet::constant]
+        type MinListingScore: Get<u32>;
     }
 
     #[pallet::pallet]
@@ -103,6 +114,63 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 创世配置
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// 初始Token [token代码, 机构代码, 创建者, 名称, 分类, 价格, 交易方向(0出售/1购买), 库存数量]
+        #[allow(clippy::type_complexity)]
+        pub tokens: Vec<(Vec<u8>, Vec<u8>, T::AccountId, Vec<u8>, Vec<u8>, u128, u8, u32)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        /// 写入初始Token（上架状态）及机构、用户索引
+        fn build(&self) {
+            for (token_code, institution_code, creator, token_name, category, price, direction, stock_quantity) in &self.tokens {
+                let bounded_token_code = BoundedVec::<u8, T::MaxTokenCodeLength>::try_from(token_code.clone())
+                    .expect("genesis token code too long");
+                let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_code.clone())
+                    .expect("genesis institution code too long");
+
+                assert!(T::Institutions::exists(&bounded_institution_code), "genesis token references unknown institution");
+                assert!(
+                    !Tokens::<T>::contains_key(&bounded_token_code, &bounded_institution_code),
+                    "duplicate genesis token"
+                );
+                assert!(*price > 0, "genesis token price must be positive");
+
+                let token_trade_direction = match direction {
+                    0 => TokenTradableDirection::Sell,
+                    1 => TokenTradableDirection::Buy,
+                    _ => panic!("invalid genesis token trade direction"),
+                };
+
+                let token_info = TokenInfo::<T> {
+                    institution_code: bounded_institution_code.clone(),
+                    token_name: token_name.clone().try_into().expect("genesis token name too long"),
+                    category: category.clone().try_into().expect("genesis token category too long"),
+                    price: *price,
+                    token_trade_direction,
+                    stock_quantity: *stock_quantity,
+                    sales_quantity: 0,
+                    status: TokenStatus::Available,
+                    creator: creator.clone(),
+                };
+
+                Tokens::<T>::insert(&bounded_token_code, &bounded_institution_code, &token_info);
+                InstitutionTokens::<T>::mutate(&bounded_institution_code, |tokens| {
+                    tokens.try_push(bounded_token_code.clone()).expect("genesis institution token list full");
+                });
+                UserTokens::<T>::mutate(creator, |tokens| {
+                    tokens
+                        .try_push((bounded_token_code, bounded_institution_code))
+                        .expect("genesis user token list full");
+                });
+            }
+        }
+    }
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -144,6 +212,12 @@ pub mod pallet {
         InvalidPrice,
         /// 无效的交易方向
         InvalidTradeDirection,
+        /// 机构不存在
+        InstitutionNotFound,
+        /// 机构已暂停营业
+        InstitutionSuspended,
+        /// 机构信誉分低于上架要求
+        ReputationTooLow,
     }
 
     #[pallet::call]
@@ -171,6 +245,11 @@ pub mod pallet {
             let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_code)
                 .map_err(|_| Error::<T>::StringConversionError)?;
             
+            // 检查机构是否存在及调用者权限
+            ensure!(T::Institutions::exists(&bounded_institution_code), Error::<T>::InstitutionNotFound);
+            Self::ensure_token_manager(&bounded_institution_code, &who)?;
+            Self::ensure_listing_reputation(&bounded_institution_code)?;
+            
             // 检查Token是否已存在
             ensure!(!Tokens::<T>::contains_key(&bounded_token_code, &bounded_institution_code), 
                 Error::<T>::TokenAlreadyExists);
@@ -254,8 +333,8 @@ pub mod pallet {
             Tokens::<T>::try_mutate(&bounded_token_code, &bounded_institution_code, |maybe_token| -> DispatchResult {
                 let token = maybe_token.as_mut().ok_or(Error::<T>::TokenNotFound)?;
                 
-                // 检查权限
-                ensure!(token.creator == who, Error::<T>::NotAuthorized);
+                // 检查权限（机构商品管理人员）
+                Self::ensure_token_manager(&bounded_institution_code, &who)?;
                 
                 // 更新各字段（如果提供）
                 if let Some(name) = token_name {
@@ -311,8 +390,8 @@ pub mod pallet {
             Tokens::<T>::try_mutate(&bounded_token_code, &bounded_institution_code, |maybe_token| -> DispatchResult {
                 let token = maybe_token.as_mut().ok_or(Error::<T>::TokenNotFound)?;
                 
-                // 检查权限
-                ensure!(token.creator == who, Error::<T>::NotAuthorized);
+                // 检查权限（机构商品管理人员）
+                Self::ensure_token_manager(&bounded_institution_code, &who)?;
                 
                 // 转换状态
                 let new_status = match status {
@@ -321,6 +400,11 @@ pub mod pallet {
                     _ => return Err(Error::<T>::InvalidStatus.into()),
                 };
                 
+                // 重新上架需满足信誉要求
+                if new_status == TokenStatus::Available {
+                    Self::ensure_listing_reputation(&bounded_institution_code)?;
+                }
+                
                 // 更新状态
                 token.status = new_status;
                 
@@ -361,8 +445,8 @@ pub mod pallet {
             Tokens::<T>::try_mutate(&bounded_token_code, &bounded_institution_code, |maybe_token| -> DispatchResult {
                 let token = maybe_token.as_mut().ok_or(Error::<T>::TokenNotFound)?;
                 
-                // 检查权限
-                ensure!(token.creator == who, Error::<T>::NotAuthorized);
+                // 检查权限（机构商品管理人员）
+                Self::ensure_token_manager(&bounded_institution_code, &who)?;
                 
                 // 更新价格
                 token.price = new_price;
@@ -401,8 +485,8 @@ pub mod pallet {
             Tokens::<T>::try_mutate(&bounded_token_code, &bounded_institution_code, |maybe_token| -> DispatchResult {
                 let token = maybe_token.as_mut().ok_or(Error::<T>::TokenNotFound)?;
                 
-                // 检查权限
-                ensure!(token.creator == who, Error::<T>::NotAuthorized);
+                // 检查权限（机构商品管理人员）
+                Self::ensure_token_manager(&bounded_institution_code, &who)?;
                 
                 // 更新库存
                 token.stock_quantity = new_stock;
@@ -440,15 +524,15 @@ pub mod pallet {
             let token = Tokens::<T>::get(&bounded_token_code, &bounded_institution_code)
                 .ok_or(Error::<T>::TokenNotFound)?;
             
-            ensure!(token.creator == who, Error::<T>::NotAuthorized);
+            Self::ensure_token_manager(&bounded_institution_code, &who)?;
             
             // 从机构Token索引中移除
             InstitutionTokens::<T>::mutate(&bounded_institution_code, |tokens| {
                 tokens.retain(|code| code != &bounded_token_code);
             });
             
-            // 从用户Token索引中移除
-            UserTokens::<T>::mutate(&who, |tokens| {
+            // 从创建者的用户Token索引中移除
+            UserTokens::<T>::mutate(&token.creator, |tokens| {
                 tokens.retain(|(code, inst)| code != &bounded_token_code || inst != &bounded_institution_code);
             });
             
@@ -480,6 +564,9 @@ pub mod pallet {
             let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_code)
                 .map_err(|_| Error::<T>::StringConversionError)?;
             
+            // 暂停营业的机构不能交易
+            ensure!(!T::Institutions::is_suspended(&bounded_institution_code), Error::<T>::InstitutionSuspended);
+            
             // 获取并更新Token信息
             Tokens::<T>::try_mutate(&bounded_token_code, &bounded_institution_code, |maybe_token| -> DispatchResult {
                 let token = maybe_token.as_mut().ok_or(Error::<T>::TokenNotFound)?;
@@ -533,4 +620,63 @@ pub mod pallet {
             Ok(())
         }
     }
-} 
\ No newline at end of file
+    
+    // 辅助函数
+    impl<T: Config> Pallet<T> {
+        /// 确认调用者拥有机构的商品管理权限
+        fn ensure_token_manager(
+            institution_code: &BoundedVec<u8, T::MaxInstitutionCodeLength>,
+            who: &T::AccountId,
+        ) -> DispatchResult {
+            ensure!(
+                T::Institutions::has_role(institution_code, who, InstitutionRole::ProductManager),
+                Error::<T>::NotAuthorized
+            );
+            Ok(())
+        }
+        
+        /// 确认机构信誉分达到上架要求
+        fn ensure_listing_reputation(institution_code: &BoundedVec<u8, T::MaxInstitutionCodeLength>) -> DispatchResult {
+            ensure!(
+                T::Reputation::score(institution_code) >= T::MinListingScore::get(),
+                Error::<T>::ReputationTooLow
+            );
+            Ok(())
+        }
+    }
+    
+    impl<T: Config> pallet_institution::OnInstitutionRemoved for Pallet<T> {
+        fn can_remove(_institution_id: &[u8]) -> DispatchResult {
+            Ok(())
+        }
+        
+        /// 分批删除机构的Token及其创建者索引
+        fn on_institution_removed(institution_id: &[u8], limit: u32) -> u32 {
+            let Ok(bounded_institution_code) = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_id.to_vec()) else {
+                return 0;
+            };
+            
+            let mut token_codes = InstitutionTokens::<T>::get(&bounded_institution_code);
+            let mut removed = 0u32;
+            while removed < limit {
+                let Some(token_code) = token_codes.pop() else {
+                    break;
+                };
+                if let Some(token) = Tokens::<T>::take(&token_code, &bounded_institution_code) {
+                    UserTokens::<T>::mutate(&token.creator, |tokens| {
+                        tokens.retain(|(code, inst)| code != &token_code || inst != &bounded_institution_code);
+                    });
+                }
+                Self::deposit_event(Event::TokenDeleted(token_code, bounded_institution_code.clone()));
+                removed += 1;
+            }
+            
+            if token_codes.is_empty() {
+                InstitutionTokens::<T>::remove(&bounded_institution_code);
+            } else {
+                InstitutionTokens::<T>::insert(&bounded_institution_code, token_codes);
+            }
+            removed
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,349 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// 承运商管理模块
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// 供其他业务模块调用的承运商查询接口
+pub trait CarrierInspect {
+    /// 承运商编码是否已登记
+    fn exists(carrier_code: &[u8]) -> bool;
+
+    /// 快递单号是否符合承运商登记的单号规则
+    fn is_valid_tracking_number(carrier_code: &[u8], tracking_number: &[u8]) -> bool;
+
+    /// 承运商是否支持货到付款
+    fn supports_cod(carrier_code: &[u8]) -> bool;
+}
+
+impl CarrierInspect for () {
+    fn exists(_carrier_code: &[u8]) -> bool {
+        true
+    }
+
+    fn is_valid_tracking_number(_carrier_code: &[u8], _tracking_number: &[u8]) -> bool {
+        true
+    }
+
+    fn supports_cod(_carrier_code: &[u8]) -> bool {
+        false
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use codec::DecodeWithMemTracking;
+    use frame_support::{pallet_prelude::*, traits::Get};
+    use frame_system::pallet_prelude::*;
+    use scale_info::TypeInfo;
+    use sp_std::prelude::*;
+    use sp_std::vec::Vec;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + scale_info::TypeInfo {
+        /// 事件类型
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// 承运商编码最大长度
+        #[pallet::constant]
+        type MaxCarrierCodeLength: Get<u32>;
+
+        /// 承运商名称最大长度
+        #[pallet::constant]
+        type MaxCarrierNameLength: Get<u32>;
+
+        /// 单号前缀最大长度
+        #[pallet::constant]
+        type MaxTrackingPrefixLength: Get<u32>;
+
+        /// 单个承运商的最大单号前缀数
+        #[pallet::constant]
+        type MaxTrackingPrefixes: Get<u32>;
+
+        /// 维护承运商名录的特权来源
+        type CarrierOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// 承运商编码
+    pub type CarrierCode<T> = BoundedVec<u8, <T as Config>::MaxCarrierCodeLength>;
+
+    /// 单号前缀
+    pub type TrackingPrefix<T> = BoundedVec<u8, <T as Config>::MaxTrackingPrefixLength>;
+
+    /// 单号字符集，适用于去掉前缀后的部分
+    #[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum TrackingCharset {
+        /// 仅数字
+        Numeric,
+        /// 大写字母及数字
+        UppercaseAlphanumeric,
+        /// 字母及数字
+        Alphanumeric,
+    }
+
+    impl TrackingCharset {
+        /// 字符是否属于本字符集
+        pub fn contains(self, byte: u8) -> bool {
+            match self {
+                TrackingCharset::Numeric => byte.is_ascii_digit(),
+                TrackingCharset::UppercaseAlphanumeric => byte.is_ascii_digit() || byte.is_ascii_uppercase(),
+                TrackingCharset::Alphanumeric => byte.is_ascii_alphanumeric(),
+            }
+        }
+    }
+
+    /// 快递单号规则
+    #[derive(CloneNoBound, Encode, Decode, DecodeWithMemTracking, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct TrackingNumberPattern<T: Config> {
+        pub min_length: u32,                                                // 最短长度（含前缀）
+        pub max_length: u32,                                                // 最长长度（含前缀）
+        pub prefixes: BoundedVec<TrackingPrefix<T>, T::MaxTrackingPrefixes>, // 允许的前缀，为空时不限
+        pub charset: TrackingCharset,                                       // 前缀之后部分的字符集
+    }
+
+    impl<T: Config> TrackingNumberPattern<T> {
+        /// 单号长度在范围内、以任一允许的前缀开头，且其余部分均属于字符集
+        pub fn matches(&self, tracking_number: &[u8]) -> bool {
+            let length = tracking_number.len() as u32;
+            if length < self.min_length || length > self.max_length {
+                return false;
+            }
+
+            let rest = if self.prefixes.is_empty() {
+                Some(tracking_number)
+            } else {
+                self.prefixes.iter().find_map(|prefix| tracking_number.strip_prefix(prefix.as_slice()))
+            };
+            rest.is_some_and(|rest| rest.iter().all(|byte| self.charset.contains(*byte)))
+        }
+
+        /// 长度范围有效且前缀不超过最长长度
+        pub fn is_well_formed(&self) -> bool {
+            self.min_length > 0
+                && self.min_length <= self.max_length
+                && self.prefixes.iter().all(|prefix| !prefix.is_empty() && prefix.len() as u32 <= self.max_length)
+        }
+    }
+
+    /// 承运商信息
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct Carrier<T: Config> {
+        pub name: BoundedVec<u8, T::MaxCarrierNameLength>, // 显示名称
+        pub tracking_pattern: TrackingNumberPattern<T>,    // 快递单号规则
+        pub supports_cod: bool,                            // 是否支持货到付款
+    }
+
+    /// 承运商存储映射
+    #[pallet::storage]
+    #[pallet::storage_prefix = "Carriers"]
+    pub type Carriers<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        CarrierCode<T>, // 主键：承运商编码
+        Carrier<T>,     // 值：承运商信息
+        OptionQuery,    // 查询策略：如果键不存在，返回 None
+    >;
+
+    /// 创世配置
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// 初始承运商 [承运商编码, 显示名称, 单号最短长度, 单号最长长度, 单号前缀列表,
+        /// 字符集(0数字/1大写字母及数字/2字母及数字), 是否支持货到付款]
+        #[allow(clippy::type_complexity)]
+        pub carriers: Vec<(Vec<u8>, Vec<u8>, u32, u32, Vec<Vec<u8>>, u8, bool)>,
+        #[serde(skip)]
+        pub _config: core::marker::PhantomData<T>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        /// 登记初始承运商
+        fn build(&self) {
+            for (code, name, min_length, max_length, prefixes, charset, supports_cod) in &self.carriers {
+                let code = CarrierCode::<T>::try_from(code.clone()).expect("genesis carrier code too long");
+                assert!(!Carriers::<T>::contains_key(&code), "duplicate genesis carrier");
+
+                let tracking_pattern = TrackingNumberPattern::<T> {
+                    min_length: *min_length,
+                    max_length: *max_length,
+                    prefixes: prefixes
+                        .iter()
+                        .map(|prefix| TrackingPrefix::<T>::try_from(prefix.clone()).expect("genesis tracking prefix too long"))
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .expect("too many genesis tracking prefixes"),
+                    charset: match charset {
+                        0 => TrackingCharset::Numeric,
+                        1 => TrackingCharset::UppercaseAlphanumeric,
+                        2 => TrackingCharset::Alphanumeric,
+                        _ => panic!("invalid genesis tracking charset"),
+                    },
+                };
+                assert!(tracking_pattern.is_well_formed(), "invalid genesis tracking number pattern");
+
+                Carriers::<T>::insert(
+                    &code,
+                    Carrier {
+                        name: name.clone().try_into().expect("genesis carrier name too long"),
+                        tracking_pattern,
+                        supports_cod: *supports_cod,
+                    },
+                );
+            }
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// 承运商已登记 [承运商编码]
+        CarrierRegistered(CarrierCode<T>),
+        /// 承运商信息已更新 [承运商编码]
+        CarrierUpdated(CarrierCode<T>),
+        /// 承运商已删除 [承运商编码]
+        CarrierRemoved(CarrierCode<T>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// 字符串转换错误
+        StringConversionError,
+        /// 承运商已存在
+        CarrierAlreadyExists,
+        /// 承运商不存在
+        CarrierNotFound,
+        /// 无效的单号规则（长度范围为空或前缀超出最长长度）
+        InvalidTrackingPattern,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// 登记承运商
+        #[pallet::call_index(0)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn register_carrier(
+            origin: OriginFor<T>,
+            carrier_code: Vec<u8>,
+            name: Vec<u8>,
+            tracking_pattern: TrackingNumberPattern<T>,
+            supports_cod: bool,
+        ) -> DispatchResult {
+            // 检查特权来源
+            T::CarrierOrigin::ensure_origin(origin)?;
+
+            // 转换为边界向量
+            let bounded_code = CarrierCode::<T>::try_from(carrier_code).map_err(|_| Error::<T>::StringConversionError)?;
+            let bounded_name = BoundedVec::<u8, T::MaxCarrierNameLength>::try_from(name)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+
+            ensure!(!Carriers::<T>::contains_key(&bounded_code), Error::<T>::CarrierAlreadyExists);
+            ensure!(tracking_pattern.is_well_formed(), Error::<T>::InvalidTrackingPattern);
+
+            Carriers::<T>::insert(&bounded_code, Carrier { name: bounded_name, tracking_pattern, supports_cod });
+
+            // 发出事件
+            Self::deposit_event(Event::CarrierRegistered(bounded_code));
+
+            Ok(())
+        }
+
+        /// 更新承运商信息
+        #[pallet::call_index(1)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn update_carrier(
+            origin: OriginFor<T>,
+            carrier_code: Vec<u8>,
+            name: Option<Vec<u8>>,
+            tracking_pattern: Option<TrackingNumberPattern<T>>,
+            supports_cod: Option<bool>,
+        ) -> DispatchResult {
+            // 检查特权来源
+            T::CarrierOrigin::ensure_origin(origin)?;
+
+            // 转换为边界向量
+            let bounded_code = CarrierCode::<T>::try_from(carrier_code).map_err(|_| Error::<T>::StringConversionError)?;
+
+            Carriers::<T>::try_mutate(&bounded_code, |maybe_carrier| -> DispatchResult {
+                let carrier = maybe_carrier.as_mut().ok_or(Error::<T>::CarrierNotFound)?;
+
+                // 更新各字段（如果提供）
+                if let Some(name) = name {
+                    carrier.name = BoundedVec::<u8, T::MaxCarrierNameLength>::try_from(name)
+                        .map_err(|_| Error::<T>::StringConversionError)?;
+                }
+
+                if let Some(pattern) = tracking_pattern {
+                    ensure!(pattern.is_well_formed(), Error::<T>::InvalidTrackingPattern);
+                    carrier.tracking_pattern = pattern;
+                }
+
+                if let Some(cod) = supports_cod {
+                    carrier.supports_cod = cod;
+                }
+
+                Ok(())
+            })?;
+
+            // 发出事件
+            Self::deposit_event(Event::CarrierUpdated(bounded_code));
+
+            Ok(())
+        }
+
+        /// 删除承运商，已发货订单中的承运商编码保留
+        #[pallet::call_index(2)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn remove_carrier(origin: OriginFor<T>, carrier_code: Vec<u8>) -> DispatchResult {
+            // 检查特权来源
+            T::CarrierOrigin::ensure_origin(origin)?;
+
+            // 转换为边界向量
+            let bounded_code = CarrierCode::<T>::try_from(carrier_code).map_err(|_| Error::<T>::StringConversionError)?;
+
+            ensure!(Carriers::<T>::contains_key(&bounded_code), Error::<T>::CarrierNotFound);
+            Carriers::<T>::remove(&bounded_code);
+
+            // 发出事件
+            Self::deposit_event(Event::CarrierRemoved(bounded_code));
+
+            Ok(())
+        }
+    }
+
+    // 辅助函数
+    impl<T: Config> Pallet<T> {
+        /// 按承运商编码查询承运商信息
+        fn carrier(carrier_code: &[u8]) -> Option<Carrier<T>> {
+            let bounded_code = CarrierCode::<T>::try_from(carrier_code.to_vec()).ok()?;
+            Carriers::<T>::get(&bounded_code)
+        }
+    }
+
+    impl<T: Config> crate::CarrierInspect for Pallet<T> {
+        fn exists(carrier_code: &[u8]) -> bool {
+            Self::carrier(carrier_code).is_some()
+        }
+
+        fn is_valid_tracking_number(carrier_code: &[u8], tracking_number: &[u8]) -> bool {
+            Self::carrier(carrier_code).is_some_and(|carrier| carrier.tracking_pattern.matches(tracking_number))
+        }
+
+        fn supports_cod(carrier_code: &[u8]) -> bool {
+            Self::carrier(carrier_code).is_some_and(|carrier| carrier.supports_cod)
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate as pallet_carrier;
+use frame_support::{derive_impl, traits::ConstU32};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    #[runtime::pallet_index(1)]
+    pub type Carrier = pallet_carrier::Pallet<Test>;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+impl pallet_carrier::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxCarrierCodeLength = ConstU32<16>;
+    type MaxCarrierNameLength = ConstU32<64>;
+    type MaxTrackingPrefixLength = ConstU32<4>;
+    type MaxTrackingPrefixes = ConstU32<3>;
+    type CarrierOrigin = EnsureRoot<u64>;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    // 顺丰：SF 开头的 12 至 15 位大写字母及数字；邮政：13 位数字，支持货到付款
+    pallet_carrier::GenesisConfig::<Test> {
+        carriers: vec![
+            (b"SF".to_vec(), b"SF Express".to_vec(), 12, 15, vec![b"SF".to_vec()], 1, false),
+            (b"EMS".to_vec(), b"China Post EMS".to_vec(), 13, 13, vec![], 0, true),
+        ],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = storage.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
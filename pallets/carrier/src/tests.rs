@@ -0,0 +1,144 @@
+use crate::{mock::*, CarrierCode, CarrierInspect, Carriers, Error, Event, TrackingCharset, TrackingNumberPattern};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::DispatchError;
+
+fn code(code: &[u8]) -> CarrierCode<Test> {
+    CarrierCode::<Test>::try_from(code.to_vec()).unwrap()
+}
+
+fn pattern(min_length: u32, max_length: u32, prefixes: &[&[u8]], charset: TrackingCharset) -> TrackingNumberPattern<Test> {
+    TrackingNumberPattern {
+        min_length,
+        max_length,
+        prefixes: BoundedVec::try_from(
+            prefixes.iter().map(|prefix| BoundedVec::try_from(prefix.to_vec()).unwrap()).collect::<Vec<_>>(),
+        )
+        .unwrap(),
+        charset,
+    }
+}
+
+#[test]
+fn pattern_checks_length_bounds() {
+    let numeric = pattern(4, 6, &[], TrackingCharset::Numeric);
+    assert!(!numeric.matches(b"123"));
+    assert!(numeric.matches(b"1234"));
+    assert!(numeric.matches(b"123456"));
+    assert!(!numeric.matches(b"1234567"));
+}
+
+#[test]
+fn pattern_checks_prefix_and_charset() {
+    let prefixed = pattern(6, 8, &[b"SF", b"JD"], TrackingCharset::Numeric);
+    assert!(prefixed.matches(b"SF1234"));
+    assert!(prefixed.matches(b"JD123456"));
+    // 前缀不在列表中或前缀之后含非数字字符
+    assert!(!prefixed.matches(b"YT1234"));
+    assert!(!prefixed.matches(b"SF12A4"));
+    // 长度包含前缀
+    assert!(!prefixed.matches(b"SF123"));
+
+    let uppercase = pattern(4, 8, &[], TrackingCharset::UppercaseAlphanumeric);
+    assert!(uppercase.matches(b"AB12CD"));
+    assert!(!uppercase.matches(b"ab12cd"));
+    assert!(!uppercase.matches(b"AB-12CD"));
+
+    let alphanumeric = pattern(4, 8, &[], TrackingCharset::Alphanumeric);
+    assert!(alphanumeric.matches(b"ab12CD"));
+    assert!(!alphanumeric.matches(b"ab 12CD"));
+}
+
+#[test]
+fn pattern_well_formedness() {
+    assert!(pattern(1, 1, &[], TrackingCharset::Numeric).is_well_formed());
+    assert!(pattern(2, 4, &[b"SF"], TrackingCharset::Numeric).is_well_formed());
+    // 长度范围为空
+    assert!(!pattern(0, 4, &[], TrackingCharset::Numeric).is_well_formed());
+    assert!(!pattern(5, 4, &[], TrackingCharset::Numeric).is_well_formed());
+    // 空前缀或前缀超出最长长度
+    assert!(!pattern(2, 4, &[b""], TrackingCharset::Numeric).is_well_formed());
+    assert!(!pattern(1, 2, &[b"SFX"], TrackingCharset::Numeric).is_well_formed());
+}
+
+#[test]
+fn root_maintains_carriers() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Carrier::register_carrier(
+            RuntimeOrigin::root(),
+            b"YTO".to_vec(),
+            b"YTO Express".to_vec(),
+            pattern(10, 12, &[b"YT"], TrackingCharset::Numeric),
+            false,
+        ));
+        System::assert_last_event(Event::CarrierRegistered(code(b"YTO")).into());
+        assert!(Carrier::exists(b"YTO"));
+
+        assert_ok!(Carrier::update_carrier(RuntimeOrigin::root(), b"YTO".to_vec(), None, None, Some(true)));
+        System::assert_last_event(Event::CarrierUpdated(code(b"YTO")).into());
+        assert!(Carrier::supports_cod(b"YTO"));
+
+        assert_ok!(Carrier::remove_carrier(RuntimeOrigin::root(), b"YTO".to_vec()));
+        System::assert_last_event(Event::CarrierRemoved(code(b"YTO")).into());
+        assert!(!Carriers::<Test>::contains_key(code(b"YTO")));
+    });
+}
+
+#[test]
+fn invalid_carrier_changes_are_rejected() {
+    new_test_ext().execute_with(|| {
+        let valid = pattern(10, 12, &[], TrackingCharset::Numeric);
+        assert_noop!(
+            Carrier::register_carrier(RuntimeOrigin::signed(1), b"YTO".to_vec(), b"YTO".to_vec(), valid.clone(), false),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Carrier::register_carrier(RuntimeOrigin::root(), b"SF".to_vec(), b"SF".to_vec(), valid.clone(), false),
+            Error::<Test>::CarrierAlreadyExists
+        );
+        assert_noop!(
+            Carrier::register_carrier(
+                RuntimeOrigin::root(),
+                b"YTO".to_vec(),
+                b"YTO".to_vec(),
+                pattern(12, 10, &[], TrackingCharset::Numeric),
+                false,
+            ),
+            Error::<Test>::InvalidTrackingPattern
+        );
+        assert_noop!(
+            Carrier::update_carrier(
+                RuntimeOrigin::root(),
+                b"SF".to_vec(),
+                None,
+                Some(pattern(0, 10, &[], TrackingCharset::Numeric)),
+                None,
+            ),
+            Error::<Test>::InvalidTrackingPattern
+        );
+        assert_noop!(
+            Carrier::update_carrier(RuntimeOrigin::root(), b"YTO".to_vec(), None, Some(valid), None),
+            Error::<Test>::CarrierNotFound
+        );
+        assert_noop!(Carrier::remove_carrier(RuntimeOrigin::root(), b"YTO".to_vec()), Error::<Test>::CarrierNotFound);
+        assert_noop!(Carrier::remove_carrier(RuntimeOrigin::signed(1), b"SF".to_vec()), DispatchError::BadOrigin);
+    });
+}
+
+#[test]
+fn inspect_checks_registered_patterns() {
+    new_test_ext().execute_with(|| {
+        assert!(Carrier::exists(b"SF"));
+        assert!(!Carrier::exists(b"YTO"));
+
+        assert!(Carrier::is_valid_tracking_number(b"SF", b"SF1234567890"));
+        assert!(!Carrier::is_valid_tracking_number(b"SF", b"1234567890"));
+        assert!(Carrier::is_valid_tracking_number(b"EMS", b"1234567890123"));
+        assert!(!Carrier::is_valid_tracking_number(b"EMS", b"123456789012"));
+        // 未登记的承运商没有可匹配的单号规则
+        assert!(!Carrier::is_valid_tracking_number(b"YTO", b"1234567890123"));
+
+        assert!(Carrier::supports_cod(b"EMS"));
+        assert!(!Carrier::supports_cod(b"SF"));
+        assert!(!Carrier::supports_cod(b"YTO"));
+    });
+}
@@ -3,183 +3,1096 @@
 /// 机构运费模板模块
 pub use pallet::*;
 
+pub mod weights;
+pub use weights::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod migrations;
+pub mod runtime_api;
+
+/// 参与运费计算的商品项
+#[derive(
+    Clone, Copy, codec::Encode, codec::Decode, codec::DecodeWithMemTracking, Eq, PartialEq, frame_support::pallet_prelude::RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct FreightItem {
+    pub quantity: u32,    // 数量
+    pub unit_weight: u64, // 单件重量
+    pub unit_volume: u64, // 单件体积（立方厘米）
+    pub unit_price: u64,  // 单价
+}
+
+/// 运费计算结果
+#[derive(
+    Clone, Copy, codec::Encode, codec::Decode, Eq, PartialEq, frame_support::pallet_prelude::RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct FreightQuote {
+    pub fee: u64,     // 运费
+    pub version: u32, // 计价所用的模板版本
+}
+
+/// 供其他业务模块调用的运费计算接口
+pub trait FreightCalculator {
+    /// 按机构运费模板的当前版本计算发往目的地区的运费（含偏远地区加价），目的地区不在配送范围内时返回错误
+    fn calculate_freight(
+        institution_id: &[u8],
+        template_id: &[u8],
+        destination_region: &[u8],
+        items: &[FreightItem],
+    ) -> Result<FreightQuote, frame_support::sp_runtime::DispatchError>;
+
+    /// 机构是否设置了运费模板，设置后下单必须按模板计算运费
+    fn has_templates(institution_id: &[u8]) -> bool;
+
+    /// 订单引用模板版本，引用期间该版本保持可读
+    fn retain_version(institution_id: &[u8], template_id: &[u8], version: u32);
+
+    /// 释放订单对模板版本的引用，历史版本不再被引用时删除
+    fn release_version(institution_id: &[u8], template_id: &[u8], version: u32);
+}
+
+impl FreightCalculator for () {
+    fn calculate_freight(
+        _institution_id: &[u8],
+        _template_id: &[u8],
+        _destination_region: &[u8],
+        _items: &[FreightItem],
+    ) -> Result<FreightQuote, frame_support::sp_runtime::DispatchError> {
+        Ok(FreightQuote { fee: 0, version: 0 })
+    }
+
+    fn has_templates(_institution_id: &[u8]) -> bool {
+        false
+    }
+
+    fn retain_version(_institution_id: &[u8], _template_id: &[u8], _version: u32) {}
+
+    fn release_version(_institution_id: &[u8], _template_id: &[u8], _version: u32) {}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::{pallet_prelude::*, traits::Get};
+    use crate::{FreightItem, FreightQuote};
+    use crate::WeightInfo;
+    use codec::DecodeWithMemTracking;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::{Hash as HashT, Saturating},
+        traits::{
+            fungible::{Inspect, MutateHold},
+            tokens::Precision,
+            Get,
+        },
+    };
     use frame_system::pallet_prelude::*;
+    use pallet_institution::{InstitutionInspect, InstitutionRole};
+    use pallet_region::RegionInspect;
     use scale_info::TypeInfo;
     use sp_std::prelude::*;
     use sp_std::vec::Vec;
 
+    /// 押金余额类型
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// 当前存储版本
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
+
     #[pallet::config]
     pub trait Config: frame_system::Config + scale_info::TypeInfo {
         /// 事件类型
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-        
-        /// 自定义区域最大长度
+
+        /// 机构ID最大长度
+        #[pallet::constant]
+        type MaxInstitutionIdLength: Get<u32>;
+
+        /// 模板ID最大长度
+        #[pallet::constant]
+        type MaxTemplateIdLength: Get<u32>;
+
+        /// 模板名称最大长度
+        #[pallet::constant]
+        type MaxTemplateNameLength: Get<u32>;
+
+        /// 地区编码最大长度
+        #[pallet::constant]
+        type MaxRegionCodeLength: Get<u32>;
+
+        /// 单条地区规则覆盖的最大地区数
+        #[pallet::constant]
+        type MaxRegionsPerRule: Get<u32>;
+
+        /// 单个模板的最大地区规则数
+        #[pallet::constant]
+        type MaxRegionalRules: Get<u32>;
+
+        /// 单条规则的最大包邮条件数
         #[pallet::constant]
-        type MaxCustomAreaLength: Get<u32>;
+        type MaxFreeShippingConditions: Get<u32>;
+
+        /// 单个模板的最大不配送地区数
+        #[pallet::constant]
+        type MaxExcludedRegions: Get<u32>;
+
+        /// 单个模板的最大偏远地区加价规则数
+        #[pallet::constant]
+        type MaxSurchargeRules: Get<u32>;
+
+        /// 机构信息及成员权限查询
+        type Institutions: InstitutionInspect<Self::AccountId>;
+
+        /// 行政区划地区库查询，用于校验地区编码及按上级地区匹配规则
+        type Regions: RegionInspect;
+
+        /// 用于冻结存储押金的货币
+        type Currency: MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// 运行时冻结原因
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// 创建运费模板的基础押金
+        #[pallet::constant]
+        type DepositBase: Get<BalanceOf<Self>>;
+
+        /// 运费模板每字节押金
+        #[pallet::constant]
+        type DepositPerByte: Get<BalanceOf<Self>>;
+
+        /// 可调用函数的权重
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
+    /// 本模块的资金冻结原因
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// 运费模板存储押金
+        #[codec(index = 0)]
+        FreightTemplateDeposit,
+    }
+
+    /// 地区编码
+    pub type RegionCode<T> = BoundedVec<u8, <T as Config>::MaxRegionCodeLength>;
+
+    /// 创世配置中的计费规则 [首件/首重, 首费, 续件/续重步长, 续费, 包邮金额门槛, 包邮件数门槛]
+    pub type GenesisRule = (u64, u64, u64, u64, Option<u64>, Option<u32>);
+
+    /// 创世配置中的加价规则 [地区编码列表, 加价方式(0固定金额/1比例), 加价金额或基点]
+    pub type GenesisSurcharge = (Vec<Vec<u8>>, u8, u64);
+
+    /// 计费方式
+    #[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum PricingMode {
+        /// 按重量计费
+        ByWeight,
+        /// 按件数计费
+        ByPieces,
+        /// 按体积重量计费：体积除以 `divisor` 得到体积重量，与实际重量取较大者
+        ByVolume { divisor: u32 },
+    }
+
+    /// 包邮条件，满足任一条件即免运费
+    #[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum FreeShippingCondition {
+        /// 订单金额不低于指定值
+        MinAmount(u64),
+        /// 商品件数不低于指定值
+        MinQuantity(u32),
+    }
+
+    /// 计费规则
+    #[derive(CloneNoBound, Encode, Decode, DecodeWithMemTracking, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct FreightRule<T: Config> {
+        pub first_unit: u64,                                                              // 首件/首重
+        pub first_fee: u64,                                                               // 首费
+        pub additional_unit: u64,                                                         // 续件/续重步长
+        pub additional_fee: u64,                                                          // 每步续费
+        pub free_shipping: BoundedVec<FreeShippingCondition, T::MaxFreeShippingConditions>, // 包邮条件
+    }
+
+    impl<T: Config> FreightRule<T> {
+        /// 按计费量（重量、件数或体积重量）、件数及金额计算运费
+        ///
+        /// 满足任一包邮条件或没有商品时为 0；不超过首件/首重收取首费，
+        /// 超出部分按续件/续重步长向上取整，每步收取续费。
+        pub fn fee(&self, units: u64, quantity: u32, amount: u64) -> u64 {
+            let free = self.free_shipping.iter().any(|condition| match *condition {
+                FreeShippingCondition::MinAmount(min) => amount >= min,
+                FreeShippingCondition::MinQuantity(min) => quantity >= min,
+            });
+            if free || quantity == 0 {
+                return 0;
+            }
+
+            let steps = units.saturating_sub(self.first_unit).div_ceil(self.additional_unit.max(1));
+            self.first_fee.saturating_add(steps.saturating_mul(self.additional_fee))
+        }
+    }
+
+    /// 地区规则，覆盖所列地区的默认规则
+    #[derive(CloneNoBound, Encode, Decode, DecodeWithMemTracking, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct RegionalRule<T: Config> {
+        pub regions: BoundedVec<RegionCode<T>, T::MaxRegionsPerRule>, // 适用地区编码
+        pub rule: FreightRule<T>,                                     // 计费规则
+    }
+
+    /// 比例加价的基点基数，10_000 基点为 100%
+    pub const BASIS_POINTS: u32 = 10_000;
+
+    /// 偏远地区加价方式
+    #[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum Surcharge {
+        /// 固定加价，包邮时仍然收取
+        Flat(u64),
+        /// 按基础运费的比例加价，单位为基点（10_000 为 100%），可超过 100%
+        Percentage(u32),
+    }
+
+    impl Surcharge {
+        /// 在基础运费之上的加价金额，比例加价向上取整
+        pub fn amount(&self, base_fee: u64) -> u64 {
+            match *self {
+                Surcharge::Flat(fee) => fee,
+                Surcharge::Percentage(basis_points) => {
+                    let amount = (u128::from(base_fee) * u128::from(basis_points)).div_ceil(u128::from(BASIS_POINTS));
+                    u64::try_from(amount).unwrap_or(u64::MAX)
+                },
+            }
+        }
+    }
+
+    /// 偏远地区加价规则，适用于所列地区及其下级地区
+    #[derive(CloneNoBound, Encode, Decode, DecodeWithMemTracking, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct SurchargeRule<T: Config> {
+        pub regions: BoundedVec<RegionCode<T>, T::MaxRegionsPerRule>, // 适用地区编码
+        pub surcharge: Surcharge,                                     // 加价方式
+    }
+
     /// 运费模板结构
-    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
     pub struct FreightTemplate<T: Config> {
-        pub area: BoundedVec<u8, T::MaxCustomAreaLength>,
-        pub first_weight: u64,
-        pub first_weight_fee: u64,
-        pub additional_weight_fee: u64,
-        pub creator: T::AccountId,
+        pub name: BoundedVec<u8, T::MaxTemplateNameLength>,              // 模板名称
+        pub pricing_mode: PricingMode,                                   // 计费方式
+        pub default_rule: FreightRule<T>,                                // 默认规则
+        pub regional_rules: BoundedVec<RegionalRule<T>, T::MaxRegionalRules>, // 地区规则
+        pub excluded_regions: BoundedVec<RegionCode<T>, T::MaxExcludedRegions>, // 不配送地区
+        pub surcharges: BoundedVec<SurchargeRule<T>, T::MaxSurchargeRules>,    // 偏远地区加价规则
+        pub creator: T::AccountId,                                       // 创建者
+    }
+
+    impl<T: Config> FreightTemplate<T> {
+        /// 按计费方式汇总商品的计费量
+        pub fn chargeable_units(&self, items: &[FreightItem]) -> u64 {
+            let total = |unit: fn(&FreightItem) -> u64| {
+                items.iter().fold(0u64, |sum, item| sum.saturating_add(unit(item).saturating_mul(item.quantity.into())))
+            };
+            match self.pricing_mode {
+                PricingMode::ByWeight => total(|item| item.unit_weight),
+                PricingMode::ByPieces => total(|_| 1),
+                PricingMode::ByVolume { divisor } => {
+                    let volumetric = total(|item| item.unit_volume).div_ceil(u64::from(divisor).max(1));
+                    total(|item| item.unit_weight).max(volumetric)
+                },
+            }
+        }
+
+        /// 按目的地区及其各级上级地区（由具体到粗略）依次匹配地区规则，均未覆盖时使用默认规则
+        pub fn rule_for(&self, lineage: &[Vec<u8>]) -> &FreightRule<T> {
+            lineage
+                .iter()
+                .find_map(|region| {
+                    self.regional_rules
+                        .iter()
+                        .find(|regional| regional.regions.iter().any(|code| code.as_slice() == region.as_slice()))
+                })
+                .map_or(&self.default_rule, |regional| &regional.rule)
+        }
+
+        /// 目的地区或其任一上级地区是否被列为不配送地区
+        pub fn is_excluded(&self, lineage: &[Vec<u8>]) -> bool {
+            lineage.iter().any(|region| self.excluded_regions.iter().any(|code| code.as_slice() == region.as_slice()))
+        }
+
+        /// 按目的地区及其各级上级地区（由具体到粗略）依次匹配加价规则
+        pub fn surcharge_for(&self, lineage: &[Vec<u8>]) -> Option<&Surcharge> {
+            lineage.iter().find_map(|region| {
+                self.surcharges
+                    .iter()
+                    .find(|rule| rule.regions.iter().any(|code| code.as_slice() == region.as_slice()))
+                    .map(|rule| &rule.surcharge)
+            })
+        }
     }
 
-    /// 运费模板存储映射
+    /// 运费模板存储映射，主键为机构 ID 和模板 ID
     #[pallet::storage]
     #[pallet::storage_prefix = "FreightTemplates"]
-    pub type FreightTemplates<T: Config> = StorageMap<
+    pub type FreightTemplates<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构ID
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxTemplateIdLength>,    // 次键：模板ID
+        FreightTemplate<T>,                        // 值：运费模板
+        OptionQuery,                               // 查询策略：如果键不存在，返回 None
+    >;
+
+    /// 运费模板版本信息
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct TemplateVersion<Hash> {
+        pub version: u32,       // 版本号，每次修改递增
+        pub content_hash: Hash, // 模板内容哈希
+    }
+
+    /// 运费模板最新版本，模板删除后保留，保证同一模板ID重新创建时版本号继续递增
+    #[pallet::storage]
+    #[pallet::storage_prefix = "TemplateVersions"]
+    pub type TemplateVersions<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        BoundedVec<u8, T::MaxCustomAreaLength>, // 主键：区域ID
-        FreightTemplate<T>,                     // 值：运费模板
-        OptionQuery,                            // 查询策略：如果键不存在，返回 None
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构ID
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxTemplateIdLength>,    // 次键：模板ID
+        TemplateVersion<T::Hash>,                  // 值：最新版本信息
+        OptionQuery,
+    >;
+
+    /// 仍被订单引用的历史版本 [模板内容, 内容哈希]
+    #[pallet::storage]
+    #[pallet::storage_prefix = "ArchivedTemplates"]
+    pub type ArchivedTemplates<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, BoundedVec<u8, T::MaxInstitutionIdLength>>, // 机构ID
+            NMapKey<Blake2_128Concat, BoundedVec<u8, T::MaxTemplateIdLength>>,    // 模板ID
+            NMapKey<Twox64Concat, u32>,                                           // 版本号
+        ),
+        (FreightTemplate<T>, T::Hash),
+        OptionQuery,
     >;
 
+    /// 各模板版本被未结束订单引用的次数
+    #[pallet::storage]
+    #[pallet::storage_prefix = "VersionReferences"]
+    pub type VersionReferences<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, BoundedVec<u8, T::MaxInstitutionIdLength>>, // 机构ID
+            NMapKey<Blake2_128Concat, BoundedVec<u8, T::MaxTemplateIdLength>>,    // 模板ID
+            NMapKey<Twox64Concat, u32>,                                           // 版本号
+        ),
+        u32,
+        ValueQuery,
+    >;
+
+    /// 运费模板存储押金 [押金账户, 押金金额]
+    #[pallet::storage]
+    #[pallet::storage_prefix = "TemplateDeposits"]
+    pub type TemplateDeposits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构ID
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxTemplateIdLength>,    // 次键：模板ID
+        (T::AccountId, BalanceOf<T>),              // 值：押金账户及金额
+        OptionQuery,
+    >;
+
+    /// 创世配置
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// 初始运费模板 [机构ID, 模板ID, 创建者, 模板名称, 计费方式(0重量/1件数/2体积), 体积重量除数,
+        /// 默认规则, 地区规则列表 [地区编码列表, 规则], 不配送地区列表, 加价规则列表]
+        #[allow(clippy::type_complexity)]
+        pub templates: Vec<(
+            Vec<u8>,
+            Vec<u8>,
+            T::AccountId,
+            Vec<u8>,
+            u8,
+            u32,
+            GenesisRule,
+            Vec<(Vec<Vec<u8>>, GenesisRule)>,
+            Vec<Vec<u8>>,
+            Vec<GenesisSurcharge>,
+        )>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        /// 写入初始运费模板，押金从创建者余额中冻结
+        fn build(&self) {
+            let rule = |&(first_unit, first_fee, additional_unit, additional_fee, min_amount, min_quantity): &GenesisRule| {
+                let free_shipping = min_amount
+                    .map(FreeShippingCondition::MinAmount)
+                    .into_iter()
+                    .chain(min_quantity.map(FreeShippingCondition::MinQuantity))
+                    .collect::<Vec<_>>();
+                FreightRule::<T> {
+                    first_unit,
+                    first_fee,
+                    additional_unit,
+                    additional_fee,
+                    free_shipping: free_shipping.try_into().expect("too many genesis free shipping conditions"),
+                }
+            };
+
+            let region_codes = |codes: &Vec<Vec<u8>>| {
+                codes
+                    .iter()
+                    .map(|code| RegionCode::<T>::try_from(code.clone()).expect("genesis region code too long"))
+                    .collect::<Vec<_>>()
+            };
+
+            for (
+                institution_id,
+                template_id,
+                creator,
+                name,
+                pricing_mode,
+                divisor,
+                default_rule,
+                regional_rules,
+                excluded_regions,
+                surcharges,
+            ) in &self.templates
+            {
+                let pricing_mode = match pricing_mode {
+                    0 => PricingMode::ByWeight,
+                    1 => PricingMode::ByPieces,
+                    2 => PricingMode::ByVolume { divisor: *divisor },
+                    _ => panic!("invalid genesis freight pricing mode"),
+                };
+                let bounded_institution_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.clone())
+                    .expect("genesis institution id too long");
+                let bounded_template_id = BoundedVec::<u8, T::MaxTemplateIdLength>::try_from(template_id.clone())
+                    .expect("genesis freight template id too long");
+                assert!(T::Institutions::exists(&bounded_institution_id), "genesis freight template references unknown institution");
+                assert!(
+                    !FreightTemplates::<T>::contains_key(&bounded_institution_id, &bounded_template_id),
+                    "duplicate genesis freight template"
+                );
+
+                let regional_rules = regional_rules
+                    .iter()
+                    .map(|(regions, regional)| RegionalRule::<T> {
+                        regions: region_codes(regions).try_into().expect("too many regions in genesis freight rule"),
+                        rule: rule(regional),
+                    })
+                    .collect::<Vec<_>>();
+                let surcharges = surcharges
+                    .iter()
+                    .map(|(regions, kind, value)| SurchargeRule::<T> {
+                        regions: region_codes(regions).try_into().expect("too many regions in genesis surcharge rule"),
+                        surcharge: match kind {
+                            0 => Surcharge::Flat(*value),
+                            1 => Surcharge::Percentage(
+                                u32::try_from(*value).expect("genesis surcharge basis points too large"),
+                            ),
+                            _ => panic!("invalid genesis freight surcharge kind"),
+                        },
+                    })
+                    .collect::<Vec<_>>();
+
+                let template = FreightTemplate::<T> {
+                    name: name.clone().try_into().expect("genesis freight template name too long"),
+                    pricing_mode,
+                    default_rule: rule(default_rule),
+                    regional_rules: regional_rules.try_into().expect("too many genesis regional freight rules"),
+                    excluded_regions: region_codes(excluded_regions)
+                        .try_into()
+                        .expect("too many genesis excluded freight regions"),
+                    surcharges: surcharges.try_into().expect("too many genesis freight surcharge rules"),
+                    creator: creator.clone(),
+                };
+                Pallet::<T>::ensure_valid_template(&template).expect("invalid genesis freight template");
+
+                Pallet::<T>::update_deposit(&bounded_institution_id, &bounded_template_id, creator, template.encoded_size())
+                    .expect("genesis freight template creator cannot cover the deposit");
+                Pallet::<T>::issue_version(&bounded_institution_id, &bounded_template_id, None, &template);
+                FreightTemplates::<T>::insert(&bounded_institution_id, &bounded_template_id, template);
+            }
+        }
+    }
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// 运费模板已创建 [区域ID, 创建者]
-        FreightTemplateCreated(BoundedVec<u8, T::MaxCustomAreaLength>, T::AccountId),
-        /// 运费模板已更新 [区域ID]
-        FreightTemplateUpdated(BoundedVec<u8, T::MaxCustomAreaLength>),
-        /// 运费模板已删除 [区域ID]
-        FreightTemplateDeleted(BoundedVec<u8, T::MaxCustomAreaLength>),
+        /// 运费模板已创建 [机构ID, 模板ID, 创建者]
+        FreightTemplateCreated(BoundedVec<u8, T::MaxInstitutionIdLength>, BoundedVec<u8, T::MaxTemplateIdLength>, T::AccountId),
+        /// 运费模板已更新 [机构ID, 模板ID]
+        FreightTemplateUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>, BoundedVec<u8, T::MaxTemplateIdLength>),
+        /// 运费模板已删除 [机构ID, 模板ID]
+        FreightTemplateDeleted(BoundedVec<u8, T::MaxInstitutionIdLength>, BoundedVec<u8, T::MaxTemplateIdLength>),
+        /// 运费模板地区规则已更新 [机构ID, 模板ID, 规则数]
+        RegionalRulesUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>, BoundedVec<u8, T::MaxTemplateIdLength>, u32),
+        /// 运费模板不配送地区及加价规则已更新 [机构ID, 模板ID]
+        DeliveryRestrictionsUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>, BoundedVec<u8, T::MaxTemplateIdLength>),
+        /// 运费模板已生成新版本 [机构ID, 模板ID, 版本号, 内容哈希]
+        TemplateVersionCreated(BoundedVec<u8, T::MaxInstitutionIdLength>, BoundedVec<u8, T::MaxTemplateIdLength>, u32, T::Hash),
     }
 
     #[pallet::error]
     pub enum Error<T> {
-        /// 区域ID已存在
-        AreaAlreadyExists,
+        /// 模板ID已存在
+        TemplateAlreadyExists,
         /// 运费模板不存在
         FreightTemplateNotFound,
+        /// 机构不存在
+        InstitutionNotFound,
         /// 无权操作此运费模板
         NotAuthorized,
         /// 字符串转换错误
         StringConversionError,
+        /// 首件/首重及续件/续重步长必须大于0
+        InvalidStepSize,
+        /// 体积重量除数必须大于0
+        InvalidVolumetricDivisor,
+        /// 地区编码未在地区库中登记
+        UnknownRegion,
+        /// 地区规则未列出任何地区
+        EmptyRegionList,
+        /// 同一地区出现在多条地区规则中
+        DuplicateRegion,
+        /// 地区规则数量超出上限
+        TooManyRegionalRules,
+        /// 不配送地区数量超出上限
+        TooManyExcludedRegions,
+        /// 加价规则数量超出上限
+        TooManySurchargeRules,
+        /// 目的地区不在配送范围内
+        RegionNotDeliverable,
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// 创建新的运费模板
+        /// 为机构创建新的运费模板
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::create_freight_template())]
         pub fn create_freight_template(
             origin: OriginFor<T>,
-            area: Vec<u8>,
-            first_weight: u64,
-            first_weight_fee: u64,
-            additional_weight_fee: u64,
+            institution_id: Vec<u8>,
+            template_id: Vec<u8>,
+            name: Vec<u8>,
+            pricing_mode: PricingMode,
+            default_rule: FreightRule<T>,
         ) -> DispatchResult {
             // 确认调用者身份
             let who = ensure_signed(origin)?;
-            
+
             // 转换为边界向量
-            let bounded_area = BoundedVec::<u8, T::MaxCustomAreaLength>::try_from(area)
+            let (bounded_institution_id, bounded_template_id) = Self::bounded_keys(institution_id, template_id)?;
+            let bounded_name = BoundedVec::<u8, T::MaxTemplateNameLength>::try_from(name)
                 .map_err(|_| Error::<T>::StringConversionError)?;
-            
-            // 检查区域ID是否已存在
-            ensure!(!FreightTemplates::<T>::contains_key(&bounded_area), Error::<T>::AreaAlreadyExists);
-            
-            // 创建运费模板
-            let template = FreightTemplate {
-                area: bounded_area.clone(),
-                first_weight,
-                first_weight_fee,
-                additional_weight_fee,
+
+            // 检查机构是否存在及调用者权限
+            ensure!(T::Institutions::exists(&bounded_institution_id), Error::<T>::InstitutionNotFound);
+            Self::ensure_template_manager(&bounded_institution_id, &who)?;
+
+            // 检查模板ID是否已存在
+            ensure!(
+                !FreightTemplates::<T>::contains_key(&bounded_institution_id, &bounded_template_id),
+                Error::<T>::TemplateAlreadyExists
+            );
+
+            // 创建运费模板，地区规则及配送限制另行设置
+            let template = FreightTemplate::<T> {
+                name: bounded_name,
+                pricing_mode,
+                default_rule,
+                regional_rules: BoundedVec::default(),
+                excluded_regions: BoundedVec::default(),
+                surcharges: BoundedVec::default(),
                 creator: who.clone(),
             };
-            
-            // 存储运费模板
-            FreightTemplates::<T>::insert(&bounded_area, template);
-            
+            Self::ensure_valid_template(&template)?;
+
+            // 按记录大小冻结存储押金
+            Self::update_deposit(&bounded_institution_id, &bounded_template_id, &who, template.encoded_size())?;
+
+            // 存储运费模板并生成首个版本
+            Self::issue_version(&bounded_institution_id, &bounded_template_id, None, &template);
+            FreightTemplates::<T>::insert(&bounded_institution_id, &bounded_template_id, template);
+
             // 发出事件
-            Self::deposit_event(Event::FreightTemplateCreated(bounded_area, who));
-            
+            Self::deposit_event(Event::FreightTemplateCreated(bounded_institution_id, bounded_template_id, who));
+
             Ok(())
         }
-        
+
         /// 更新运费模板
         #[pallet::call_index(1)]
-        #[pallet::weight(5_000)]
+        #[pallet::weight(T::WeightInfo::update_freight_template())]
         pub fn update_freight_template(
             origin: OriginFor<T>,
-            area: Vec<u8>,
-            first_weight: Option<u64>,
-            first_weight_fee: Option<u64>,
-            additional_weight_fee: Option<u64>,
+            institution_id: Vec<u8>,
+            template_id: Vec<u8>,
+            name: Option<Vec<u8>>,
+            pricing_mode: Option<PricingMode>,
+            default_rule: Option<FreightRule<T>>,
         ) -> DispatchResult {
             // 确认调用者身份
             let who = ensure_signed(origin)?;
-            
+
             // 转换为边界向量
-            let bounded_area = BoundedVec::<u8, T::MaxCustomAreaLength>::try_from(area)
-                .map_err(|_| Error::<T>::StringConversionError)?;
-            
+            let (bounded_institution_id, bounded_template_id) = Self::bounded_keys(institution_id, template_id)?;
+
+            // 检查权限（机构的商品管理人员）
+            Self::ensure_template_manager(&bounded_institution_id, &who)?;
+
             // 获取并更新运费模板
-            FreightTemplates::<T>::try_mutate(&bounded_area, |maybe_template| -> DispatchResult {
+            FreightTemplates::<T>::try_mutate(&bounded_institution_id, &bounded_template_id, |maybe_template| -> DispatchResult {
                 let template = maybe_template.as_mut().ok_or(Error::<T>::FreightTemplateNotFound)?;
-                
-                // 检查权限（仅创建者可以更新）
-                ensure!(template.creator == who, Error::<T>::NotAuthorized);
-                
+                let previous = template.clone();
+
                 // 更新各字段（如果提供）
-                if let Some(weight) = first_weight {
-                    template.first_weight = weight;
+                if let Some(name) = name {
+                    template.name = BoundedVec::<u8, T::MaxTemplateNameLength>::try_from(name)
+                        .map_err(|_| Error::<T>::StringConversionError)?;
                 }
-                
-                if let Some(fee) = first_weight_fee {
-                    template.first_weight_fee = fee;
+
+                if let Some(mode) = pricing_mode {
+                    template.pricing_mode = mode;
                 }
-                
-                if let Some(add_fee) = additional_weight_fee {
-                    template.additional_weight_fee = add_fee;
+
+                if let Some(rule) = default_rule {
+                    template.default_rule = rule;
                 }
-                
+                Self::ensure_valid_template(template)?;
+
+                // 按新的记录大小调整押金并生成新版本
+                Self::update_deposit(&bounded_institution_id, &bounded_template_id, &who, template.encoded_size())?;
+                Self::issue_version(&bounded_institution_id, &bounded_template_id, Some(&previous), template);
+
                 // 发出事件
-                Self::deposit_event(Event::FreightTemplateUpdated(bounded_area.clone()));
-                
+                Self::deposit_event(Event::FreightTemplateUpdated(bounded_institution_id.clone(), bounded_template_id.clone()));
+
                 Ok(())
             })
         }
-        
+
         /// 删除运费模板
         #[pallet::call_index(2)]
-        #[pallet::weight(5_000)]
+        #[pallet::weight(T::WeightInfo::delete_freight_template())]
         pub fn delete_freight_template(
             origin: OriginFor<T>,
-            area: Vec<u8>,
+            institution_id: Vec<u8>,
+            template_id: Vec<u8>,
         ) -> DispatchResult {
             // 确认调用者身份
             let who = ensure_signed(origin)?;
-            
+
             // 转换为边界向量
-            let bounded_area = BoundedVec::<u8, T::MaxCustomAreaLength>::try_from(area)
-                .map_err(|_| Error::<T>::StringConversionError)?;
-            
-            // 获取运费模板并检查权限
-            let template = FreightTemplates::<T>::get(&bounded_area)
+            let (bounded_institution_id, bounded_template_id) = Self::bounded_keys(institution_id, template_id)?;
+
+            // 检查运费模板是否存在及调用者权限
+            let template = FreightTemplates::<T>::get(&bounded_institution_id, &bounded_template_id)
                 .ok_or(Error::<T>::FreightTemplateNotFound)?;
-            
-            ensure!(template.creator == who, Error::<T>::NotAuthorized);
-            
+            Self::ensure_template_manager(&bounded_institution_id, &who)?;
+
+            // 退还存储押金，当前版本仍被订单引用时归档保留
+            Self::release_deposit(&bounded_institution_id, &bounded_template_id)?;
+            Self::archive_if_referenced(&bounded_institution_id, &bounded_template_id, &template);
+
             // 删除运费模板
-            FreightTemplates::<T>::remove(&bounded_area);
-            
+            FreightTemplates::<T>::remove(&bounded_institution_id, &bounded_template_id);
+
             // 发出事件
-            Self::deposit_event(Event::FreightTemplateDeleted(bounded_area));
-            
+            Self::deposit_event(Event::FreightTemplateDeleted(bounded_institution_id, bounded_template_id));
+
+            Ok(())
+        }
+
+        /// 替换运费模板的地区规则列表
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_regional_rules())]
+        pub fn set_regional_rules(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            template_id: Vec<u8>,
+            regional_rules: Vec<RegionalRule<T>>,
+        ) -> DispatchResult {
+            // 确认调用者身份
+            let who = ensure_signed(origin)?;
+
+            // 转换为边界向量
+            let (bounded_institution_id, bounded_template_id) = Self::bounded_keys(institution_id, template_id)?;
+            let rule_count = regional_rules.len() as u32;
+            let bounded_rules = BoundedVec::<RegionalRule<T>, T::MaxRegionalRules>::try_from(regional_rules)
+                .map_err(|_| Error::<T>::TooManyRegionalRules)?;
+
+            // 检查权限（机构的商品管理人员）
+            Self::ensure_template_manager(&bounded_institution_id, &who)?;
+
+            // 地区编码必须已在地区库中登记
+            ensure!(
+                bounded_rules.iter().all(|regional| regional.regions.iter().all(|code| T::Regions::exists(code))),
+                Error::<T>::UnknownRegion
+            );
+
+            FreightTemplates::<T>::try_mutate(&bounded_institution_id, &bounded_template_id, |maybe_template| -> DispatchResult {
+                let template = maybe_template.as_mut().ok_or(Error::<T>::FreightTemplateNotFound)?;
+                let previous = template.clone();
+                template.regional_rules = bounded_rules;
+                Self::ensure_valid_template(template)?;
+
+                // 按新的记录大小调整押金并生成新版本
+                Self::update_deposit(&bounded_institution_id, &bounded_template_id, &who, template.encoded_size())?;
+                Self::issue_version(&bounded_institution_id, &bounded_template_id, Some(&previous), template);
+
+                Ok(())
+            })?;
+
+            // 发出事件
+            Self::deposit_event(Event::RegionalRulesUpdated(bounded_institution_id, bounded_template_id, rule_count));
+
+            Ok(())
+        }
+
+        /// 替换运费模板的不配送地区列表及偏远地区加价规则
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::set_delivery_restrictions())]
+        pub fn set_delivery_restrictions(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            template_id: Vec<u8>,
+            excluded_regions: Vec<Vec<u8>>,
+            surcharges: Vec<SurchargeRule<T>>,
+        ) -> DispatchResult {
+            // 确认调用者身份
+            let who = ensure_signed(origin)?;
+
+            // 转换为边界向量
+            let (bounded_institution_id, bounded_template_id) = Self::bounded_keys(institution_id, template_id)?;
+            let bounded_excluded = excluded_regions
+                .into_iter()
+                .map(|code| RegionCode::<T>::try_from(code).map_err(|_| Error::<T>::StringConversionError))
+                .collect::<Result<Vec<_>, _>>()?;
+            let bounded_excluded = BoundedVec::<RegionCode<T>, T::MaxExcludedRegions>::try_from(bounded_excluded)
+                .map_err(|_| Error::<T>::TooManyExcludedRegions)?;
+            let bounded_surcharges = BoundedVec::<SurchargeRule<T>, T::MaxSurchargeRules>::try_from(surcharges)
+                .map_err(|_| Error::<T>::TooManySurchargeRules)?;
+
+            // 检查权限（机构的商品管理人员）
+            Self::ensure_template_manager(&bounded_institution_id, &who)?;
+
+            // 地区编码必须已在地区库中登记
+            ensure!(
+                bounded_excluded.iter().all(|code| T::Regions::exists(code))
+                    && bounded_surcharges.iter().all(|rule| rule.regions.iter().all(|code| T::Regions::exists(code))),
+                Error::<T>::UnknownRegion
+            );
+
+            FreightTemplates::<T>::try_mutate(&bounded_institution_id, &bounded_template_id, |maybe_template| -> DispatchResult {
+                let template = maybe_template.as_mut().ok_or(Error::<T>::FreightTemplateNotFound)?;
+                let previous = template.clone();
+                template.excluded_regions = bounded_excluded;
+                template.surcharges = bounded_surcharges;
+                Self::ensure_valid_template(template)?;
+
+                // 按新的记录大小调整押金并生成新版本
+                Self::update_deposit(&bounded_institution_id, &bounded_template_id, &who, template.encoded_size())?;
+                Self::issue_version(&bounded_institution_id, &bounded_template_id, Some(&previous), template);
+
+                Ok(())
+            })?;
+
+            // 发出事件
+            Self::deposit_event(Event::DeliveryRestrictionsUpdated(bounded_institution_id, bounded_template_id));
+
+            Ok(())
+        }
+    }
+
+    // 辅助函数
+    impl<T: Config> Pallet<T> {
+        /// 按机构运费模板的当前版本计算发往目的地区的运费
+        pub fn calculate_freight(
+            institution_id: &[u8],
+            template_id: &[u8],
+            destination_region: &[u8],
+            items: &[FreightItem],
+        ) -> Result<FreightQuote, DispatchError> {
+            let (bounded_institution_id, bounded_template_id) =
+                Self::bounded_keys(institution_id.to_vec(), template_id.to_vec())?;
+            let template = FreightTemplates::<T>::get(&bounded_institution_id, &bounded_template_id)
+                .ok_or(Error::<T>::FreightTemplateNotFound)?;
+            let version = TemplateVersions::<T>::get(&bounded_institution_id, &bounded_template_id)
+                .map(|info| info.version)
+                .unwrap_or_default();
+
+            let (quantity, amount) = items.iter().fold((0u32, 0u64), |(quantity, amount), item| {
+                (
+                    quantity.saturating_add(item.quantity),
+                    amount.saturating_add(item.unit_price.saturating_mul(item.quantity.into())),
+                )
+            });
+
+            // 目的地区须已在地区库中登记，否则无法匹配上级地区的规则
+            ensure!(T::Regions::exists(destination_region), Error::<T>::UnknownRegion);
+
+            // 目的地区或其上级地区被列为不配送地区时拒绝计算
+            let lineage = T::Regions::lineage(destination_region);
+            ensure!(!template.is_excluded(&lineage), Error::<T>::RegionNotDeliverable);
+            if quantity == 0 {
+                return Ok(FreightQuote { fee: 0, version });
+            }
+
+            let base_fee = template.rule_for(&lineage).fee(template.chargeable_units(items), quantity, amount);
+            let surcharge = template.surcharge_for(&lineage).map_or(0, |surcharge| surcharge.amount(base_fee));
+            Ok(FreightQuote { fee: base_fee.saturating_add(surcharge), version })
+        }
+
+        /// 查询运费模板的指定版本：当前版本读取现行模板，历史版本读取归档 [模板内容, 内容哈希]
+        pub fn template_version(
+            institution_id: &[u8],
+            template_id: &[u8],
+            version: u32,
+        ) -> Option<(FreightTemplate<T>, T::Hash)> {
+            let (bounded_institution_id, bounded_template_id) =
+                Self::bounded_keys(institution_id.to_vec(), template_id.to_vec()).ok()?;
+            let current = FreightTemplates::<T>::get(&bounded_institution_id, &bounded_template_id)
+                .zip(TemplateVersions::<T>::get(&bounded_institution_id, &bounded_template_id))
+                .filter(|(_, info)| info.version == version)
+                .map(|(template, info)| (template, info.content_hash));
+            current.or_else(|| ArchivedTemplates::<T>::get((bounded_institution_id, bounded_template_id, version)))
+        }
+
+        /// 为模板内容生成新版本；上一版本仍被订单引用时先归档
+        fn issue_version(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            template_id: &BoundedVec<u8, T::MaxTemplateIdLength>,
+            previous: Option<&FreightTemplate<T>>,
+            template: &FreightTemplate<T>,
+        ) {
+            if let Some(previous) = previous {
+                Self::archive_if_referenced(institution_id, template_id, previous);
+            }
+
+            let version = TemplateVersions::<T>::get(institution_id, template_id)
+                .map_or(1, |info| info.version.saturating_add(1));
+            let content_hash = T::Hashing::hash_of(template);
+            TemplateVersions::<T>::insert(institution_id, template_id, TemplateVersion { version, content_hash });
+
+            Self::deposit_event(Event::TemplateVersionCreated(institution_id.clone(), template_id.clone(), version, content_hash));
+        }
+
+        /// 将即将被替换或删除的最新版本归档，仅在仍被订单引用时保留
+        fn archive_if_referenced(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            template_id: &BoundedVec<u8, T::MaxTemplateIdLength>,
+            template: &FreightTemplate<T>,
+        ) {
+            let Some(info) = TemplateVersions::<T>::get(institution_id, template_id) else {
+                return;
+            };
+            let key = (institution_id.clone(), template_id.clone(), info.version);
+            if VersionReferences::<T>::get(&key) > 0 {
+                ArchivedTemplates::<T>::insert(&key, (template.clone(), info.content_hash));
+            }
+        }
+
+        /// 将机构ID和模板ID转换为边界向量
+        #[allow(clippy::type_complexity)]
+        fn bounded_keys(
+            institution_id: Vec<u8>,
+            template_id: Vec<u8>,
+        ) -> Result<(BoundedVec<u8, T::MaxInstitutionIdLength>, BoundedVec<u8, T::MaxTemplateIdLength>), DispatchError> {
+            let bounded_institution_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            let bounded_template_id = BoundedVec::<u8, T::MaxTemplateIdLength>::try_from(template_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            Ok((bounded_institution_id, bounded_template_id))
+        }
+
+        /// 校验计费方式、计费步长、地区规则及配送限制：每条地区规则和加价规则至少列出一个地区，
+        /// 同一地区在地区规则、不配送列表和加价规则中各只能出现一次
+        pub(crate) fn ensure_valid_template(template: &FreightTemplate<T>) -> DispatchResult {
+            if let PricingMode::ByVolume { divisor } = template.pricing_mode {
+                ensure!(divisor > 0, Error::<T>::InvalidVolumetricDivisor);
+            }
+            Self::ensure_valid_rule(&template.default_rule)?;
+
+            let mut seen: Vec<&[u8]> = Vec::new();
+            for regional in template.regional_rules.iter() {
+                ensure!(!regional.regions.is_empty(), Error::<T>::EmptyRegionList);
+                Self::ensure_valid_rule(&regional.rule)?;
+                for code in regional.regions.iter() {
+                    ensure!(!seen.contains(&code.as_slice()), Error::<T>::DuplicateRegion);
+                    seen.push(code.as_slice());
+                }
+            }
+
+            let mut excluded: Vec<&[u8]> = Vec::new();
+            for code in template.excluded_regions.iter() {
+                ensure!(!excluded.contains(&code.as_slice()), Error::<T>::DuplicateRegion);
+                excluded.push(code.as_slice());
+            }
+
+            let mut surcharged: Vec<&[u8]> = Vec::new();
+            for rule in template.surcharges.iter() {
+                ensure!(!rule.regions.is_empty(), Error::<T>::EmptyRegionList);
+                for code in rule.regions.iter() {
+                    ensure!(!surcharged.contains(&code.as_slice()), Error::<T>::DuplicateRegion);
+                    surcharged.push(code.as_slice());
+                }
+            }
             Ok(())
         }
+
+        /// 首件/首重及续件/续重步长必须大于0
+        fn ensure_valid_rule(rule: &FreightRule<T>) -> DispatchResult {
+            ensure!(rule.first_unit > 0 && rule.additional_unit > 0, Error::<T>::InvalidStepSize);
+            Ok(())
+        }
+
+        /// 确认调用者拥有机构的商品管理权限：运费模板与商品价格同属定价配置，
+        /// 由商品管理人员维护（所有者及管理员拥有全部角色），订单处理人员只负责履约，不能修改运费
+        fn ensure_template_manager(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            who: &T::AccountId,
+        ) -> DispatchResult {
+            ensure!(
+                T::Institutions::has_role(institution_id, who, InstitutionRole::ProductManager),
+                Error::<T>::NotAuthorized
+            );
+            Ok(())
+        }
+
+        /// 计算存储押金：基础押金 + 每字节押金 × 编码长度
+        fn deposit_for(encoded_len: usize) -> BalanceOf<T> {
+            T::DepositBase::get()
+                .saturating_add(T::DepositPerByte::get().saturating_mul((encoded_len as u32).into()))
+        }
+
+        /// 按记录的编码长度冻结或调整押金；调用者不是原押金账户时，退还原押金并由调用者重新缴纳
+        fn update_deposit(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            template_id: &BoundedVec<u8, T::MaxTemplateIdLength>,
+            who: &T::AccountId,
+            encoded_len: usize,
+        ) -> DispatchResult {
+            let reason = HoldReason::FreightTemplateDeposit.into();
+            let new_deposit = Self::deposit_for(encoded_len);
+
+            match TemplateDeposits::<T>::get(institution_id, template_id) {
+                Some((depositor, old_deposit)) if &depositor == who => {
+                    if new_deposit > old_deposit {
+                        T::Currency::hold(&reason, who, new_deposit.saturating_sub(old_deposit))?;
+                    } else if old_deposit > new_deposit {
+                        T::Currency::release(&reason, who, old_deposit.saturating_sub(new_deposit), Precision::BestEffort)?;
+                    }
+                },
+                Some((depositor, old_deposit)) => {
+                    T::Currency::release(&reason, &depositor, old_deposit, Precision::BestEffort)?;
+                    T::Currency::hold(&reason, who, new_deposit)?;
+                },
+                None => T::Currency::hold(&reason, who, new_deposit)?,
+            }
+
+            TemplateDeposits::<T>::insert(institution_id, template_id, (who.clone(), new_deposit));
+            Ok(())
+        }
+
+        /// 退还运费模板的存储押金
+        pub(crate) fn release_deposit(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            template_id: &BoundedVec<u8, T::MaxTemplateIdLength>,
+        ) -> DispatchResult {
+            if let Some((depositor, deposit)) = TemplateDeposits::<T>::take(institution_id, template_id) {
+                T::Currency::release(&HoldReason::FreightTemplateDeposit.into(), &depositor, deposit, Precision::BestEffort)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<T: Config> crate::FreightCalculator for Pallet<T> {
+        fn calculate_freight(
+            institution_id: &[u8],
+            template_id: &[u8],
+            destination_region: &[u8],
+            items: &[FreightItem],
+        ) -> Result<FreightQuote, DispatchError> {
+            Self::calculate_freight(institution_id, template_id, destination_region, items)
+        }
+
+        fn has_templates(institution_id: &[u8]) -> bool {
+            BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec())
+                .map(|id| FreightTemplates::<T>::iter_key_prefix(&id).next().is_some())
+                .unwrap_or(false)
+        }
+
+        fn retain_version(institution_id: &[u8], template_id: &[u8], version: u32) {
+            let Ok((bounded_institution_id, bounded_template_id)) =
+                Self::bounded_keys(institution_id.to_vec(), template_id.to_vec())
+            else {
+                return;
+            };
+            VersionReferences::<T>::mutate((bounded_institution_id, bounded_template_id, version), |count| {
+                *count = count.saturating_add(1)
+            });
+        }
+
+        fn release_version(institution_id: &[u8], template_id: &[u8], version: u32) {
+            let Ok((bounded_institution_id, bounded_template_id)) =
+                Self::bounded_keys(institution_id.to_vec(), template_id.to_vec())
+            else {
+                return;
+            };
+            let key = (bounded_institution_id, bounded_template_id, version);
+            let remaining = VersionReferences::<T>::mutate_exists(&key, |count| {
+                let remaining = count.unwrap_or_default().saturating_sub(1);
+                *count = (remaining > 0).then_some(remaining);
+                remaining
+            });
+            if remaining == 0 {
+                ArchivedTemplates::<T>::remove(&key);
+            }
+        }
+    }
+
+    impl<T: Config> pallet_institution::OnInstitutionRemoved for Pallet<T> {
+        fn can_remove(_institution_id: &[u8]) -> DispatchResult {
+            Ok(())
+        }
+
+        /// 分批删除机构的运费模板及版本记录并退还押金
+        fn on_institution_removed(institution_id: &[u8], limit: u32) -> u32 {
+            let Ok(bounded_id) = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec()) else {
+                return 0;
+            };
+
+            let template_ids: Vec<_> = FreightTemplates::<T>::iter_key_prefix(&bounded_id).take(limit as usize).collect();
+            let mut removed = template_ids.len() as u32;
+            for template_id in template_ids {
+                let _ = Self::release_deposit(&bounded_id, &template_id);
+                FreightTemplates::<T>::remove(&bounded_id, &template_id);
+                Self::deposit_event(Event::FreightTemplateDeleted(bounded_id.clone(), template_id));
+            }
+
+            // 机构删除前已没有未结束的订单，版本记录一并清除
+            removed += TemplateVersions::<T>::drain_prefix(&bounded_id).take((limit - removed) as usize).count() as u32;
+            removed += ArchivedTemplates::<T>::drain_prefix((&bounded_id,)).take((limit - removed) as usize).count() as u32;
+            removed += VersionReferences::<T>::drain_prefix((&bounded_id,)).take((limit - removed) as usize).count() as u32;
+            removed
+        }
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,525 @@
+//! 运费模板模块存储迁移
+
+/// v1：运费模板由全局区域主键改为按 (机构ID, 模板ID) 存储
+///
+/// 原有模板迁入创建者名下的机构（持有多个机构时取机构ID最小者），原区域名作为模板ID；
+/// 创建者名下没有机构或区域名超出模板ID长度的模板被删除并退还押金。
+/// 原区域名的长度上限与 `MaxTemplateNameLength` 相同。
+pub mod v1 {
+    use crate::pallet::{BalanceOf, Config, HoldReason, Pallet, TemplateDeposits};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        storage_alias,
+        traits::{fungible::MutateHold, tokens::Precision, UncheckedOnRuntimeUpgrade},
+    };
+    use pallet_institution::InstitutionInspect;
+    use sp_std::vec::Vec;
+
+    /// v0 版本的运费模板结构
+    #[derive(Encode, Decode)]
+    pub struct OldFreightTemplate<T: Config> {
+        pub area: BoundedVec<u8, T::MaxTemplateNameLength>,
+        pub first_weight: u64,
+        pub first_weight_fee: u64,
+        pub additional_weight_fee: u64,
+        pub creator: T::AccountId,
+    }
+
+    /// v1 版本的运费模板结构
+    #[derive(Encode, Decode)]
+    pub struct FreightTemplateV1<T: Config> {
+        pub area: BoundedVec<u8, T::MaxTemplateNameLength>,
+        pub first_weight: u64,
+        pub first_weight_fee: u64,
+        pub additional_weight_fee: u64,
+        pub creator: T::AccountId,
+    }
+
+    /// v0 版本的存储，与 v1 存储共用前缀，存储别名须使用原存储名
+    pub mod v0 {
+        use super::*;
+
+        /// v0 版本的运费模板存储
+        #[storage_alias]
+        pub type FreightTemplates<T: Config> = StorageMap<
+            Pallet<T>,
+            Blake2_128Concat,
+            BoundedVec<u8, <T as Config>::MaxTemplateNameLength>,
+            OldFreightTemplate<T>,
+            OptionQuery,
+        >;
+
+        /// v0 版本的运费模板押金存储
+        #[storage_alias]
+        pub type TemplateDeposits<T: Config> = StorageMap<
+            Pallet<T>,
+            Blake2_128Concat,
+            BoundedVec<u8, <T as Config>::MaxTemplateNameLength>,
+            (<T as frame_system::Config>::AccountId, BalanceOf<T>),
+            OptionQuery,
+        >;
+    }
+
+    /// v1 版本的运费模板存储
+    #[storage_alias]
+    pub type FreightTemplates<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        BoundedVec<u8, <T as Config>::MaxInstitutionIdLength>,
+        Blake2_128Concat,
+        BoundedVec<u8, <T as Config>::MaxTemplateIdLength>,
+        FreightTemplateV1<T>,
+        OptionQuery,
+    >;
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((v0::FreightTemplates::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            // 新旧存储共用前缀，先取出全部旧记录再写入
+            let templates: Vec<_> = v0::FreightTemplates::<T>::drain().collect();
+            let deposits: Vec<_> = v0::TemplateDeposits::<T>::drain().collect();
+            let (mut reads, mut writes) = ((templates.len() + deposits.len()) as u64, (templates.len() + deposits.len()) as u64);
+
+            for (area, old) in templates {
+                reads += 1;
+                let deposit = deposits.iter().find(|(key, _)| key == &area).map(|(_, deposit)| deposit.clone());
+                let institution_id = T::Institutions::institutions_of(&old.creator)
+                    .into_iter()
+                    .min()
+                    .and_then(|id| BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(id).ok());
+                let template_id = BoundedVec::<u8, T::MaxTemplateIdLength>::try_from(area.clone().into_inner()).ok();
+
+                let (Some(institution_id), Some(template_id)) = (institution_id, template_id) else {
+                    if let Some((depositor, amount)) = deposit {
+                        let _ = T::Currency::release(
+                            &HoldReason::FreightTemplateDeposit.into(),
+                            &depositor,
+                            amount,
+                            Precision::BestEffort,
+                        );
+                        writes += 1;
+                    }
+                    continue;
+                };
+
+                FreightTemplates::<T>::insert(&institution_id, &template_id, FreightTemplateV1 {
+                    area: old.area,
+                    first_weight: old.first_weight,
+                    first_weight_fee: old.first_weight_fee,
+                    additional_weight_fee: old.additional_weight_fee,
+                    creator: old.creator,
+                });
+                if let Some(deposit) = deposit {
+                    TemplateDeposits::<T>::insert(&institution_id, &template_id, deposit);
+                    writes += 1;
+                }
+                writes += 1;
+            }
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after = FreightTemplates::<T>::iter_values().count() as u32;
+            ensure!(after <= before, "freight templates created during migration");
+            ensure!(
+                TemplateDeposits::<T>::iter_keys().all(|(institution_id, template_id)| {
+                    FreightTemplates::<T>::contains_key(&institution_id, &template_id)
+                }),
+                "deposit without freight template after migration"
+            );
+            Ok(())
+        }
+    }
+
+    /// 从 v0 迁移到 v1，仅在链上存储版本为 0 时执行
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// v2：运费模板改为默认规则加地区规则，原首重、续重费用成为默认规则
+///
+/// 原模板的区域名作为模板名称；原续重费用按每单位重量计费，迁移后续重步长为 1，不设包邮条件。
+pub mod v2 {
+    use super::v1::FreightTemplateV1;
+    use crate::pallet::{Config, FreightRule, Pallet, RegionalRule};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        storage_alias,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// v2 版本的运费模板结构
+    #[derive(Encode, Decode)]
+    pub struct FreightTemplateV2<T: Config> {
+        pub name: BoundedVec<u8, T::MaxTemplateNameLength>,
+        pub default_rule: FreightRule<T>,
+        pub regional_rules: BoundedVec<RegionalRule<T>, T::MaxRegionalRules>,
+        pub creator: T::AccountId,
+    }
+
+    /// v2 版本的运费模板存储
+    #[storage_alias]
+    pub type FreightTemplates<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        BoundedVec<u8, <T as Config>::MaxInstitutionIdLength>,
+        Blake2_128Concat,
+        BoundedVec<u8, <T as Config>::MaxTemplateIdLength>,
+        FreightTemplateV2<T>,
+        OptionQuery,
+    >;
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV1ToV2<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((FreightTemplates::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            FreightTemplates::<T>::translate::<FreightTemplateV1<T>, _>(|_, _, old| {
+                translated += 1;
+                Some(FreightTemplateV2 {
+                    name: old.area,
+                    default_rule: FreightRule {
+                        first_unit: old.first_weight.max(1),
+                        first_fee: old.first_weight_fee,
+                        additional_unit: 1,
+                        additional_fee: old.additional_weight_fee,
+                        free_shipping: BoundedVec::default(),
+                    },
+                    regional_rules: BoundedVec::default(),
+                    creator: old.creator,
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after = FreightTemplates::<T>::iter_values().count() as u32;
+            ensure!(before == after, "freight templates lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v1 迁移到 v2，仅在链上存储版本为 1 时执行
+    pub type MigrateV1ToV2<T> = VersionedMigration<
+        1,
+        2,
+        InnerMigrateV1ToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// v3：运费模板增加计费方式，已有模板均按重量计费
+pub mod v3 {
+    use super::v2::FreightTemplateV2;
+    use crate::pallet::{Config, FreightRule, Pallet, PricingMode, RegionalRule};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        storage_alias,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// v3 版本的运费模板结构
+    #[derive(Encode, Decode)]
+    pub struct FreightTemplateV3<T: Config> {
+        pub name: BoundedVec<u8, T::MaxTemplateNameLength>,
+        pub pricing_mode: PricingMode,
+        pub default_rule: FreightRule<T>,
+        pub regional_rules: BoundedVec<RegionalRule<T>, T::MaxRegionalRules>,
+        pub creator: T::AccountId,
+    }
+
+    /// v3 版本的运费模板存储
+    #[storage_alias]
+    pub type FreightTemplates<T: Config> = StorageDoubleMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        BoundedVec<u8, <T as Config>::MaxInstitutionIdLength>,
+        Blake2_128Concat,
+        BoundedVec<u8, <T as Config>::MaxTemplateIdLength>,
+        FreightTemplateV3<T>,
+        OptionQuery,
+    >;
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV2ToV3<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV2ToV3<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((FreightTemplates::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            FreightTemplates::<T>::translate::<FreightTemplateV2<T>, _>(|_, _, old| {
+                translated += 1;
+                Some(FreightTemplateV3 {
+                    name: old.name,
+                    pricing_mode: PricingMode::ByWeight,
+                    default_rule: old.default_rule,
+                    regional_rules: old.regional_rules,
+                    creator: old.creator,
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after = FreightTemplates::<T>::iter_values().count() as u32;
+            ensure!(before == after, "freight templates lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v2 迁移到 v3，仅在链上存储版本为 2 时执行
+    pub type MigrateV2ToV3<T> = VersionedMigration<
+        2,
+        3,
+        InnerMigrateV2ToV3<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// v4：运费模板增加不配送地区及偏远地区加价规则，已有模板均不设限制
+pub mod v4 {
+    use super::v3::FreightTemplateV3;
+    use crate::pallet::{Config, FreightTemplate, FreightTemplates, Pallet};
+    #[cfg(feature = "try-runtime")]
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV3ToV4<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV3ToV4<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((FreightTemplates::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            FreightTemplates::<T>::translate::<FreightTemplateV3<T>, _>(|_, _, old| {
+                translated += 1;
+                Some(FreightTemplate {
+                    name: old.name,
+                    pricing_mode: old.pricing_mode,
+                    default_rule: old.default_rule,
+                    regional_rules: old.regional_rules,
+                    excluded_regions: BoundedVec::default(),
+                    surcharges: BoundedVec::default(),
+                    creator: old.creator,
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after = FreightTemplates::<T>::iter_values().count() as u32;
+            ensure!(before == after, "freight templates lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v3 迁移到 v4，仅在链上存储版本为 3 时执行
+    pub type MigrateV3ToV4<T> = VersionedMigration<
+        3,
+        4,
+        InnerMigrateV3ToV4<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// v5：运费模板引入版本号及内容哈希，已有模板记为版本 1
+pub mod v5 {
+    use crate::pallet::{Config, FreightTemplates, Pallet, TemplateVersion, TemplateVersions};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        sp_runtime::traits::Hash as HashT,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    #[cfg(feature = "try-runtime")]
+    use codec::{Decode, Encode};
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV4ToV5<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV4ToV5<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((FreightTemplates::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut count = 0u64;
+            for (institution_id, template_id, template) in FreightTemplates::<T>::iter() {
+                count += 1;
+                TemplateVersions::<T>::insert(
+                    &institution_id,
+                    &template_id,
+                    TemplateVersion { version: 1, content_hash: T::Hashing::hash_of(&template) },
+                );
+            }
+            T::DbWeight::get().reads_writes(count, count)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let versioned = TemplateVersions::<T>::iter_keys().count() as u32;
+            ensure!(before == versioned, "freight template without version after migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v4 迁移到 v5，仅在链上存储版本为 4 时执行
+    pub type MigrateV4ToV5<T> = VersionedMigration<
+        4,
+        5,
+        InnerMigrateV4ToV5<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// v6：偏远地区比例加价由 `Perbill` 改为基点（10_000 为 100%），按原比例换算
+///
+/// 两者编码长度相同，只换算数值；内容哈希随编码变化重新计算，版本号不变。
+pub mod v6 {
+    use crate::pallet::{
+        ArchivedTemplates, Config, FreightTemplate, FreightTemplates, Pallet, Surcharge, TemplateVersions,
+    };
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        sp_runtime::traits::Hash as HashT,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    #[cfg(feature = "try-runtime")]
+    use codec::{Decode, Encode};
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// `Perbill` 每基点对应的份数
+    const PARTS_PER_BASIS_POINT: u32 = 100_000;
+
+    /// 将模板中按 `Perbill` 份数存储的比例加价换算为基点，返回是否有改动
+    pub fn convert_surcharges<T: Config>(template: &mut FreightTemplate<T>) -> bool {
+        let mut changed = false;
+        for rule in template.surcharges.iter_mut() {
+            if let Surcharge::Percentage(parts) = rule.surcharge {
+                rule.surcharge = Surcharge::Percentage(parts.div_ceil(PARTS_PER_BASIS_POINT));
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV5ToV6<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV5ToV6<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((FreightTemplates::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads = 0u64;
+            let mut writes = 0u64;
+
+            for (institution_id, template_id, mut template) in FreightTemplates::<T>::iter() {
+                reads += 1;
+                if !convert_surcharges(&mut template) {
+                    continue;
+                }
+                TemplateVersions::<T>::mutate(&institution_id, &template_id, |maybe_info| {
+                    if let Some(info) = maybe_info {
+                        info.content_hash = T::Hashing::hash_of(&template);
+                    }
+                });
+                FreightTemplates::<T>::insert(&institution_id, &template_id, template);
+                reads += 1;
+                writes += 2;
+            }
+
+            ArchivedTemplates::<T>::translate::<(FreightTemplate<T>, T::Hash), _>(|_, (mut template, content_hash)| {
+                reads += 1;
+                if !convert_surcharges(&mut template) {
+                    return Some((template, content_hash));
+                }
+                writes += 1;
+                let content_hash = T::Hashing::hash_of(&template);
+                Some((template, content_hash))
+            });
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after = FreightTemplates::<T>::iter_values().count() as u32;
+            ensure!(before == after, "freight templates lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v5 迁移到 v6，仅在链上存储版本为 5 时执行
+    pub type MigrateV5ToV6<T> = VersionedMigration<
+        5,
+        6,
+        InnerMigrateV5ToV6<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
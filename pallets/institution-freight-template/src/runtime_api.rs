@@ -0,0 +1,18 @@
+//! 运费计算运行时 API
+
+use crate::FreightItem;
+use frame_support::sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// 供前端预览的运费试算
+    pub trait FreightApi {
+        /// 按机构运费模板计算发往目的地区的运费（含偏远地区加价），目的地区不在配送范围内时返回错误
+        fn calculate_freight(
+            institution_id: Vec<u8>,
+            template_id: Vec<u8>,
+            destination_region: Vec<u8>,
+            items: Vec<FreightItem>,
+        ) -> Result<u64, DispatchError>;
+    }
+}
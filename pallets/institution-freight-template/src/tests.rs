@@ -0,0 +1,535 @@
+use crate::{
+    mock::*, ArchivedTemplates, Error, Event, FreeShippingCondition, FreightCalculator, FreightItem, FreightQuote,
+    FreightRule, FreightTemplates, HoldReason, PricingMode, RegionalRule, Surcharge, SurchargeRule, TemplateDeposits,
+    TemplateVersions, VersionReferences,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{fungible::InspectHold, ConstU32, Get},
+    BoundedVec,
+};
+use pallet_institution::{InstitutionRole, OnInstitutionRemoved};
+use sp_runtime::traits::Hash;
+
+fn bounded<S: Get<u32>>(value: &[u8]) -> BoundedVec<u8, S> {
+    BoundedVec::try_from(value.to_vec()).unwrap()
+}
+
+fn regions<S: Get<u32>>(codes: &[&[u8]]) -> BoundedVec<BoundedVec<u8, ConstU32<12>>, S> {
+    BoundedVec::try_from(codes.iter().map(|code| bounded(code)).collect::<Vec<_>>()).unwrap()
+}
+
+/// 首件/首重及续件/续重规则，不设包邮条件
+fn rule(first_unit: u64, first_fee: u64, additional_unit: u64, additional_fee: u64) -> FreightRule<Test> {
+    FreightRule { first_unit, first_fee, additional_unit, additional_fee, free_shipping: BoundedVec::default() }
+}
+
+fn item(quantity: u32, unit_weight: u64, unit_volume: u64, unit_price: u64) -> FreightItem {
+    FreightItem { quantity, unit_weight, unit_volume, unit_price }
+}
+
+fn create(who: u64, template_id: &[u8], pricing_mode: PricingMode, default_rule: FreightRule<Test>) -> frame_support::dispatch::DispatchResult {
+    InstitutionFreightTemplate::create_freight_template(
+        RuntimeOrigin::signed(who),
+        b"INST_001".to_vec(),
+        template_id.to_vec(),
+        b"Standard".to_vec(),
+        pricing_mode,
+        default_rule,
+    )
+}
+
+fn update_rule(who: u64, template_id: &[u8], default_rule: FreightRule<Test>) -> frame_support::dispatch::DispatchResult {
+    InstitutionFreightTemplate::update_freight_template(
+        RuntimeOrigin::signed(who),
+        b"INST_001".to_vec(),
+        template_id.to_vec(),
+        None,
+        None,
+        Some(default_rule),
+    )
+}
+
+fn quote(destination: &[u8], items: &[FreightItem]) -> Result<FreightQuote, sp_runtime::DispatchError> {
+    InstitutionFreightTemplate::calculate_freight(b"INST_001", b"standard", destination, items)
+}
+
+fn fee(destination: &[u8], items: &[FreightItem]) -> u64 {
+    quote(destination, items).unwrap().fee
+}
+
+fn deposit_on_hold(who: u64) -> u64 {
+    Balances::balance_on_hold(&HoldReason::FreightTemplateDeposit.into(), &who)
+}
+
+#[test]
+fn weight_pricing_rounds_additional_steps_up() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        // 首重 1000 克 10 元，每续重 500 克 3 元
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)));
+        System::assert_last_event(Event::FreightTemplateCreated(bounded(b"INST_001"), bounded(b"standard"), 1).into());
+
+        assert_eq!(fee(b"440300", &[item(1, 1_000, 0, 50)]), 10);
+        assert_eq!(fee(b"440300", &[item(1, 1_001, 0, 50)]), 13);
+        assert_eq!(fee(b"440300", &[item(1, 1_500, 0, 50)]), 13);
+        assert_eq!(fee(b"440300", &[item(1, 1_501, 0, 50)]), 16);
+        // 多件商品按总重量计费
+        assert_eq!(fee(b"440300", &[item(2, 600, 0, 50), item(1, 300, 0, 50)]), 13);
+        // 没有商品时不收运费
+        assert_eq!(fee(b"440300", &[]), 0);
+    });
+}
+
+#[test]
+fn piece_and_volume_pricing() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        // 首件 1 件 5 元，每续 2 件 2 元
+        assert_ok!(create(1, b"standard", PricingMode::ByPieces, rule(1, 5, 2, 2)));
+        assert_eq!(fee(b"440300", &[item(1, 9_999, 0, 50)]), 5);
+        assert_eq!(fee(b"440300", &[item(3, 0, 0, 50), item(1, 0, 0, 50)]), 9);
+
+        // 体积除以 6000 得到体积重量，与实际重量取较大者
+        assert_ok!(update_rule(1, b"standard", rule(1, 10, 1, 5)));
+        assert_ok!(InstitutionFreightTemplate::update_freight_template(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            b"standard".to_vec(),
+            None,
+            Some(PricingMode::ByVolume { divisor: 6_000 }),
+            None,
+        ));
+        assert_eq!(fee(b"440300", &[item(1, 1, 12_000, 50)]), 15);
+        assert_eq!(fee(b"440300", &[item(1, 1, 12_001, 50)]), 20);
+        assert_eq!(fee(b"440300", &[item(1, 4, 12_000, 50)]), 25);
+    });
+}
+
+#[test]
+fn free_shipping_conditions() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        let mut default_rule = rule(1_000, 10, 500, 3);
+        default_rule.free_shipping =
+            BoundedVec::try_from(vec![FreeShippingCondition::MinAmount(100), FreeShippingCondition::MinQuantity(5)]).unwrap();
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, default_rule));
+
+        assert_eq!(fee(b"440300", &[item(1, 2_000, 0, 99)]), 16);
+        assert_eq!(fee(b"440300", &[item(1, 2_000, 0, 100)]), 0);
+        assert_eq!(fee(b"440300", &[item(5, 400, 0, 1)]), 0);
+    });
+}
+
+#[test]
+fn invalid_templates_are_rejected() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_noop!(create(1, b"standard", PricingMode::ByWeight, rule(0, 10, 500, 3)), Error::<Test>::InvalidStepSize);
+        assert_noop!(create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 0, 3)), Error::<Test>::InvalidStepSize);
+        assert_noop!(
+            create(1, b"standard", PricingMode::ByVolume { divisor: 0 }, rule(1, 10, 1, 5)),
+            Error::<Test>::InvalidVolumetricDivisor
+        );
+
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)));
+        assert_noop!(
+            create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)),
+            Error::<Test>::TemplateAlreadyExists
+        );
+
+        let set_rules = |rules: Vec<RegionalRule<Test>>| {
+            InstitutionFreightTemplate::set_regional_rules(RuntimeOrigin::signed(1), b"INST_001".to_vec(), b"standard".to_vec(), rules)
+        };
+        assert_noop!(
+            set_rules(vec![RegionalRule { regions: regions(&[b"XX"]), rule: rule(1_000, 10, 500, 3) }]),
+            Error::<Test>::UnknownRegion
+        );
+        assert_noop!(
+            set_rules(vec![RegionalRule { regions: regions(&[]), rule: rule(1_000, 10, 500, 3) }]),
+            Error::<Test>::EmptyRegionList
+        );
+        assert_noop!(
+            set_rules(vec![
+                RegionalRule { regions: regions(&[b"440000"]), rule: rule(1_000, 10, 500, 3) },
+                RegionalRule { regions: regions(&[b"650000", b"440000"]), rule: rule(1_000, 20, 500, 6) },
+            ]),
+            Error::<Test>::DuplicateRegion
+        );
+        assert_noop!(
+            set_rules(vec![RegionalRule { regions: regions(&[b"440000"]), rule: rule(1_000, 10, 0, 3) }]),
+            Error::<Test>::InvalidStepSize
+        );
+
+        let set_restrictions = |excluded: Vec<Vec<u8>>, surcharges: Vec<SurchargeRule<Test>>| {
+            InstitutionFreightTemplate::set_delivery_restrictions(
+                RuntimeOrigin::signed(1),
+                b"INST_001".to_vec(),
+                b"standard".to_vec(),
+                excluded,
+                surcharges,
+            )
+        };
+        assert_noop!(set_restrictions(vec![b"XX".to_vec()], vec![]), Error::<Test>::UnknownRegion);
+        assert_noop!(
+            set_restrictions(vec![b"540000".to_vec(), b"540000".to_vec()], vec![]),
+            Error::<Test>::DuplicateRegion
+        );
+        assert_noop!(
+            set_restrictions(vec![], vec![SurchargeRule { regions: regions(&[]), surcharge: Surcharge::Flat(5) }]),
+            Error::<Test>::EmptyRegionList
+        );
+    });
+}
+
+#[test]
+fn product_managers_maintain_templates() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2, InstitutionRole::OrderClerk));
+        assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 3, InstitutionRole::ProductManager));
+
+        // 订单处理人员及无关账户不能维护运费模板
+        assert_noop!(create(2, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)), Error::<Test>::NotAuthorized);
+        assert_noop!(create(4, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)), Error::<Test>::NotAuthorized);
+
+        assert_ok!(create(3, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)));
+        assert_noop!(update_rule(2, b"standard", rule(1_000, 1, 500, 1)), Error::<Test>::NotAuthorized);
+        assert_noop!(
+            InstitutionFreightTemplate::delete_freight_template(RuntimeOrigin::signed(2), b"INST_001".to_vec(), b"standard".to_vec()),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(update_rule(1, b"standard", rule(1_000, 12, 500, 3)));
+
+        assert_noop!(
+            InstitutionFreightTemplate::create_freight_template(
+                RuntimeOrigin::signed(1),
+                b"INST_404".to_vec(),
+                b"standard".to_vec(),
+                b"Standard".to_vec(),
+                PricingMode::ByWeight,
+                rule(1_000, 10, 500, 3),
+            ),
+            Error::<Test>::InstitutionNotFound
+        );
+    });
+}
+
+#[test]
+fn regional_rules_follow_region_lineage() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)));
+        assert_ok!(InstitutionFreightTemplate::set_regional_rules(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            b"standard".to_vec(),
+            vec![
+                RegionalRule { regions: regions(&[b"440000"]), rule: rule(1_000, 6, 500, 1) },
+                RegionalRule { regions: regions(&[b"440300"]), rule: rule(1_000, 4, 500, 1) },
+            ],
+        ));
+        System::assert_last_event(Event::RegionalRulesUpdated(bounded(b"INST_001"), bounded(b"standard"), 2).into());
+
+        // 最具体的地区规则优先，其次为上级地区规则，均未覆盖时使用默认规则
+        assert_eq!(fee(b"440300", &[item(1, 1_000, 0, 50)]), 4);
+        assert_eq!(fee(b"440000", &[item(1, 1_000, 0, 50)]), 6);
+        assert_eq!(fee(b"650100", &[item(1, 1_000, 0, 50)]), 10);
+
+        // 目的地区须已在地区库中登记
+        assert_noop!(quote(b"XX", &[item(1, 1_000, 0, 50)]), Error::<Test>::UnknownRegion);
+        assert_noop!(
+            InstitutionFreightTemplate::calculate_freight(b"INST_001", b"express", b"440300", &[item(1, 1_000, 0, 50)]),
+            Error::<Test>::FreightTemplateNotFound
+        );
+    });
+}
+
+#[test]
+fn excluded_regions_and_surcharges() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        let mut default_rule = rule(1_000, 10, 500, 3);
+        default_rule.free_shipping = BoundedVec::try_from(vec![FreeShippingCondition::MinAmount(100)]).unwrap();
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, default_rule));
+        assert_ok!(InstitutionFreightTemplate::set_delivery_restrictions(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            b"standard".to_vec(),
+            vec![b"540000".to_vec()],
+            vec![
+                SurchargeRule { regions: regions(&[b"650000"]), surcharge: Surcharge::Flat(8) },
+                // 150%
+                SurchargeRule { regions: regions(&[b"440300"]), surcharge: Surcharge::Percentage(15_000) },
+                // 25%
+                SurchargeRule { regions: regions(&[b"440000"]), surcharge: Surcharge::Percentage(2_500) },
+            ],
+        ));
+        System::assert_last_event(Event::DeliveryRestrictionsUpdated(bounded(b"INST_001"), bounded(b"standard")).into());
+
+        // 不配送地区及其下级地区均拒绝计算
+        assert_noop!(quote(b"540000", &[item(1, 1_000, 0, 50)]), Error::<Test>::RegionNotDeliverable);
+        assert_noop!(quote(b"540100", &[item(1, 1_000, 0, 50)]), Error::<Test>::RegionNotDeliverable);
+
+        // 固定加价适用于下级地区，包邮时仍然收取
+        assert_eq!(fee(b"650100", &[item(1, 1_000, 0, 50)]), 18);
+        assert_eq!(fee(b"650100", &[item(1, 1_000, 0, 100)]), 8);
+
+        // 比例加价可超过 100%，按最具体的地区匹配并向上取整
+        assert_eq!(fee(b"440300", &[item(1, 1_000, 0, 50)]), 25);
+        assert_eq!(fee(b"440000", &[item(1, 1_001, 0, 50)]), 17);
+        assert_eq!(fee(b"440000", &[item(1, 1_000, 0, 100)]), 0);
+
+        assert_eq!(fee(b"CN", &[item(1, 1_000, 0, 50)]), 10);
+    });
+}
+
+#[test]
+fn surcharge_amounts() {
+    assert_eq!(Surcharge::Flat(8).amount(0), 8);
+    assert_eq!(Surcharge::Percentage(10_000).amount(13), 13);
+    assert_eq!(Surcharge::Percentage(20_000).amount(13), 26);
+    assert_eq!(Surcharge::Percentage(1).amount(13), 1);
+    assert_eq!(Surcharge::Percentage(0).amount(13), 0);
+    assert_eq!(Surcharge::Percentage(u32::MAX).amount(u64::MAX), u64::MAX);
+}
+
+#[test]
+fn deposit_follows_template_size() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 3, InstitutionRole::ProductManager));
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)));
+
+        let (depositor, deposit) = TemplateDeposits::<Test>::get(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")).unwrap();
+        assert_eq!(depositor, 1);
+        assert_eq!(deposit_on_hold(1), deposit);
+
+        // 记录变大时补缴押金
+        assert_ok!(InstitutionFreightTemplate::set_delivery_restrictions(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            b"standard".to_vec(),
+            vec![b"540000".to_vec()],
+            vec![],
+        ));
+        let (_, larger) = TemplateDeposits::<Test>::get(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")).unwrap();
+        assert!(larger > deposit);
+        assert_eq!(deposit_on_hold(1), larger);
+
+        // 其他成员修改时退还原押金并由其重新缴纳
+        assert_ok!(update_rule(3, b"standard", rule(1_000, 12, 500, 3)));
+        assert_eq!(deposit_on_hold(1), 0);
+        assert_eq!(deposit_on_hold(3), larger);
+
+        assert_ok!(InstitutionFreightTemplate::delete_freight_template(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            b"standard".to_vec(),
+        ));
+        System::assert_last_event(Event::FreightTemplateDeleted(bounded(b"INST_001"), bounded(b"standard")).into());
+        assert_eq!(deposit_on_hold(3), 0);
+        assert!(!TemplateDeposits::<Test>::contains_key(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")));
+    });
+}
+
+#[test]
+fn every_change_issues_a_new_version() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert!(!InstitutionFreightTemplate::has_templates(b"INST_001"));
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)));
+        assert!(InstitutionFreightTemplate::has_templates(b"INST_001"));
+
+        let template = FreightTemplates::<Test>::get(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")).unwrap();
+        let info = TemplateVersions::<Test>::get(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")).unwrap();
+        assert_eq!(info.version, 1);
+        assert_eq!(info.content_hash, <Test as frame_system::Config>::Hashing::hash_of(&template));
+        assert_eq!(quote(b"440300", &[item(1, 1_000, 0, 50)]), Ok(FreightQuote { fee: 10, version: 1 }));
+
+        assert_ok!(update_rule(1, b"standard", rule(1_000, 12, 500, 3)));
+        let template = FreightTemplates::<Test>::get(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")).unwrap();
+        let content_hash = <Test as frame_system::Config>::Hashing::hash_of(&template);
+        System::assert_has_event(Event::TemplateVersionCreated(bounded(b"INST_001"), bounded(b"standard"), 2, content_hash).into());
+        assert_eq!(quote(b"440300", &[item(1, 1_000, 0, 50)]), Ok(FreightQuote { fee: 12, version: 2 }));
+
+        // 未被订单引用的旧版本不归档
+        assert_eq!(InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 2), Some((template, content_hash)));
+        assert_eq!(InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 1), None);
+        assert_eq!(ArchivedTemplates::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn referenced_versions_stay_readable() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)));
+        let original = InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 1).unwrap();
+
+        // 两个订单引用版本 1
+        InstitutionFreightTemplate::retain_version(b"INST_001", b"standard", 1);
+        InstitutionFreightTemplate::retain_version(b"INST_001", b"standard", 1);
+        let key = (bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard"), 1u32);
+        assert_eq!(VersionReferences::<Test>::get(&key), 2);
+
+        assert_ok!(update_rule(1, b"standard", rule(1_000, 12, 500, 3)));
+        assert_eq!(InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 1), Some(original.clone()));
+
+        // 删除模板后被引用的当前版本同样保留
+        InstitutionFreightTemplate::retain_version(b"INST_001", b"standard", 2);
+        assert_ok!(InstitutionFreightTemplate::delete_freight_template(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            b"standard".to_vec(),
+        ));
+        assert!(InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 2).is_some());
+
+        // 同一模板ID重新创建时版本号继续递增
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)));
+        assert_eq!(quote(b"440300", &[item(1, 1_000, 0, 50)]).unwrap().version, 3);
+
+        // 最后一个引用释放后删除归档
+        InstitutionFreightTemplate::release_version(b"INST_001", b"standard", 1);
+        assert!(InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 1).is_some());
+        InstitutionFreightTemplate::release_version(b"INST_001", b"standard", 1);
+        assert_eq!(InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 1), None);
+        assert!(!VersionReferences::<Test>::contains_key(&key));
+        InstitutionFreightTemplate::release_version(b"INST_001", b"standard", 2);
+        assert_eq!(ArchivedTemplates::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn removing_institution_clears_templates() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)));
+        InstitutionFreightTemplate::retain_version(b"INST_001", b"standard", 1);
+        assert_ok!(update_rule(1, b"standard", rule(1_000, 12, 500, 3)));
+
+        // 模板、最新版本、归档版本及版本引用各一条，分两批清理
+        assert_eq!(InstitutionFreightTemplate::on_institution_removed(b"INST_001", 2), 2);
+        assert!(!InstitutionFreightTemplate::has_templates(b"INST_001"));
+        assert_eq!(ArchivedTemplates::<Test>::iter().count(), 1);
+        assert_eq!(InstitutionFreightTemplate::on_institution_removed(b"INST_001", 10), 2);
+
+        assert!(!InstitutionFreightTemplate::has_templates(b"INST_001"));
+        assert_eq!(deposit_on_hold(1), 0);
+        assert_eq!(TemplateVersions::<Test>::iter().count(), 0);
+        assert_eq!(ArchivedTemplates::<Test>::iter().count(), 0);
+        assert_eq!(VersionReferences::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn migrations_upgrade_v0_templates_to_current_layout() {
+    use crate::migrations::{v1, v2, v3, v4, v5, v6};
+    use frame_support::traits::{fungible::MutateHold, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        // 账户 1 名下有机构，账户 2 名下没有
+        let reason: RuntimeHoldReason = HoldReason::FreightTemplateDeposit.into();
+        assert_ok!(Balances::hold(&reason, &1, 40));
+        assert_ok!(Balances::hold(&reason, &2, 50));
+        for (area, creator, deposit) in [(&b"standard"[..], 1u64, 40u64), (&b"orphan"[..], 2, 50)] {
+            v1::v0::FreightTemplates::<Test>::insert(
+                bounded::<ConstU32<64>>(area),
+                v1::OldFreightTemplate::<Test> {
+                    area: bounded(area),
+                    first_weight: 1_000,
+                    first_weight_fee: 10,
+                    additional_weight_fee: 3,
+                    creator,
+                },
+            );
+            v1::v0::TemplateDeposits::<Test>::insert(bounded::<ConstU32<64>>(area), (creator, deposit));
+        }
+        StorageVersion::new(0).put::<InstitutionFreightTemplate>();
+
+        v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+        v2::MigrateV1ToV2::<Test>::on_runtime_upgrade();
+        v3::MigrateV2ToV3::<Test>::on_runtime_upgrade();
+        v4::MigrateV3ToV4::<Test>::on_runtime_upgrade();
+        v5::MigrateV4ToV5::<Test>::on_runtime_upgrade();
+        v6::MigrateV5ToV6::<Test>::on_runtime_upgrade();
+        assert_eq!(InstitutionFreightTemplate::on_chain_storage_version(), StorageVersion::new(6));
+
+        // 原区域名成为模板ID及名称，原首重、续重费用成为按重量计费的默认规则
+        let template = FreightTemplates::<Test>::get(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")).unwrap();
+        assert_eq!(template.name, bounded::<ConstU32<64>>(b"standard"));
+        assert_eq!(template.pricing_mode, PricingMode::ByWeight);
+        assert_eq!(template.default_rule, rule(1_000, 10, 1, 3));
+        assert!(template.regional_rules.is_empty() && template.excluded_regions.is_empty() && template.surcharges.is_empty());
+        assert_eq!(template.creator, 1);
+
+        let info = TemplateVersions::<Test>::get(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")).unwrap();
+        assert_eq!(info.version, 1);
+        assert_eq!(info.content_hash, <Test as frame_system::Config>::Hashing::hash_of(&template));
+        assert_eq!(fee(b"440300", &[item(1, 1_002, 0, 50)]), 16);
+
+        // 押金随模板迁移；创建者名下没有机构的模板被删除并退还押金
+        assert_eq!(
+            TemplateDeposits::<Test>::get(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")),
+            Some((1, 40))
+        );
+        assert_eq!(deposit_on_hold(1), 40);
+        assert_eq!(deposit_on_hold(2), 0);
+        assert_eq!(FreightTemplates::<Test>::iter().count(), 1);
+        assert_eq!(TemplateDeposits::<Test>::iter().count(), 1);
+
+        // 已是最新版本时再次执行不做改动
+        v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+        assert_eq!(FreightTemplates::<Test>::iter().count(), 1);
+    });
+}
+
+#[test]
+fn migration_v6_converts_percentage_surcharges_to_basis_points() {
+    use crate::migrations::v6::MigrateV5ToV6;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"standard", PricingMode::ByWeight, rule(1_000, 10, 500, 3)));
+        // v5 中比例加价按 `Perbill` 份数存储：25% 为 250_000_000
+        assert_ok!(InstitutionFreightTemplate::set_delivery_restrictions(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            b"standard".to_vec(),
+            vec![],
+            vec![
+                SurchargeRule { regions: regions(&[b"650000"]), surcharge: Surcharge::Flat(8) },
+                SurchargeRule { regions: regions(&[b"440000"]), surcharge: Surcharge::Percentage(250_000_000) },
+            ],
+        ));
+        // 版本 2 仍被订单引用，版本 3 修改后归档
+        InstitutionFreightTemplate::retain_version(b"INST_001", b"standard", 2);
+        assert_ok!(update_rule(1, b"standard", rule(1_000, 12, 500, 3)));
+        StorageVersion::new(5).put::<InstitutionFreightTemplate>();
+
+        MigrateV5ToV6::<Test>::on_runtime_upgrade();
+        assert_eq!(InstitutionFreightTemplate::on_chain_storage_version(), StorageVersion::new(6));
+
+        let expected = vec![
+            SurchargeRule { regions: regions(&[b"650000"]), surcharge: Surcharge::Flat(8) },
+            SurchargeRule { regions: regions(&[b"440000"]), surcharge: Surcharge::Percentage(2_500) },
+        ];
+        let current = FreightTemplates::<Test>::get(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")).unwrap();
+        assert_eq!(current.surcharges.to_vec(), expected);
+        let info = TemplateVersions::<Test>::get(bounded::<ConstU32<64>>(b"INST_001"), bounded::<ConstU32<32>>(b"standard")).unwrap();
+        assert_eq!(info.version, 3);
+        assert_eq!(info.content_hash, <Test as frame_system::Config>::Hashing::hash_of(&current));
+
+        let (archived, archived_hash) = InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 2).unwrap();
+        assert_eq!(archived.surcharges.to_vec(), expected);
+        assert_eq!(archived_hash, <Test as frame_system::Config>::Hashing::hash_of(&archived));
+
+        // 基础运费 12 元，加价 25% 向上取整为 3 元
+        assert_eq!(fee(b"440300", &[item(1, 1_000, 0, 50)]), 15);
+    });
+}
@@ -3,10 +3,24 @@
 /// 机构支付方式管理模块
 pub use pallet::*;
 
+pub mod weights;
+pub use weights::*;
+
+pub mod migrations;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 #[frame_support::pallet]
 pub mod pallet {
+    use codec::DecodeWithMemTracking;
     use frame_support::{pallet_prelude::*, traits::Get};
     use frame_system::pallet_prelude::*;
+    use crate::WeightInfo;
+    use pallet_institution::{InstitutionInspect, InstitutionRole};
     use scale_info::TypeInfo;
     use sp_std::prelude::*;
     use sp_std::vec::Vec;
@@ -14,6 +28,9 @@ pub mod pallet {
     /// 支付方式信息最大长度类型
     pub type MaxPaymentLengthType = ConstU32<256>;
 
+    /// 当前存储版本
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::config]
     pub trait Config: frame_system::Config + scale_info::TypeInfo {
         /// 事件类型
@@ -26,19 +43,96 @@ pub mod pallet {
         /// 支付方式信息最大长度
         #[pallet::constant]
         type MaxPaymentLength: Get<u32>;
+
+        /// 每个机构的收款方式条目上限
+        #[pallet::constant]
+        type MaxPaymentEntries: Get<u32>;
+
+        /// 收款方式显示名称最大长度
+        #[pallet::constant]
+        type MaxPaymentLabelLength: Get<u32>;
+
+        /// 机构信息及成员权限查询
+        type Institutions: InstitutionInspect<Self::AccountId>;
+
+        /// 可调用函数的权重
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
-    /// 支付方式结构
-    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// v1 迁移将 v0 的微信、支付宝、代币、其他四个字段各转为一个条目，条目上限不能低于 4
+        fn integrity_test() {
+            assert!(T::MaxPaymentEntries::get() >= 4, "MaxPaymentEntries must hold the four v0 payment fields");
+        }
+    }
+
+    /// 支付方式字段
+    pub type PaymentText<T> = BoundedVec<u8, <T as Config>::MaxPaymentLength>;
+
+    /// 收款方式类型及收款信息
+    #[derive(CloneNoBound, Encode, Decode, DecodeWithMemTracking, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub enum PaymentKind<T: Config> {
+        /// 银行转账
+        BankTransfer {
+            bank: PaymentText<T>,    // 开户银行
+            branch: PaymentText<T>,  // 开户支行
+            account: PaymentText<T>, // 银行账号
+        },
+        /// 微信支付
+        WeChat { account: PaymentText<T> },
+        /// 支付宝
+        Alipay { account: PaymentText<T> },
+        /// 链上资产
+        OnChainAsset {
+            asset_id: u32,           // 资产ID
+            address: PaymentText<T>, // 收款地址
+        },
+        /// 现金（到店或货到付款）
+        Cash,
+        /// 其他方式
+        Other { details: PaymentText<T> },
+    }
+
+    impl<T: Config> PaymentKind<T> {
+        /// 收款信息是否完整：账号、地址等必填字段不能为空
+        pub fn is_complete(&self) -> bool {
+            match self {
+                PaymentKind::BankTransfer { bank, account, .. } => !bank.is_empty() && !account.is_empty(),
+                PaymentKind::WeChat { account } | PaymentKind::Alipay { account } => !account.is_empty(),
+                PaymentKind::OnChainAsset { address, .. } => !address.is_empty(),
+                PaymentKind::Cash => true,
+                PaymentKind::Other { details } => !details.is_empty(),
+            }
+        }
+    }
+
+    /// 收款方式条目
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct PaymentEntry<T: Config> {
+        pub id: u32,                                          // 条目ID，机构内唯一
+        pub kind: PaymentKind<T>,                             // 收款方式类型及收款信息
+        pub label: BoundedVec<u8, T::MaxPaymentLabelLength>, // 显示名称
+        pub enabled: bool,                                    // 是否启用
+        pub priority: u32,                                    // 显示顺序，数值越小越靠前
+    }
+
+    /// 机构支付方式结构
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen, frame_support::DefaultNoBound)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
     pub struct PaymentMethod<T: Config> {
-        pub wechat: Option<BoundedVec<u8, T::MaxPaymentLength>>,
-        pub alipay: Option<BoundedVec<u8, T::MaxPaymentLength>>,
-        pub token: Option<BoundedVec<u8, T::MaxPaymentLength>>,
-        pub other: Option<BoundedVec<u8, T::MaxPaymentLength>>,
+        pub entries: BoundedVec<PaymentEntry<T>, T::MaxPaymentEntries>, // 收款方式条目
+        pub next_entry_id: u32,                                         // 下一个条目ID
     }
 
     /// 支付方式存储映射
@@ -52,13 +146,72 @@ pub mod pallet {
         OptionQuery,                               // 查询策略：如果键不存在，返回 None
     >;
 
+    /// 创世收款方式条目 [类型(0:银行转账,1:微信,2:支付宝,3:链上资产,4:现金,5:其他), 收款信息, 资产ID, 显示名称, 是否启用, 显示顺序]
+    ///
+    /// 收款信息按类型依次填写：银行转账为开户银行、开户支行、银行账号，链上资产为收款地址，现金为空；资产ID仅链上资产使用。
+    pub type GenesisPaymentEntry = (u8, Vec<Vec<u8>>, u32, Vec<u8>, bool, u32);
+
+    /// 创世配置
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// 初始支付方式 [机构ID, 收款方式条目]
+        pub payment_methods: Vec<(Vec<u8>, Vec<GenesisPaymentEntry>)>,
+        #[serde(skip)]
+        pub _config: core::marker::PhantomData<T>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        /// 写入机构的初始支付方式
+        fn build(&self) {
+            let text = |details: &Vec<Vec<u8>>, index: usize| {
+                PaymentText::<T>::try_from(details.get(index).cloned().unwrap_or_default())
+                    .expect("genesis payment detail too long")
+            };
+
+            for (institution_id, entries) in &self.payment_methods {
+                let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.clone())
+                    .expect("genesis institution id too long");
+                assert!(T::Institutions::exists(&bounded_id), "genesis payment method for unknown institution");
+                assert!(!entries.is_empty(), "genesis payment method must set at least one entry");
+                assert!(!PaymentMethods::<T>::contains_key(&bounded_id), "duplicate genesis payment method");
+
+                let mut payment = PaymentMethod::<T>::default();
+                for (kind, details, asset_id, label, enabled, priority) in entries {
+                    let kind = match kind {
+                        0 => PaymentKind::BankTransfer {
+                            bank: text(details, 0),
+                            branch: text(details, 1),
+                            account: text(details, 2),
+                        },
+                        1 => PaymentKind::WeChat { account: text(details, 0) },
+                        2 => PaymentKind::Alipay { account: text(details, 0) },
+                        3 => PaymentKind::OnChainAsset { asset_id: *asset_id, address: text(details, 0) },
+                        4 => PaymentKind::Cash,
+                        5 => PaymentKind::Other { details: text(details, 0) },
+                        _ => panic!("invalid genesis payment kind"),
+                    };
+                    let label = BoundedVec::<u8, T::MaxPaymentLabelLength>::try_from(label.clone())
+                        .expect("genesis payment label too long");
+                    Pallet::<T>::push_entry(&mut payment, kind, label, *enabled, *priority)
+                        .expect("invalid genesis payment entry");
+                }
+
+                PaymentMethods::<T>::insert(&bounded_id, payment);
+            }
+        }
+    }
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// 支付方式已创建 [机构ID, 创建者]
-        PaymentMethodCreated(BoundedVec<u8, T::MaxInstitutionIdLength>, T::AccountId),
-        /// 支付方式已更新 [机构ID]
-        PaymentMethodUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>),
+        /// 收款方式条目已添加 [机构ID, 条目ID, 操作者]
+        PaymentEntryAdded(BoundedVec<u8, T::MaxInstitutionIdLength>, u32, T::AccountId),
+        /// 收款方式条目已更新 [机构ID, 条目ID]
+        PaymentEntryUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>, u32),
+        /// 收款方式条目已删除 [机构ID, 条目ID]
+        PaymentEntryRemoved(BoundedVec<u8, T::MaxInstitutionIdLength>, u32),
         /// 支付方式已删除 [机构ID]
         PaymentMethodDeleted(BoundedVec<u8, T::MaxInstitutionIdLength>),
     }
@@ -75,22 +228,24 @@ pub mod pallet {
         NotAuthorized,
         /// 字符串转换错误
         StringConversionError,
-        /// 至少需要设置一种支付方式
-        AtLeastOnePaymentMethodRequired,
+        /// 机构已停用或暂停营业
+        InstitutionInactive,
+        /// 收款方式条目数量超出上限
+        TooManyPaymentEntries,
+        /// 收款方式条目不存在
+        PaymentEntryNotFound,
+        /// 收款信息不完整
+        IncompletePaymentDetails,
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// 创建或更新机构的支付方式
-        #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
-        pub fn set_payment_method(
+        /// 删除机构的支付方式
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::remove_payment_method())]
+        pub fn remove_payment_method(
             origin: OriginFor<T>,
             institution_id: Vec<u8>,
-            wechat: Option<Vec<u8>>,
-            alipay: Option<Vec<u8>>,
-            token: Option<Vec<u8>>,
-            other: Option<Vec<u8>>,
         ) -> DispatchResult {
             // 确认调用者身份
             let who = ensure_signed(origin)?;
@@ -99,127 +254,140 @@ pub mod pallet {
             let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
                 .map_err(|_| Error::<T>::StringConversionError)?;
             
-            // 确保至少设置了一种支付方式
-            ensure!(
-                wechat.is_some() || alipay.is_some() || token.is_some() || other.is_some(),
-                Error::<T>::AtLeastOnePaymentMethodRequired
-            );
+            // 检查机构状态及调用者权限
+            Self::ensure_payment_manager(&bounded_id, &who)?;
             
-            // 转换支付方式字段为边界向量
-            let bounded_wechat = match wechat {
-                Some(w) => Some(BoundedVec::<u8, T::MaxPaymentLength>::try_from(w)
-                    .map_err(|_| Error::<T>::StringConversionError)?),
-                None => None,
-            };
+            // 检查支付方式是否存在
+            ensure!(PaymentMethods::<T>::contains_key(&bounded_id), Error::<T>::PaymentMethodNotFound);
             
-            let bounded_alipay = match alipay {
-                Some(a) => Some(BoundedVec::<u8, T::MaxPaymentLength>::try_from(a)
-                    .map_err(|_| Error::<T>::StringConversionError)?),
-                None => None,
-            };
+            // 删除支付方式
+            PaymentMethods::<T>::remove(&bounded_id);
             
-            let bounded_token = match token {
-                Some(t) => Some(BoundedVec::<u8, T::MaxPaymentLength>::try_from(t)
-                    .map_err(|_| Error::<T>::StringConversionError)?),
-                None => None,
-            };
+            // 发出事件
+            Self::deposit_event(Event::PaymentMethodDeleted(bounded_id));
             
-            let bounded_other = match other {
-                Some(o) => Some(BoundedVec::<u8, T::MaxPaymentLength>::try_from(o)
-                    .map_err(|_| Error::<T>::StringConversionError)?),
-                None => None,
-            };
+            Ok(())
+        }
+        
+        // 调用索引 0、2 为已移除的按固定字段设置支付方式的接口，不再复用
+        
+        /// 添加收款方式条目
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::add_payment_entry())]
+        pub fn add_payment_entry(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            kind: PaymentKind<T>,
+            label: Vec<u8>,
+            enabled: bool,
+            priority: u32,
+        ) -> DispatchResult {
+            // 确认调用者身份
+            let who = ensure_signed(origin)?;
             
-            // 创建支付方式结构
-            let payment_method = PaymentMethod {
-                wechat: bounded_wechat,
-                alipay: bounded_alipay,
-                token: bounded_token,
-                other: bounded_other,
-            };
+            // 转换为边界向量
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            let bounded_label = BoundedVec::<u8, T::MaxPaymentLabelLength>::try_from(label)
+                .map_err(|_| Error::<T>::StringConversionError)?;
             
-            // 检查是否已存在支付方式
-            let is_update = PaymentMethods::<T>::contains_key(&bounded_id);
+            // 检查机构状态及调用者权限
+            Self::ensure_payment_manager(&bounded_id, &who)?;
             
-            // 存储支付方式
-            PaymentMethods::<T>::insert(&bounded_id, payment_method);
+            let entry_id = PaymentMethods::<T>::try_mutate(&bounded_id, |maybe_payment| -> Result<u32, DispatchError> {
+                let payment = maybe_payment.get_or_insert_with(PaymentMethod::default);
+                Self::push_entry(payment, kind, bounded_label, enabled, priority)
+            })?;
             
-            // 发出相应事件
-            if is_update {
-                Self::deposit_event(Event::PaymentMethodUpdated(bounded_id));
-            } else {
-                Self::deposit_event(Event::PaymentMethodCreated(bounded_id, who));
-            }
+            // 发出事件
+            Self::deposit_event(Event::PaymentEntryAdded(bounded_id, entry_id, who));
             
             Ok(())
         }
         
-        /// 删除机构的支付方式
-        #[pallet::call_index(1)]
-        #[pallet::weight(5_000)]
-        pub fn remove_payment_method(
+        /// 更新收款方式条目
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::update_payment_entry())]
+        pub fn update_payment_entry(
             origin: OriginFor<T>,
             institution_id: Vec<u8>,
+            entry_id: u32,
+            kind: PaymentKind<T>,
+            label: Vec<u8>,
+            enabled: bool,
+            priority: u32,
         ) -> DispatchResult {
             // 确认调用者身份
-            let _who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
             
             // 转换为边界向量
             let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
                 .map_err(|_| Error::<T>::StringConversionError)?;
+            let bounded_label = BoundedVec::<u8, T::MaxPaymentLabelLength>::try_from(label)
+                .map_err(|_| Error::<T>::StringConversionError)?;
             
-            // 检查支付方式是否存在
-            ensure!(PaymentMethods::<T>::contains_key(&bounded_id), Error::<T>::PaymentMethodNotFound);
+            // 检查机构状态及调用者权限
+            Self::ensure_payment_manager(&bounded_id, &who)?;
+            ensure!(kind.is_complete(), Error::<T>::IncompletePaymentDetails);
             
-            // 删除支付方式
-            PaymentMethods::<T>::remove(&bounded_id);
+            PaymentMethods::<T>::try_mutate(&bounded_id, |maybe_payment| -> DispatchResult {
+                let payment = maybe_payment.as_mut().ok_or(Error::<T>::PaymentMethodNotFound)?;
+                let entry = payment
+                    .entries
+                    .iter_mut()
+                    .find(|entry| entry.id == entry_id)
+                    .ok_or(Error::<T>::PaymentEntryNotFound)?;
+                entry.kind = kind;
+                entry.label = bounded_label;
+                entry.enabled = enabled;
+                entry.priority = priority;
+                Ok(())
+            })?;
             
             // 发出事件
-            Self::deposit_event(Event::PaymentMethodDeleted(bounded_id));
+            Self::deposit_event(Event::PaymentEntryUpdated(bounded_id, entry_id));
             
             Ok(())
         }
         
-        /// 更新特定支付方式字段
-        #[pallet::call_index(2)]
-        #[pallet::weight(7_000)]
-        pub fn update_payment_field(
+        /// 删除收款方式条目，删除最后一个条目时一并删除机构的支付方式
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::remove_payment_entry())]
+        pub fn remove_payment_entry(
             origin: OriginFor<T>,
             institution_id: Vec<u8>,
-            field_type: u8, // 0: wechat, 1: alipay, 2: token, 3: other
-            value: Option<Vec<u8>>,
+            entry_id: u32,
         ) -> DispatchResult {
             // 确认调用者身份
-            let _who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
             
             // 转换为边界向量
             let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
                 .map_err(|_| Error::<T>::StringConversionError)?;
             
-            // 转换值为边界向量
-            let bounded_value = match value {
-                Some(v) => Some(BoundedVec::<u8, T::MaxPaymentLength>::try_from(v)
-                    .map_err(|_| Error::<T>::StringConversionError)?),
-                None => None,
-            };
+            // 检查机构状态及调用者权限
+            Self::ensure_payment_manager(&bounded_id, &who)?;
             
-            // 更新支付方式
-            PaymentMethods::<T>::try_mutate(&bounded_id, |maybe_payment| -> DispatchResult {
+            let removed_all = PaymentMethods::<T>::try_mutate_exists(&bounded_id, |maybe_payment| -> Result<bool, DispatchError> {
                 let payment = maybe_payment.as_mut().ok_or(Error::<T>::PaymentMethodNotFound)?;
-                
-                match field_type {
-                    0 => payment.wechat = bounded_value,
-                    1 => payment.alipay = bounded_value,
-                    2 => payment.token = bounded_value,
-                    3 => payment.other = bounded_value,
-                    _ => return Err(Error::<T>::StringConversionError.into()),
+                let index = payment
+                    .entries
+                    .iter()
+                    .position(|entry| entry.id == entry_id)
+                    .ok_or(Error::<T>::PaymentEntryNotFound)?;
+                payment.entries.remove(index);
+                if payment.entries.is_empty() {
+                    *maybe_payment = None;
+                    return Ok(true);
                 }
-                
-                Ok(())
+                Ok(false)
             })?;
             
             // 发出事件
-            Self::deposit_event(Event::PaymentMethodUpdated(bounded_id));
+            Self::deposit_event(Event::PaymentEntryRemoved(bounded_id.clone(), entry_id));
+            if removed_all {
+                Self::deposit_event(Event::PaymentMethodDeleted(bounded_id));
+            }
             
             Ok(())
         }
@@ -231,9 +399,71 @@ pub mod pallet {
             PaymentMethods::<T>::get(institution_id)
         }
         
-        /// 检查机构是否设置了支付方式
+        /// 检查机构是否设置了已启用的收款方式
         pub fn has_payment_method(institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>) -> bool {
-            PaymentMethods::<T>::contains_key(institution_id)
+            PaymentMethods::<T>::get(institution_id).is_some_and(|payment| payment.entries.iter().any(|entry| entry.enabled))
+        }
+        
+        /// 获取机构已启用的收款方式条目，按显示顺序排列
+        pub fn enabled_entries(institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>) -> Vec<PaymentEntry<T>> {
+            let mut entries: Vec<PaymentEntry<T>> = PaymentMethods::<T>::get(institution_id)
+                .map(|payment| payment.entries.into_iter().filter(|entry| entry.enabled).collect())
+                .unwrap_or_default();
+            entries.sort_by_key(|entry| (entry.priority, entry.id));
+            entries
+        }
+        
+        /// 校验收款信息并追加条目，返回新条目ID
+        fn push_entry(
+            payment: &mut PaymentMethod<T>,
+            kind: PaymentKind<T>,
+            label: BoundedVec<u8, T::MaxPaymentLabelLength>,
+            enabled: bool,
+            priority: u32,
+        ) -> Result<u32, DispatchError> {
+            ensure!(kind.is_complete(), Error::<T>::IncompletePaymentDetails);
+            
+            let id = payment.next_entry_id;
+            payment
+                .entries
+                .try_push(PaymentEntry { id, kind, label, enabled, priority })
+                .map_err(|_| Error::<T>::TooManyPaymentEntries)?;
+            payment.next_entry_id = id.saturating_add(1);
+            
+            Ok(id)
+        }
+        
+        /// 确认机构存在且正常营业，调用者为机构所有者或拥有财务角色
+        fn ensure_payment_manager(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            who: &T::AccountId,
+        ) -> DispatchResult {
+            ensure!(T::Institutions::exists(institution_id), Error::<T>::InstitutionNotFound);
+            ensure!(T::Institutions::is_active(institution_id), Error::<T>::InstitutionInactive);
+            ensure!(
+                T::Institutions::has_role(institution_id, who, InstitutionRole::Finance),
+                Error::<T>::NotAuthorized
+            );
+            Ok(())
+        }
+    }
+
+    impl<T: Config> pallet_institution::OnInstitutionRemoved for Pallet<T> {
+        fn can_remove(_institution_id: &[u8]) -> DispatchResult {
+            Ok(())
+        }
+        
+        /// 删除机构的支付方式
+        fn on_institution_removed(institution_id: &[u8], _limit: u32) -> u32 {
+            let Ok(bounded_id) = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec()) else {
+                return 0;
+            };
+            
+            if PaymentMethods::<T>::take(&bounded_id).is_none() {
+                return 0;
+            }
+            Self::deposit_event(Event::PaymentMethodDeleted(bounded_id));
+            1
         }
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
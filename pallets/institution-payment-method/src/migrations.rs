@@ -0,0 +1,115 @@
+//! 机构支付方式模块存储迁移
+
+/// v1：固定的微信、支付宝、代币、其他字段改为收款方式条目列表
+///
+/// 原有字段按微信、支付宝、代币、其他的顺序转为已启用的条目，显示顺序依次递增。
+/// 原代币字段只记录了代币名称，没有资产ID和收款地址，转为“其他”类型保留原文。
+pub mod v1 {
+    use crate::pallet::{Config, Pallet, PaymentEntry, PaymentKind, PaymentMethod, PaymentMethods, PaymentText};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    use sp_std::vec::Vec;
+
+    /// v0 版本的支付方式结构
+    #[derive(Encode, Decode)]
+    pub struct OldPaymentMethod<T: Config> {
+        pub wechat: Option<PaymentText<T>>,
+        pub alipay: Option<PaymentText<T>>,
+        pub token: Option<PaymentText<T>>,
+        pub other: Option<PaymentText<T>>,
+    }
+
+    impl<T: Config> OldPaymentMethod<T> {
+        /// 已填写的字段数，迁移后每个字段对应一个条目
+        pub fn field_count(&self) -> u32 {
+            [&self.wechat, &self.alipay, &self.token, &self.other].iter().filter(|field| field.is_some()).count() as u32
+        }
+    }
+
+    /// v0 版本的存储定义
+    pub mod v0 {
+        use super::*;
+
+        /// v0 版本的支付方式存储
+        #[frame_support::storage_alias]
+        pub type PaymentMethods<T: Config> = StorageMap<
+            Pallet<T>,
+            Blake2_128Concat,
+            BoundedVec<u8, <T as Config>::MaxInstitutionIdLength>,
+            OldPaymentMethod<T>,
+            OptionQuery,
+        >;
+    }
+
+    /// 未检查存储版本的迁移逻辑
+    ///
+    /// 条目上限不低于 4 由 `integrity_test` 保证，v0 的四个字段都能转为条目。
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            let (records, fields) = v0::PaymentMethods::<T>::iter_values()
+                .fold((0u32, 0u32), |(records, fields), old| (records + 1, fields + old.field_count()));
+            Ok((records, fields).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            PaymentMethods::<T>::translate::<OldPaymentMethod<T>, _>(|_, old| {
+                translated += 1;
+                let kinds: Vec<(PaymentKind<T>, &[u8])> = [
+                    old.wechat.map(|account| (PaymentKind::WeChat { account }, "微信".as_bytes())),
+                    old.alipay.map(|account| (PaymentKind::Alipay { account }, "支付宝".as_bytes())),
+                    old.token.map(|details| (PaymentKind::Other { details }, "代币".as_bytes())),
+                    old.other.map(|details| (PaymentKind::Other { details }, "其他".as_bytes())),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+
+                let mut payment = PaymentMethod::<T>::default();
+                for (kind, label) in kinds {
+                    let id = payment.next_entry_id;
+                    let entry = PaymentEntry {
+                        id,
+                        kind,
+                        label: BoundedVec::truncate_from(label.to_vec()),
+                        enabled: true,
+                        priority: id,
+                    };
+                    if payment.entries.try_push(entry).is_err() {
+                        frame_support::defensive!("MaxPaymentEntries is below the four v0 payment fields");
+                        break;
+                    }
+                    payment.next_entry_id = id + 1;
+                }
+                Some(payment)
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let (records, fields) = <(u32, u32)>::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let (records_after, entries_after) = PaymentMethods::<T>::iter_values()
+                .fold((0u32, 0u32), |(records, entries), payment| (records + 1, entries + payment.entries.len() as u32));
+            ensure!(records == records_after, "payment methods lost during migration");
+            ensure!(fields == entries_after, "payment details dropped during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v0 迁移到 v1，仅在链上存储版本为 0 时执行
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -0,0 +1,260 @@
+use crate::{mock::*, Error, Event, PaymentKind, PaymentMethods};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use pallet_institution::InstitutionRole;
+
+fn bounded(id: &[u8]) -> BoundedVec<u8, frame_support::traits::ConstU32<64>> {
+    BoundedVec::try_from(id.to_vec()).unwrap()
+}
+
+fn wechat(account: &[u8]) -> PaymentKind<Test> {
+    PaymentKind::WeChat { account: BoundedVec::try_from(account.to_vec()).unwrap() }
+}
+
+fn add_wechat(who: u64, id: &[u8], account: &[u8]) -> frame_support::dispatch::DispatchResult {
+    InstitutionPaymentMethod::add_payment_entry(
+        RuntimeOrigin::signed(who),
+        id.to_vec(),
+        wechat(account),
+        b"WeChat".to_vec(),
+        true,
+        0,
+    )
+}
+
+fn update_wechat(who: u64, id: &[u8], entry_id: u32, account: &[u8]) -> frame_support::dispatch::DispatchResult {
+    InstitutionPaymentMethod::update_payment_entry(
+        RuntimeOrigin::signed(who),
+        id.to_vec(),
+        entry_id,
+        wechat(account),
+        b"WeChat".to_vec(),
+        true,
+        0,
+    )
+}
+
+#[test]
+fn owner_manages_payment_method() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_ok!(add_wechat(1, b"INST_001", b"wx-owner"));
+        System::assert_last_event(Event::PaymentEntryAdded(bounded(b"INST_001"), 0, 1).into());
+
+        assert_ok!(update_wechat(1, b"INST_001", 0, b"wx-renamed"));
+        let payment = PaymentMethods::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_eq!(payment.entries[0].kind, wechat(b"wx-renamed"));
+
+        assert_ok!(InstitutionPaymentMethod::remove_payment_method(RuntimeOrigin::signed(1), b"INST_001".to_vec()));
+        assert!(!PaymentMethods::<Test>::contains_key(bounded(b"INST_001")));
+    });
+}
+
+#[test]
+fn stranger_cannot_touch_payment_method() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(add_wechat(1, b"INST_001", b"wx-owner"));
+
+        assert_noop!(add_wechat(2, b"INST_001", b"wx-attacker"), Error::<Test>::NotAuthorized);
+        assert_noop!(update_wechat(2, b"INST_001", 0, b"wx-attacker"), Error::<Test>::NotAuthorized);
+        assert_noop!(
+            InstitutionPaymentMethod::remove_payment_entry(RuntimeOrigin::signed(2), b"INST_001".to_vec(), 0),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            InstitutionPaymentMethod::remove_payment_method(RuntimeOrigin::signed(2), b"INST_001".to_vec()),
+            Error::<Test>::NotAuthorized
+        );
+
+        let payment = PaymentMethods::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_eq!(payment.entries[0].kind, wechat(b"wx-owner"));
+    });
+}
+
+#[test]
+fn finance_role_manages_payment_method() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        // 其他角色的成员无权修改收款信息
+        assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2, InstitutionRole::OrderClerk));
+        assert_noop!(add_wechat(2, b"INST_001", b"wx-clerk"), Error::<Test>::NotAuthorized);
+
+        assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 3, InstitutionRole::Finance));
+        assert_ok!(add_wechat(3, b"INST_001", b"wx-finance"));
+        assert_ok!(InstitutionPaymentMethod::remove_payment_method(RuntimeOrigin::signed(3), b"INST_001".to_vec()));
+
+        // 撤销财务角色后不再有权限
+        assert_ok!(Institution::revoke_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 3, InstitutionRole::Finance));
+        assert_noop!(add_wechat(3, b"INST_001", b"wx-finance"), Error::<Test>::NotAuthorized);
+    });
+}
+
+#[test]
+fn unknown_institution_is_rejected() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(add_wechat(1, b"INST_404", b"wx-ghost"), Error::<Test>::InstitutionNotFound);
+        assert_noop!(
+            InstitutionPaymentMethod::remove_payment_method(RuntimeOrigin::signed(1), b"INST_404".to_vec()),
+            Error::<Test>::InstitutionNotFound
+        );
+    });
+}
+
+#[test]
+fn inactive_institution_is_rejected() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        create_institution(2, b"INST_002");
+        assert_ok!(add_wechat(1, b"INST_001", b"wx-one"));
+
+        assert_ok!(Institution::suspend_institution(RuntimeOrigin::root(), b"INST_001".to_vec(), 10, b"audit".to_vec()));
+        assert_noop!(update_wechat(1, b"INST_001", 0, b"wx-new"), Error::<Test>::InstitutionInactive);
+        assert_noop!(
+            InstitutionPaymentMethod::remove_payment_method(RuntimeOrigin::signed(1), b"INST_001".to_vec()),
+            Error::<Test>::InstitutionInactive
+        );
+
+        // 暂停期满后恢复
+        System::set_block_number(10);
+        assert_ok!(update_wechat(1, b"INST_001", 0, b"wx-new"));
+
+        assert_ok!(Institution::update_institution_status(RuntimeOrigin::signed(2), b"INST_002".to_vec(), 2));
+        assert_noop!(add_wechat(2, b"INST_002", b"wx-two"), Error::<Test>::InstitutionInactive);
+    });
+}
+
+#[test]
+fn entries_are_listed_by_priority() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_ok!(InstitutionPaymentMethod::add_payment_entry(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            PaymentKind::BankTransfer {
+                bank: BoundedVec::try_from(b"Bank".to_vec()).unwrap(),
+                branch: BoundedVec::try_from(b"Branch".to_vec()).unwrap(),
+                account: BoundedVec::try_from(b"6225880123456789".to_vec()).unwrap(),
+            },
+            b"Bank transfer".to_vec(),
+            true,
+            5,
+        ));
+        assert_ok!(InstitutionPaymentMethod::add_payment_entry(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            PaymentKind::Cash,
+            b"Cash".to_vec(),
+            false,
+            0,
+        ));
+        assert_ok!(InstitutionPaymentMethod::add_payment_entry(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            PaymentKind::OnChainAsset { asset_id: 1, address: BoundedVec::try_from(b"5Grwva".to_vec()).unwrap() },
+            b"FMT".to_vec(),
+            true,
+            1,
+        ));
+
+        let ids: Vec<u32> = InstitutionPaymentMethod::enabled_entries(&bounded(b"INST_001")).iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, vec![2, 0]);
+
+        assert_ok!(add_wechat(1, b"INST_001", b"wx-fourth"));
+        assert_noop!(add_wechat(1, b"INST_001", b"wx-fifth"), Error::<Test>::TooManyPaymentEntries);
+    });
+}
+
+#[test]
+fn incomplete_details_are_rejected() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_noop!(add_wechat(1, b"INST_001", b""), Error::<Test>::IncompletePaymentDetails);
+        assert_ok!(add_wechat(1, b"INST_001", b"wx-owner"));
+        assert_noop!(update_wechat(1, b"INST_001", 0, b""), Error::<Test>::IncompletePaymentDetails);
+        assert_noop!(update_wechat(1, b"INST_001", 7, b"wx-other"), Error::<Test>::PaymentEntryNotFound);
+    });
+}
+
+#[test]
+fn removing_last_entry_removes_payment_method() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(add_wechat(1, b"INST_001", b"wx-one"));
+        assert_ok!(add_wechat(1, b"INST_001", b"wx-two"));
+
+        assert_ok!(InstitutionPaymentMethod::remove_payment_entry(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 0));
+        System::assert_last_event(Event::PaymentEntryRemoved(bounded(b"INST_001"), 0).into());
+        assert_noop!(
+            InstitutionPaymentMethod::remove_payment_entry(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 0),
+            Error::<Test>::PaymentEntryNotFound
+        );
+
+        // 条目ID不复用
+        assert_ok!(add_wechat(1, b"INST_001", b"wx-three"));
+        let ids: Vec<u32> = PaymentMethods::<Test>::get(bounded(b"INST_001")).unwrap().entries.iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+
+        assert_ok!(InstitutionPaymentMethod::remove_payment_entry(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 1));
+        assert_ok!(InstitutionPaymentMethod::remove_payment_entry(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2));
+        System::assert_last_event(Event::PaymentMethodDeleted(bounded(b"INST_001")).into());
+        assert!(!PaymentMethods::<Test>::contains_key(bounded(b"INST_001")));
+    });
+}
+
+#[test]
+fn migration_converts_fixed_fields_to_entries() {
+    use crate::migrations::v1::{MigrateV0ToV1, OldPaymentMethod};
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let old = OldPaymentMethod::<Test> {
+            wechat: Some(BoundedVec::try_from(b"wx-old".to_vec()).unwrap()),
+            alipay: None,
+            token: Some(BoundedVec::try_from(b"FMT".to_vec()).unwrap()),
+            other: None,
+        };
+        frame_support::storage::unhashed::put(&PaymentMethods::<Test>::hashed_key_for(bounded(b"INST_001")), &old);
+        StorageVersion::new(0).put::<InstitutionPaymentMethod>();
+
+        MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        let payment = PaymentMethods::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_eq!(payment.next_entry_id, 2);
+        assert_eq!(payment.entries[0].kind, wechat(b"wx-old"));
+        assert_eq!(payment.entries[1].kind, PaymentKind::Other { details: BoundedVec::try_from(b"FMT".to_vec()).unwrap() });
+        assert!(payment.entries.iter().all(|entry| entry.enabled));
+        assert_eq!(payment.entries[1].priority, 1);
+        assert_eq!(InstitutionPaymentMethod::on_chain_storage_version(), StorageVersion::new(1));
+    });
+}
+
+#[test]
+fn migration_keeps_all_four_fixed_fields() {
+    use crate::migrations::v1::{v0, MigrateV0ToV1, OldPaymentMethod};
+    use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let text = |value: &[u8]| Some(BoundedVec::try_from(value.to_vec()).unwrap());
+        let old = OldPaymentMethod::<Test> {
+            wechat: text(b"wx-old"),
+            alipay: text(b"ali-old"),
+            token: text(b"FMT"),
+            other: text(b"cash on delivery"),
+        };
+        assert_eq!(old.field_count(), 4);
+        v0::PaymentMethods::<Test>::insert(bounded(b"INST_001"), old);
+        StorageVersion::new(0).put::<InstitutionPaymentMethod>();
+
+        MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        let payment = PaymentMethods::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_eq!(payment.entries.len(), 4);
+        assert_eq!(payment.next_entry_id, 4);
+        assert_eq!(payment.entries[1].kind, PaymentKind::Alipay { account: BoundedVec::try_from(b"ali-old".to_vec()).unwrap() });
+        assert_eq!(payment.entries[3].kind, PaymentKind::Other { details: BoundedVec::try_from(b"cash on delivery".to_vec()).unwrap() });
+    });
+}
@@ -0,0 +1,77 @@
+
+//! Weights for pallet_institution_payment_method
+//!
+//! 尚未运行基准测试，以下权重为按存储访问次数手工估算的值，
+//! 接入基准测试后由 benchmark CLI 生成的结果替换。
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_institution_payment_method.
+pub trait WeightInfo {
+	fn remove_payment_method() -> Weight;
+	fn add_payment_entry() -> Weight;
+	fn update_payment_entry() -> Weight;
+	fn remove_payment_entry() -> Weight;
+}
+
+/// Weights for pallet_institution_payment_method using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: InstitutionPaymentMethod PaymentMethods (r:1 w:1), Institution Institutions (r:1), InstitutionMembers (r:1)
+	fn remove_payment_method() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: InstitutionPaymentMethod PaymentMethods (r:1 w:1), Institution Institutions (r:1), InstitutionMembers (r:1)
+	fn add_payment_entry() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: InstitutionPaymentMethod PaymentMethods (r:1 w:1), Institution Institutions (r:1), InstitutionMembers (r:1)
+	fn update_payment_entry() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: InstitutionPaymentMethod PaymentMethods (r:1 w:1), Institution Institutions (r:1), InstitutionMembers (r:1)
+	fn remove_payment_entry() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: InstitutionPaymentMethod PaymentMethods (r:1 w:1), Institution Institutions (r:1), InstitutionMembers (r:1)
+	fn remove_payment_method() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: InstitutionPaymentMethod PaymentMethods (r:1 w:1), Institution Institutions (r:1), InstitutionMembers (r:1)
+	fn add_payment_entry() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: InstitutionPaymentMethod PaymentMethods (r:1 w:1), Institution Institutions (r:1), InstitutionMembers (r:1)
+	fn update_payment_entry() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: InstitutionPaymentMethod PaymentMethods (r:1 w:1), Institution Institutions (r:1), InstitutionMembers (r:1)
+	fn remove_payment_entry() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
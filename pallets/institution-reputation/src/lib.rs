@@ -0,0 +1,193 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// 机构信誉管理模块
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod runtime_api;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::{
+            traits::{Saturating, UniqueSaturatedInto, Zero},
+            Perbill,
+        },
+        traits::Get,
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_institution::{CommerceOutcome, InstitutionInspect, OnCommerceOutcome, ReputationInspect};
+    use scale_info::TypeInfo;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + scale_info::TypeInfo {
+        /// 事件类型
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// 机构ID最大长度
+        #[pallet::constant]
+        type MaxInstitutionIdLength: Get<u32>;
+
+        /// 信誉分衰减周期（区块数），为 0 时不衰减
+        #[pallet::constant]
+        type DecayPeriod: Get<BlockNumberFor<Self>>;
+
+        /// 每个衰减周期后保留的信誉分比例
+        #[pallet::constant]
+        type DecayRetention: Get<Perbill>;
+
+        /// 机构信息查询
+        type Institutions: InstitutionInspect<Self::AccountId>;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// 机构信誉记录
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+    pub struct ReputationRecord<BlockNumber> {
+        pub completed_orders: u32,     // 已完成订单数
+        pub cancelled_orders: u32,     // 已取消订单数
+        pub refunded_orders: u32,      // 已退款订单数
+        pub c2c_completed_orders: u32, // 已完成 C2C 订单数
+        pub c2c_disputes_lost: u32,    // C2C 公证败诉数
+        pub review_count: u32,         // 商品评价数
+        pub review_total: u32,         // 商品评价总分
+        pub score: u32,                // 信誉分（截至 updated_at 的衰减结果）
+        pub updated_at: BlockNumber,   // 最近一次衰减结算的区块
+    }
+
+    /// 机构信誉存储映射
+    #[pallet::storage]
+    #[pallet::storage_prefix = "Reputations"]
+    pub type Reputations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        ReputationRecord<BlockNumberFor<T>>,       // 值：信誉记录
+        OptionQuery,                               // 查询策略：如果键不存在，返回 None
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// 机构信誉已更新 [机构ID, 交易结果, 新信誉分]
+        ReputationUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>, CommerceOutcome, u32),
+        /// 机构信誉记录已删除 [机构ID]
+        ReputationRemoved(BoundedVec<u8, T::MaxInstitutionIdLength>),
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// 查询机构信誉记录，信誉分衰减至当前区块
+        pub fn reputation(institution_id: &[u8]) -> Option<ReputationRecord<BlockNumberFor<T>>> {
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec()).ok()?;
+            let mut record = Reputations::<T>::get(&bounded_id)?;
+            Self::decay(&mut record, frame_system::Pallet::<T>::block_number());
+            Some(record)
+        }
+
+        /// 查询机构当前（已衰减）的信誉分，无记录时为 0
+        pub fn reputation_score(institution_id: &[u8]) -> u32 {
+            Self::reputation(institution_id).map(|record| record.score).unwrap_or_default()
+        }
+
+        /// 交易结果对信誉分的影响
+        ///
+        /// 完成订单 +10，取消 -2，退款 -5，C2C 公证败诉 -30；
+        /// 商品评价以 3 分为中性，每高（低）1 分 +5（-5）。
+        pub fn outcome_delta(outcome: CommerceOutcome) -> i64 {
+            match outcome {
+                CommerceOutcome::OrderCompleted | CommerceOutcome::C2cOrderCompleted => 10,
+                CommerceOutcome::OrderCancelled => -2,
+                CommerceOutcome::OrderRefunded => -5,
+                CommerceOutcome::C2cDisputeLost => -30,
+                CommerceOutcome::ProductReviewed(rating) => (i64::from(rating) - 3) * 5,
+            }
+        }
+
+        /// 按经过的完整衰减周期结算信誉分，不足一个周期的区块留待下次结算
+        fn decay(record: &mut ReputationRecord<BlockNumberFor<T>>, now: BlockNumberFor<T>) {
+            let period = T::DecayPeriod::get();
+            if period.is_zero() || now <= record.updated_at {
+                return;
+            }
+
+            let periods = now.saturating_sub(record.updated_at) / period;
+            if periods.is_zero() {
+                return;
+            }
+
+            let exponent: u32 = periods.unique_saturated_into();
+            record.score = T::DecayRetention::get().saturating_pow(exponent as usize).mul_floor(record.score);
+            record.updated_at = record.updated_at.saturating_add(periods.saturating_mul(period));
+        }
+    }
+
+    impl<T: Config> OnCommerceOutcome for Pallet<T> {
+        /// 更新原始计数并按衰减后的信誉分累加本次结果
+        fn on_commerce_outcome(institution_id: &[u8], outcome: CommerceOutcome) {
+            if !T::Institutions::exists(institution_id) {
+                return;
+            }
+            let Ok(bounded_id) = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec()) else {
+                return;
+            };
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let score = Reputations::<T>::mutate(&bounded_id, |maybe_record| {
+                let record = maybe_record.get_or_insert_with(|| ReputationRecord { updated_at: now, ..Default::default() });
+                Self::decay(record, now);
+
+                match outcome {
+                    CommerceOutcome::OrderCompleted => record.completed_orders.saturating_inc(),
+                    CommerceOutcome::OrderCancelled => record.cancelled_orders.saturating_inc(),
+                    CommerceOutcome::OrderRefunded => record.refunded_orders.saturating_inc(),
+                    CommerceOutcome::C2cOrderCompleted => record.c2c_completed_orders.saturating_inc(),
+                    CommerceOutcome::C2cDisputeLost => record.c2c_disputes_lost.saturating_inc(),
+                    CommerceOutcome::ProductReviewed(rating) => {
+                        record.review_count.saturating_inc();
+                        record.review_total = record.review_total.saturating_add(rating.into());
+                    },
+                }
+
+                let score = i64::from(record.score).saturating_add(Self::outcome_delta(outcome));
+                record.score = score.clamp(0, i64::from(u32::MAX)) as u32;
+                record.score
+            });
+
+            Self::deposit_event(Event::ReputationUpdated(bounded_id, outcome, score));
+        }
+    }
+
+    impl<T: Config> ReputationInspect for Pallet<T> {
+        fn score(institution_id: &[u8]) -> u32 {
+            Self::reputation_score(institution_id)
+        }
+    }
+
+    impl<T: Config> pallet_institution::OnInstitutionRemoved for Pallet<T> {
+        fn can_remove(_institution_id: &[u8]) -> DispatchResult {
+            Ok(())
+        }
+
+        /// 删除机构的信誉记录
+        fn on_institution_removed(institution_id: &[u8], _limit: u32) -> u32 {
+            let Ok(bounded_id) = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec()) else {
+                return 0;
+            };
+
+            if Reputations::<T>::take(&bounded_id).is_none() {
+                return 0;
+            }
+            Self::deposit_event(Event::ReputationRemoved(bounded_id));
+            1
+        }
+    }
+}
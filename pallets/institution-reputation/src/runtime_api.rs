@@ -0,0 +1,15 @@
+//! 机构信誉运行时 API
+
+use crate::ReputationRecord;
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// 查询机构信誉分及原始计数
+    pub trait InstitutionReputationApi<BlockNumber> where BlockNumber: Codec {
+        /// 机构当前（已衰减）的信誉分，无记录时为 0
+        fn reputation_score(institution_id: Vec<u8>) -> u32;
+        /// 机构信誉记录（信誉分已衰减至当前区块），无记录时返回 None
+        fn reputation(institution_id: Vec<u8>) -> Option<ReputationRecord<BlockNumber>>;
+    }
+}
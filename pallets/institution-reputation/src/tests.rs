@@ -0,0 +1,125 @@
+use crate::{mock::*, Event, Reputations};
+use frame_support::{assert_ok, BoundedVec};
+use pallet_institution::{CommerceOutcome, OnCommerceOutcome, ReputationInspect};
+
+fn bounded(id: &[u8]) -> BoundedVec<u8, frame_support::traits::ConstU32<64>> {
+    BoundedVec::try_from(id.to_vec()).unwrap()
+}
+
+fn record(id: &[u8], outcome: CommerceOutcome) {
+    InstitutionReputation::on_commerce_outcome(id, outcome);
+}
+
+#[test]
+fn outcomes_update_counters_and_score() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        record(b"INST_001", CommerceOutcome::OrderCompleted);
+        record(b"INST_001", CommerceOutcome::C2cOrderCompleted);
+        record(b"INST_001", CommerceOutcome::ProductReviewed(5));
+        System::assert_last_event(
+            Event::ReputationUpdated(bounded(b"INST_001"), CommerceOutcome::ProductReviewed(5), 30).into(),
+        );
+
+        record(b"INST_001", CommerceOutcome::OrderRefunded);
+        record(b"INST_001", CommerceOutcome::OrderCancelled);
+        record(b"INST_001", CommerceOutcome::ProductReviewed(1));
+
+        let reputation = InstitutionReputation::reputation(b"INST_001").unwrap();
+        assert_eq!(reputation.completed_orders, 1);
+        assert_eq!(reputation.c2c_completed_orders, 1);
+        assert_eq!(reputation.refunded_orders, 1);
+        assert_eq!(reputation.cancelled_orders, 1);
+        assert_eq!((reputation.review_count, reputation.review_total), (2, 6));
+        assert_eq!(reputation.score, 10 + 10 + 10 - 5 - 2 - 10);
+    });
+}
+
+#[test]
+fn score_never_drops_below_zero() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        record(b"INST_001", CommerceOutcome::OrderCompleted);
+        record(b"INST_001", CommerceOutcome::C2cDisputeLost);
+        assert_eq!(InstitutionReputation::reputation_score(b"INST_001"), 0);
+
+        // 扣到 0 后不留负分
+        record(b"INST_001", CommerceOutcome::OrderCompleted);
+        assert_eq!(InstitutionReputation::reputation_score(b"INST_001"), 10);
+        assert_eq!(InstitutionReputation::reputation(b"INST_001").unwrap().c2c_disputes_lost, 1);
+    });
+}
+
+#[test]
+fn unknown_institution_is_ignored() {
+    new_test_ext().execute_with(|| {
+        record(b"INST_404", CommerceOutcome::OrderCompleted);
+
+        assert!(!Reputations::<Test>::contains_key(bounded(b"INST_404")));
+        assert!(System::events().is_empty());
+    });
+}
+
+#[test]
+fn score_decays_per_whole_period() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        for _ in 0..4 {
+            record(b"INST_001", CommerceOutcome::OrderCompleted);
+        }
+
+        // 不足一个周期不衰减
+        System::set_block_number(10);
+        assert_eq!(InstitutionReputation::reputation_score(b"INST_001"), 40);
+
+        System::set_block_number(11);
+        assert_eq!(InstitutionReputation::reputation_score(b"INST_001"), 20);
+
+        // 三个完整周期：40 × 50%³
+        System::set_block_number(35);
+        assert_eq!(InstitutionReputation::reputation_score(b"INST_001"), 5);
+
+        // 新结果在衰减后的分数上累加，结算区块只前进完整周期
+        record(b"INST_001", CommerceOutcome::OrderCompleted);
+        let stored = Reputations::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_eq!((stored.score, stored.updated_at), (15, 31));
+
+        System::set_block_number(41);
+        assert_eq!(InstitutionReputation::reputation_score(b"INST_001"), 7);
+    });
+}
+
+#[test]
+fn queries_decay_without_writing_storage() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        record(b"INST_001", CommerceOutcome::OrderCompleted);
+        record(b"INST_001", CommerceOutcome::OrderCompleted);
+
+        // 运行时 API 及 ReputationInspect 返回衰减至当前区块的分数
+        System::set_block_number(21);
+        assert_eq!(InstitutionReputation::reputation_score(b"INST_001"), 5);
+        assert_eq!(InstitutionReputation::reputation(b"INST_001").unwrap().score, 5);
+        assert_eq!(<InstitutionReputation as ReputationInspect>::score(b"INST_001"), 5);
+
+        let stored = Reputations::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_eq!((stored.score, stored.updated_at), (20, 1));
+
+        assert_eq!(InstitutionReputation::reputation_score(b"INST_404"), 0);
+        assert!(InstitutionReputation::reputation(b"INST_404").is_none());
+    });
+}
+
+#[test]
+fn record_is_removed_with_institution() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        record(b"INST_001", CommerceOutcome::OrderCompleted);
+
+        assert_ok!(Institution::delete_institution(RuntimeOrigin::signed(1), b"INST_001".to_vec()));
+        assert!(!Reputations::<Test>::contains_key(bounded(b"INST_001")));
+        System::assert_has_event(Event::ReputationRemoved(bounded(b"INST_001")).into());
+    });
+}
@@ -3,14 +3,159 @@
 /// 机构管理模块
 pub use pallet::*;
 
+pub mod weights;
+pub use weights::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// 供其他业务模块查询机构及其成员权限的接口
+pub trait InstitutionInspect<AccountId> {
+    /// 机构是否存在
+    fn exists(institution_id: &[u8]) -> bool;
+    /// 机构是否已认证（分支可继承总部认证）
+    fn is_certified(institution_id: &[u8]) -> bool;
+    /// 机构是否处于暂停营业状态
+    fn is_suspended(institution_id: &[u8]) -> bool;
+    /// 机构是否正常营业（存在且未停用、未处于暂停营业状态）
+    fn is_active(institution_id: &[u8]) -> bool;
+    /// 机构是否持有指定类型且仍有效的核验声明（未过期且核验方仍受信任）
+    fn has_attestation(institution_id: &[u8], claim_type: u32) -> bool;
+    /// 账户是否拥有机构的指定角色（所有者拥有全部角色，管理员拥有除所有者外的全部角色，
+    /// 上级机构的管理员拥有分支除所有者外的全部角色）
+    fn has_role(institution_id: &[u8], who: &AccountId, role: InstitutionRole) -> bool;
+    /// 账户作为所有者持有的机构ID列表
+    fn institutions_of(who: &AccountId) -> sp_std::vec::Vec<sp_std::vec::Vec<u8>>;
+}
+
+/// 机构删除时由依赖模块实现的钩子，可按元组组合多个模块
+pub trait OnInstitutionRemoved {
+    /// 检查机构是否可以删除（例如不存在未结束的订单），返回错误时拒绝删除
+    fn can_remove(institution_id: &[u8]) -> frame_support::dispatch::DispatchResult;
+    /// 机构删除后分批清理依赖数据：最多删除 `limit`（至少为 1）项，返回实际删除的项数，
+    /// 返回值小于 `limit` 表示已全部清理完毕
+    fn on_institution_removed(institution_id: &[u8], limit: u32) -> u32;
+}
+
+/// 订单结算时按机构分润合约分配收入
+pub trait ProfitDistributor<AccountId, Balance> {
+    /// 按机构当前生效的分润合约拆分订单收入 `amount`，平台分成及各受益人的款项依次交由 `pay`
+    /// 支付（款项来源由调用方决定，例如买家的托管款项）；没有生效的分润合约时全额支付给机构所有者
+    fn distribute(
+        institution_id: &[u8],
+        amount: Balance,
+        pay: &mut dyn FnMut(&AccountId, Balance) -> frame_support::dispatch::DispatchResult,
+    ) -> frame_support::dispatch::DispatchResult;
+}
+
+impl<AccountId, Balance> ProfitDistributor<AccountId, Balance> for () {
+    fn distribute(
+        _institution_id: &[u8],
+        _amount: Balance,
+        _pay: &mut dyn FnMut(&AccountId, Balance) -> frame_support::dispatch::DispatchResult,
+    ) -> frame_support::dispatch::DispatchResult {
+        Ok(())
+    }
+}
+
+/// 影响机构信誉的交易结果
+#[derive(
+    Clone, Copy, codec::Encode, codec::Decode, codec::DecodeWithMemTracking, Eq, PartialEq, frame_support::pallet_prelude::RuntimeDebug, scale_info::TypeInfo,
+)]
+pub enum CommerceOutcome {
+    /// 订单已完成
+    OrderCompleted,
+    /// 订单已取消
+    OrderCancelled,
+    /// 订单已退款
+    OrderRefunded,
+    /// C2C 订单已完成
+    C2cOrderCompleted,
+    /// C2C 订单公证后被取消（机构败诉）
+    C2cDisputeLost,
+    /// 商品评价，评分 1-5
+    ProductReviewed(u8),
+}
+
+/// 交易结果回调，由信誉模块实现，订单及商品模块调用
+pub trait OnCommerceOutcome {
+    /// 记录机构的一次交易结果
+    fn on_commerce_outcome(institution_id: &[u8], outcome: CommerceOutcome);
+}
+
+impl OnCommerceOutcome for () {
+    fn on_commerce_outcome(_institution_id: &[u8], _outcome: CommerceOutcome) {}
+}
+
+/// 供其他业务模块查询机构信誉分的接口
+pub trait ReputationInspect {
+    /// 机构当前（已衰减）的信誉分
+    fn score(institution_id: &[u8]) -> u32;
+}
+
+impl ReputationInspect for () {
+    fn score(_institution_id: &[u8]) -> u32 {
+        0
+    }
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl OnInstitutionRemoved for Tuple {
+    fn can_remove(institution_id: &[u8]) -> frame_support::dispatch::DispatchResult {
+        for_tuples!( #( Tuple::can_remove(institution_id)?; )* );
+        Ok(())
+    }
+
+    fn on_institution_removed(institution_id: &[u8], limit: u32) -> u32 {
+        let mut removed = 0u32;
+        for_tuples!( #(
+            if removed < limit {
+                removed = removed.saturating_add(Tuple::on_institution_removed(institution_id, limit - removed));
+            }
+        )* );
+        removed
+    }
+}
+
+pub mod migrations;
+pub mod runtime_api;
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::{pallet_prelude::*, traits::Get};
+    use codec::{DecodeAll, DecodeWithMemTracking};
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::{
+            traits::{Hash as _, Saturating, Verify, Zero},
+            Perbill,
+        },
+        traits::{
+            fungible::{Inspect, Mutate, MutateHold},
+            tokens::Precision,
+            Get,
+        },
+    };
     use frame_system::pallet_prelude::*;
+    use crate::OnInstitutionRemoved as _;
+    use crate::WeightInfo;
     use scale_info::TypeInfo;
+    use sp_core::{ed25519, sr25519, H256};
     use sp_std::prelude::*;
     use sp_std::vec::Vec;
 
+    /// 当前存储版本
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
+    /// 核验方签名的上下文前缀，签名内容为前缀与 SCALE 编码声明的拼接
+    pub const ATTESTATION_CONTEXT: &[u8] = b"fissionmall/attestation:";
+
+    /// 押金余额类型
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
     #[pallet::config]
     pub trait Config: frame_system::Config + scale_info::TypeInfo {
         /// 事件类型
@@ -32,36 +177,391 @@ pub mod pallet {
         #[pallet::constant]
         type MaxBusinessScopeLength: Get<u32>;
         
-        /// 合约最大长度
+        /// 合约最大长度（旧版自由格式合约）
         #[pallet::constant]
         type MaxContractLength: Get<u32>;
+
+        /// 分润合约受益人最大数量
+        #[pallet::constant]
+        type MaxBeneficiaries: Get<u32>;
+
+        /// 收取平台分成的账户
+        type PlatformAccount: Get<Self::AccountId>;
+
+        /// 有权认证或撤销认证机构的来源（root、理事会或登记员集合）
+        type CertifyOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// 撤销认证原因最大长度
+        #[pallet::constant]
+        type MaxRevocationReasonLength: Get<u32>;
+
+        /// 有权增删登记员的特权来源
+        type RegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// 登记员最大数量
+        #[pallet::constant]
+        type MaxRegistrars: Get<u32>;
+
+        /// 机构通过认证所需的审核通过数
+        #[pallet::constant]
+        type ApprovalThreshold: Get<u32>;
+
+        /// 驳回审核案件所需的驳回数
+        #[pallet::constant]
+        type RejectionThreshold: Get<u32>;
+
+        /// 审核意见最大长度
+        #[pallet::constant]
+        type MaxAuditCommentLength: Get<u32>;
+
+        /// 每个机构保留的历史审核案件最大数量
+        #[pallet::constant]
+        type MaxAuditHistory: Get<u32>;
+
+        /// 所有权转让提议的有效期（区块数）
+        #[pallet::constant]
+        type OwnerTransferPeriod: Get<BlockNumberFor<Self>>;
+
+        /// 用于冻结存储押金及支付分润的货币
+        type Currency: Mutate<Self::AccountId>
+            + MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// 运行时冻结原因
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// 创建机构的基础押金
+        #[pallet::constant]
+        type DepositBase: Get<BalanceOf<Self>>;
+
+        /// 机构记录每字节押金
+        #[pallet::constant]
+        type DepositPerByte: Get<BalanceOf<Self>>;
+
+        /// 机构删除时的依赖检查与级联清理钩子
+        type OnInstitutionRemoved: crate::OnInstitutionRemoved;
+
+        /// 发证机关代码最大长度
+        #[pallet::constant]
+        type MaxAuthorityCodeLength: Get<u32>;
+
+        /// 营业执照注册号最大长度
+        #[pallet::constant]
+        type MaxRegistrationNumberLength: Get<u32>;
+
+        /// 营业执照到期前提前发出预警的区块数
+        #[pallet::constant]
+        type LicenseExpiryWarningPeriod: Get<BlockNumberFor<Self>>;
+
+        /// 同一区块内到期（或预警）的营业执照最大数量
+        #[pallet::constant]
+        type MaxLicenseExpiriesPerBlock: Get<u32>;
+
+        /// 总部/分支层级的最大深度（总部之下的层数）
+        #[pallet::constant]
+        type MaxHierarchyDepth: Get<u32>;
+
+        /// 每个机构的直属分支最大数量
+        #[pallet::constant]
+        type MaxBranches: Get<u32>;
+
+        /// 每个机构保留的信息修订记录最大数量
+        #[pallet::constant]
+        type MaxRevisions: Get<u32>;
+
+        /// 有权暂停或恢复机构营业的特权来源
+        type SuspendOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// 暂停原因最大长度
+        #[pallet::constant]
+        type MaxSuspensionReasonLength: Get<u32>;
+
+        /// 同一区块内自动解除的暂停最大数量
+        #[pallet::constant]
+        type MaxSuspensionsPerBlock: Get<u32>;
+
+        /// 有权增删受信任核验方公钥的特权来源
+        type AttestorOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// 受信任核验方公钥最大数量
+        #[pallet::constant]
+        type MaxAttestors: Get<u32>;
+
+        /// 机构目录分页查询每页最大条数
+        #[pallet::constant]
+        type MaxDirectoryPageSize: Get<u32>;
+
+        /// 机构目录按名称前缀查询时单次最多检查的机构数
+        #[pallet::constant]
+        type MaxDirectoryScan: Get<u32>;
+
+        /// 已删除机构的依赖数据每批最多清理的条数
+        #[pallet::constant]
+        type CleanupBatchSize: Get<u32>;
+
+        /// 可调用函数的权重
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
+    /// 本模块的资金冻结原因
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// 机构记录存储押金
+        #[codec(index = 0)]
+        InstitutionDeposit,
+    }
+
     /// 机构状态枚举
-    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub enum InstitutionStatus<T: Config> {
+        Certified,           // 已认证
+        NotCertified,        // 未认证
+        Deactivated,         // 注销
+        Suspended {          // 暂停营业
+            until: BlockNumberFor<T>,                             // 自动解除区块
+            reason: BoundedVec<u8, T::MaxSuspensionReasonLength>, // 暂停原因
+        },
+    }
+
+    impl<T: Config> InstitutionStatus<T> {
+        /// 状态编码（事件中使用）
+        pub fn code(&self) -> u8 {
+            match self {
+                InstitutionStatus::Certified => 0,
+                InstitutionStatus::NotCertified => 1,
+                InstitutionStatus::Deactivated => 2,
+                InstitutionStatus::Suspended { .. } => 3,
+            }
+        }
+    }
+
+    /// 机构成员角色枚举
+    #[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[repr(u8)]
-    pub enum InstitutionStatus {
-        Certified = 0,       // 已认证
-        NotCertified = 1,    // 未认证
-        Deactivated = 2,     // 注销
+    pub enum InstitutionRole {
+        Owner = 0,          // 所有者（即机构创建者，不可授予）
+        Admin = 1,          // 管理员
+        ProductManager = 2, // 商品管理
+        OrderClerk = 3,     // 订单处理
+        Finance = 4,        // 财务
+    }
+
+    /// 分润受益人
+    #[derive(CloneNoBound, Encode, Decode, DecodeWithMemTracking, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct ProfitShare<T: Config> {
+        pub beneficiary: T::AccountId, // 受益人账户
+        pub share: Perbill,            // 分成比例（扣除平台分成后）
+    }
+
+    /// 结构化分润定义
+    #[derive(CloneNoBound, Encode, Decode, DecodeWithMemTracking, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct RevenueSplit<T: Config> {
+        pub shares: BoundedVec<ProfitShare<T>, T::MaxBeneficiaries>, // 受益人列表，比例之和须为100%
+        pub platform_cut: Option<Perbill>,                           // 平台分成比例
+        pub effective_from: BlockNumberFor<T>,                       // 生效区块
+        pub effective_until: Option<BlockNumberFor<T>>,              // 失效区块（不含）
+    }
+
+    /// 分润合约
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub enum ProfitContract<T: Config> {
+        /// 迁移前的自由格式合约，链上不执行
+        Legacy(BoundedVec<u8, T::MaxContractLength>),
+        /// 结构化分润定义
+        Split(RevenueSplit<T>),
+    }
+
+    /// 上级机构（总部）关联
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct ParentLink<T: Config> {
+        pub parent_id: BoundedVec<u8, T::MaxInstitutionIdLength>, // 上级机构ID
+        pub inherit_certification: bool,                          // 是否继承上级认证状态
+        pub inherit_profit_contract: bool,                        // 是否继承上级分润合约
     }
 
     /// 机构信息结构
-    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
     pub struct InstitutionInfo<T: Config> {
         pub institution_name: BoundedVec<u8, T::MaxNameLength>, // 机构名称
-        pub status: InstitutionStatus,                         // 机构状态
+        pub status: InstitutionStatus<T>,                      // 机构状态
         pub institution_full_name: BoundedVec<u8, T::MaxNameLength>, // 机构全名
         pub license_image_url: BoundedVec<u8, T::MaxNameLength>, // 营业执照图片URL
         pub responsible_person: BoundedVec<u8, T::MaxResponsiblePersonLength>, // 负责人
         pub business_scope: BoundedVec<u8, T::MaxBusinessScopeLength>, // 经营范围
-        pub profit_contract: Option<BoundedVec<u8, T::MaxContractLength>>, // 分润合约
+        pub profit_contract: Option<ProfitContract<T>>,        // 分润合约
         pub created_date: frame_system::pallet_prelude::BlockNumberFor<T>, // 创建日期
         pub creator: T::AccountId,                             // 创建者
+        pub parent: Option<ParentLink<T>>,                     // 上级机构
+    }
+
+    /// 认证记录结构
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct CertificationRecord<T: Config> {
+        pub certifier: Option<T::AccountId>,                   // 认证人（非签名来源时为空）
+        pub certified_at: BlockNumberFor<T>,                   // 认证区块
+        pub revoked_at: Option<BlockNumberFor<T>>,             // 撤销区块
+        pub revocation_reason: Option<BoundedVec<u8, T::MaxRevocationReasonLength>>, // 撤销原因
+    }
+
+    /// 审核案件状态枚举
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[repr(u8)]
+    pub enum AuditStatus {
+        Pending = 0,   // 审核中
+        Approved = 1,  // 已通过
+        Rejected = 2,  // 已驳回
+    }
+
+    /// 登记员审核意见
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct AuditVote<T: Config> {
+        pub registrar: T::AccountId,                                  // 登记员
+        pub approve: bool,                                            // 是否通过
+        pub comment: Option<BoundedVec<u8, T::MaxAuditCommentLength>>, // 审核意见
+        pub voted_at: BlockNumberFor<T>,                              // 审核区块
+    }
+
+    /// 机构审核案件
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct AuditCase<T: Config> {
+        pub status: AuditStatus,                               // 案件状态
+        pub opened_at: BlockNumberFor<T>,                      // 立案区块
+        pub closed_at: Option<BlockNumberFor<T>>,              // 结案区块
+        pub votes: BoundedVec<AuditVote<T>, T::MaxRegistrars>, // 审核意见列表
+    }
+
+    /// 待接受的所有权转让
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct PendingOwnerTransfer<T: Config> {
+        pub new_owner: T::AccountId,       // 新所有者
+        pub expires_at: BlockNumberFor<T>, // 过期区块
+    }
+
+    /// 营业执照记录
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct LicenseRecord<T: Config> {
+        pub content_hash: H256,                                                 // 执照文件内容哈希
+        pub authority_code: BoundedVec<u8, T::MaxAuthorityCodeLength>,          // 发证机关代码
+        pub registration_number: BoundedVec<u8, T::MaxRegistrationNumberLength>, // 注册号
+        pub expires_at: BlockNumberFor<T>,                                      // 到期区块
+    }
+
+    /// 机构信息字段变更（保存修改前的值）
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub enum InstitutionFieldChange<T: Config> {
+        InstitutionName(BoundedVec<u8, T::MaxNameLength>),                // 机构名称
+        InstitutionFullName(BoundedVec<u8, T::MaxNameLength>),            // 机构全名
+        LicenseImageUrl(BoundedVec<u8, T::MaxNameLength>),                // 营业执照图片URL
+        ResponsiblePerson(BoundedVec<u8, T::MaxResponsiblePersonLength>), // 负责人
+        BusinessScope(BoundedVec<u8, T::MaxBusinessScopeLength>),         // 经营范围
+        ProfitContract(Option<ProfitContract<T>>),                        // 分润合约
+        Status(InstitutionStatus<T>),                                     // 机构状态
+        Creator(T::AccountId),                                            // 所有者
+        Parent(Option<ParentLink<T>>),                                    // 上级机构
+    }
+
+    /// 机构信息修订记录
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct InstitutionRevision<T: Config> {
+        pub revision: u32,                                             // 修订号（本次修改后的版本）
+        pub previous_hash: T::Hash,                                    // 修改前完整记录的哈希
+        pub changes: BoundedVec<InstitutionFieldChange<T>, ConstU32<6>>, // 变更字段及其原值
+        pub block: BlockNumberFor<T>,                                  // 修改区块
+        pub signer: Option<T::AccountId>,                              // 修改人（治理来源或自动变更时为空）
+    }
+
+    /// 暂停营业申诉
+    #[derive(CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    #[codec(mel_bound())]
+    pub struct SuspensionAppeal<T: Config> {
+        pub appellant: T::AccountId,         // 申诉人
+        pub evidence_hash: H256,             // 证据哈希
+        pub submitted_at: BlockNumberFor<T>, // 提交区块
+    }
+
+    /// 第三方核验方公钥
+    #[derive(Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum AttestorKey {
+        Sr25519(sr25519::Public), // sr25519 公钥
+        Ed25519(ed25519::Public), // ed25519 公钥
+    }
+
+    /// 核验方签名，类型须与核验方公钥一致
+    #[derive(Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum AttestorSignature {
+        Sr25519(sr25519::Signature), // sr25519 签名
+        Ed25519(ed25519::Signature), // ed25519 签名
+    }
+
+    impl AttestorSignature {
+        /// 使用核验方公钥验证消息签名
+        pub fn verify(&self, message: &[u8], attestor: &AttestorKey) -> bool {
+            match (self, attestor) {
+                (Self::Sr25519(signature), AttestorKey::Sr25519(public)) => signature.verify(message, public),
+                (Self::Ed25519(signature), AttestorKey::Ed25519(public)) => signature.verify(message, public),
+                _ => false,
+            }
+        }
+    }
+
+    /// 核验方签署的机构声明（以 SCALE 编码提交）
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct AttestationClaim<T: Config> {
+        pub institution_id: BoundedVec<u8, T::MaxInstitutionIdLength>, // 机构ID
+        pub claim_type: u32,                                           // 声明类型（由核验方定义）
+        pub expires_at: BlockNumberFor<T>,                             // 失效区块
+        pub evidence_hash: H256,                                       // 证据哈希
+    }
+
+    /// 已验证的核验声明
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Attestation<T: Config> {
+        pub attestor: AttestorKey,           // 核验方公钥
+        pub evidence_hash: H256,             // 证据哈希
+        pub expires_at: BlockNumberFor<T>,   // 失效区块
+        pub submitted_at: BlockNumberFor<T>, // 提交区块
+        pub submitter: T::AccountId,         // 提交人
+    }
+
+    /// 机构目录查询条件
+    #[derive(Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    pub enum InstitutionFilter<AccountId> {
+        All,                // 全部机构
+        Status(u8),         // 按状态编码（见 `InstitutionStatus::code`）
+        Creator(AccountId), // 按所有者
+        NamePrefix(Vec<u8>), // 按机构名称前缀
+    }
+
+    /// 机构目录分页结果
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    pub struct InstitutionPage<Info> {
+        pub items: Vec<(Vec<u8>, Info)>,  // 机构ID及机构信息
+        pub next_cursor: Option<Vec<u8>>, // 下一页游标，None 表示已到末尾
     }
 
     /// 机构存储映射
@@ -75,135 +575,636 @@ pub mod pallet {
         OptionQuery,                               // 查询策略：如果键不存在，返回 None
     >;
 
-    #[pallet::event]
-    #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    pub enum Event<T: Config> {
-        /// 机构已创建 [机构ID, 创建者]
-        InstitutionCreated(BoundedVec<u8, T::MaxInstitutionIdLength>, T::AccountId),
-        /// 机构状态已更新 [机构ID, 新状态(u8)]
-        InstitutionStatusUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>, u8),
-        /// 机构信息已更新 [机构ID]
-        InstitutionInfoUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>),
-        /// 机构已删除 [机构ID]
-        InstitutionDeleted(BoundedVec<u8, T::MaxInstitutionIdLength>),
-    }
+    /// 机构认证记录（保留最近一次认证及其撤销信息）
+    #[pallet::storage]
+    #[pallet::storage_prefix = "Certifications"]
+    pub type Certifications<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        CertificationRecord<T>,                    // 值：认证记录
+        OptionQuery,
+    >;
 
-    #[pallet::error]
-    pub enum Error<T> {
-        /// 机构ID已存在
-        InstitutionIdAlreadyExists,
-        /// 机构不存在
-        InstitutionNotFound,
-        /// 无权操作此机构
-        NotAuthorized,
-        /// 字符串转换错误
-        StringConversionError,
-        /// 无效状态
-        InvalidStatus,
-    }
+    /// 机构成员角色映射
+    #[pallet::storage]
+    #[pallet::storage_prefix = "InstitutionMembers"]
+    pub type InstitutionMembers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        Blake2_128Concat,
+        T::AccountId,                              // 次键：成员账户
+        BoundedVec<InstitutionRole, ConstU32<5>>,  // 值：成员角色列表
+        ValueQuery,
+    >;
 
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// 创建新的机构
-        #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
-        pub fn create_institution(
-            origin: OriginFor<T>,
-            institution_id: Vec<u8>,
-            institution_name: Vec<u8>,
-            institution_full_name: Vec<u8>,
-            license_image_url: Vec<u8>,
-            responsible_person: Vec<u8>,
-            business_scope: Vec<u8>,
-            profit_contract: Option<Vec<u8>>,
-        ) -> DispatchResult {
-            // 确认调用者身份
-            let who = ensure_signed(origin)?;
-            
-            // 转换为边界向量
-            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
-                .map_err(|_| Error::<T>::StringConversionError)?;
-            
-            // 检查机构ID是否已存在
-            ensure!(!Institutions::<T>::contains_key(&bounded_id), Error::<T>::InstitutionIdAlreadyExists);
-            
-            // 转换其他字段为边界向量
-            let bounded_name = BoundedVec::<u8, T::MaxNameLength>::try_from(institution_name)
-                .map_err(|_| Error::<T>::StringConversionError)?;
-                
-            let bounded_full_name = BoundedVec::<u8, T::MaxNameLength>::try_from(institution_full_name)
-                .map_err(|_| Error::<T>::StringConversionError)?;
-                
-            let bounded_license_url = BoundedVec::<u8, T::MaxNameLength>::try_from(license_image_url)
-                .map_err(|_| Error::<T>::StringConversionError)?;
-                
-            let bounded_responsible = BoundedVec::<u8, T::MaxResponsiblePersonLength>::try_from(responsible_person)
-                .map_err(|_| Error::<T>::StringConversionError)?;
-                
-            let bounded_scope = BoundedVec::<u8, T::MaxBusinessScopeLength>::try_from(business_scope)
-                .map_err(|_| Error::<T>::StringConversionError)?;
-                
-            // 处理可选的分润合约
-            let bounded_contract = match profit_contract {
-                Some(contract) => Some(BoundedVec::<u8, T::MaxContractLength>::try_from(contract)
-                    .map_err(|_| Error::<T>::StringConversionError)?),
-                None => None,
-            };
-            
-            // 创建机构信息
-            let institution = InstitutionInfo {
-                institution_name: bounded_name,
-                status: InstitutionStatus::NotCertified, // 默认为未认证
-                institution_full_name: bounded_full_name,
-                license_image_url: bounded_license_url,
-                responsible_person: bounded_responsible,
-                business_scope: bounded_scope,
-                profit_contract: bounded_contract,
-                created_date: frame_system::Pallet::<T>::block_number(),
-                creator: who.clone(),
-            };
-            
-            // 存储机构信息
-            Institutions::<T>::insert(&bounded_id, institution);
-            
-            // 发出事件
-            Self::deposit_event(Event::InstitutionCreated(bounded_id, who));
-            
-            Ok(())
-        }
-        
-        /// 更新机构状态
-        #[pallet::call_index(1)]
-        #[pallet::weight(5_000)]
-        pub fn update_institution_status(
-            origin: OriginFor<T>,
-            institution_id: Vec<u8>,
-            status: u8,
-        ) -> DispatchResult {
-            // 确认调用者身份
-            let who = ensure_signed(origin)?;
-            
-            // 转换为边界向量
-            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
-                .map_err(|_| Error::<T>::StringConversionError)?;
-            
-            // 获取并更新机构状态
-            Institutions::<T>::try_mutate(&bounded_id, |maybe_institution| -> DispatchResult {
-                let institution = maybe_institution.as_mut().ok_or(Error::<T>::InstitutionNotFound)?;
-                
-                // 检查权限（仅创建者可以更新）
-                ensure!(institution.creator == who, Error::<T>::NotAuthorized);
-                
-                // 将 u8 转换为 InstitutionStatus
-                let new_status = match status {
-                    0 => InstitutionStatus::Certified,
-                    1 => InstitutionStatus::NotCertified,
-                    2 => InstitutionStatus::Deactivated,
-                    _ => return Err(Error::<T>::InvalidStatus.into()),
-                };
-                
+    /// 按状态编码索引的机构
+    #[pallet::storage]
+    #[pallet::storage_prefix = "InstitutionsByStatus"]
+    pub type InstitutionsByStatus<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u8,                                        // 主键：状态编码
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 次键：机构 ID
+        (),
+        OptionQuery,
+    >;
+
+    /// 按所有者索引的机构
+    #[pallet::storage]
+    #[pallet::storage_prefix = "InstitutionsByCreator"]
+    pub type InstitutionsByCreator<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,                              // 主键：所有者账户
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 次键：机构 ID
+        (),
+        OptionQuery,
+    >;
+
+    /// 已删除机构的墓碑记录，防止机构ID被他人重新注册
+    #[pallet::storage]
+    #[pallet::storage_prefix = "InstitutionTombstones"]
+    pub type InstitutionTombstones<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        (T::AccountId, BlockNumberFor<T>),         // 值：最后所有者及删除区块
+        OptionQuery,
+    >;
+
+    /// 已删除但依赖数据尚未清理完毕的机构
+    #[pallet::storage]
+    #[pallet::storage_prefix = "PendingCleanups"]
+    pub type PendingCleanups<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        (),
+        OptionQuery,
+    >;
+
+    /// 机构存储押金 [押金账户, 押金金额]
+    #[pallet::storage]
+    #[pallet::storage_prefix = "InstitutionDeposits"]
+    pub type InstitutionDeposits<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        (T::AccountId, BalanceOf<T>),              // 值：押金账户及金额
+        OptionQuery,
+    >;
+
+    /// 待接受的机构所有权转让
+    #[pallet::storage]
+    #[pallet::storage_prefix = "PendingOwnerTransfers"]
+    pub type PendingOwnerTransfers<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        PendingOwnerTransfer<T>,                   // 值：转让提议
+        OptionQuery,
+    >;
+
+    /// 登记员集合
+    #[pallet::storage]
+    #[pallet::storage_prefix = "Registrars"]
+    pub type Registrars<T: Config> = StorageValue<
+        _,
+        BoundedVec<T::AccountId, T::MaxRegistrars>, // 登记员账户列表
+        ValueQuery,
+    >;
+
+    /// 进行中的审核案件
+    #[pallet::storage]
+    #[pallet::storage_prefix = "AuditCases"]
+    pub type AuditCases<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        AuditCase<T>,                              // 值：审核案件
+        OptionQuery,
+    >;
+
+    /// 已结案的审核案件历史（超出上限时丢弃最早的记录）
+    #[pallet::storage]
+    #[pallet::storage_prefix = "AuditHistory"]
+    pub type AuditHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>,        // 主键：机构 ID
+        BoundedVec<AuditCase<T>, T::MaxAuditHistory>,     // 值：历史案件列表
+        ValueQuery,
+    >;
+
+    /// 机构的直属分支索引
+    #[pallet::storage]
+    #[pallet::storage_prefix = "InstitutionBranches"]
+    pub type InstitutionBranches<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>,                                 // 主键：上级机构 ID
+        BoundedVec<BoundedVec<u8, T::MaxInstitutionIdLength>, T::MaxBranches>,     // 值：直属分支ID列表
+        ValueQuery,
+    >;
+
+    /// 机构营业执照记录
+    #[pallet::storage]
+    #[pallet::storage_prefix = "Licenses"]
+    pub type Licenses<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        LicenseRecord<T>,                          // 值：营业执照记录
+        OptionQuery,
+    >;
+
+    /// 按区块索引的营业执照到期队列
+    #[pallet::storage]
+    #[pallet::storage_prefix = "LicenseExpiries"]
+    pub type LicenseExpiries<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,                                                                 // 主键：到期区块
+        BoundedVec<BoundedVec<u8, T::MaxInstitutionIdLength>, T::MaxLicenseExpiriesPerBlock>, // 值：机构ID列表
+        ValueQuery,
+    >;
+
+    /// 按区块索引的营业执照到期预警队列
+    #[pallet::storage]
+    #[pallet::storage_prefix = "LicenseExpiryWarnings"]
+    pub type LicenseExpiryWarnings<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,                                                                 // 主键：预警区块
+        BoundedVec<BoundedVec<u8, T::MaxInstitutionIdLength>, T::MaxLicenseExpiriesPerBlock>, // 值：机构ID列表
+        ValueQuery,
+    >;
+
+    /// 机构当前的修订号（创建时为 0，每次信息变更加 1）
+    #[pallet::storage]
+    #[pallet::storage_prefix = "InstitutionRevisionCount"]
+    pub type InstitutionRevisionCount<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        u32,                                       // 值：当前修订号
+        ValueQuery,
+    >;
+
+    /// 机构信息修订日志（超出上限时丢弃最早的记录）
+    #[pallet::storage]
+    #[pallet::storage_prefix = "InstitutionRevisions"]
+    pub type InstitutionRevisions<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>,          // 主键：机构 ID
+        BoundedVec<InstitutionRevision<T>, T::MaxRevisions>, // 值：修订记录列表
+        ValueQuery,
+    >;
+
+    /// 按区块索引的暂停自动解除队列
+    #[pallet::storage]
+    #[pallet::storage_prefix = "SuspensionExpiries"]
+    pub type SuspensionExpiries<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,                                                              // 主键：解除区块
+        BoundedVec<BoundedVec<u8, T::MaxInstitutionIdLength>, T::MaxSuspensionsPerBlock>, // 值：机构ID列表
+        ValueQuery,
+    >;
+
+    /// 暂停营业申诉
+    #[pallet::storage]
+    #[pallet::storage_prefix = "SuspensionAppeals"]
+    pub type SuspensionAppeals<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        SuspensionAppeal<T>,                       // 值：申诉记录
+        OptionQuery,
+    >;
+
+    /// 受信任的核验方公钥列表
+    #[pallet::storage]
+    #[pallet::storage_prefix = "Attestors"]
+    pub type Attestors<T: Config> = StorageValue<
+        _,
+        BoundedVec<AttestorKey, T::MaxAttestors>, // 核验方公钥列表
+        ValueQuery,
+    >;
+
+    /// 机构的核验声明，同一类型仅保留最新一条
+    #[pallet::storage]
+    #[pallet::storage_prefix = "Attestations"]
+    pub type Attestations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionIdLength>, // 主键：机构 ID
+        Twox64Concat,
+        u32,                                       // 次键：声明类型
+        Attestation<T>,                            // 值：核验声明
+        OptionQuery,
+    >;
+
+    /// 创世机构 [机构ID, 创建者, 名称, 全名, 营业执照URL, 负责人, 经营范围, 是否已认证]
+    pub type GenesisInstitution<AccountId> = (Vec<u8>, AccountId, Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>, bool);
+
+    /// 创世配置
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// 初始登记员
+        pub registrars: Vec<T::AccountId>,
+        /// 初始机构
+        pub institutions: Vec<GenesisInstitution<T::AccountId>>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        /// 写入初始登记员及机构；机构押金同样从创建者余额中冻结，未认证机构立案审核
+        fn build(&self) {
+            let registrars = BoundedVec::<T::AccountId, T::MaxRegistrars>::try_from(self.registrars.clone())
+                .expect("genesis registrars exceed MaxRegistrars");
+            Registrars::<T>::put(registrars);
+
+            for (id, creator, name, full_name, license_url, responsible, scope, certified) in &self.institutions {
+                let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(id.clone())
+                    .expect("genesis institution id too long");
+                assert!(!Institutions::<T>::contains_key(&bounded_id), "duplicate genesis institution id");
+
+                let institution = InstitutionInfo::<T> {
+                    institution_name: name.clone().try_into().expect("genesis institution name too long"),
+                    status: if *certified { InstitutionStatus::Certified } else { InstitutionStatus::NotCertified },
+                    institution_full_name: full_name.clone().try_into().expect("genesis institution full name too long"),
+                    license_image_url: license_url.clone().try_into().expect("genesis license image url too long"),
+                    responsible_person: responsible.clone().try_into().expect("genesis responsible person too long"),
+                    business_scope: scope.clone().try_into().expect("genesis business scope too long"),
+                    profit_contract: None,
+                    created_date: frame_system::Pallet::<T>::block_number(),
+                    creator: creator.clone(),
+                    parent: None,
+                };
+
+                Pallet::<T>::update_deposit(&bounded_id, creator, institution.encoded_size())
+                    .expect("genesis institution creator cannot cover the deposit");
+                Pallet::<T>::index_institution(&bounded_id, &institution);
+                Institutions::<T>::insert(&bounded_id, institution);
+
+                if *certified {
+                    Pallet::<T>::record_certification(&bounded_id, None);
+                } else {
+                    Pallet::<T>::open_audit_case(&bounded_id);
+                }
+            }
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 处理本区块的营业执照到期预警、到期失效及暂停自动解除
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let warnings = LicenseExpiryWarnings::<T>::take(now);
+            let expiries = LicenseExpiries::<T>::take(now);
+            let suspensions = SuspensionExpiries::<T>::take(now);
+            let processed = (warnings.len() + expiries.len() + suspensions.len()) as u64;
+            
+            for institution_id in warnings {
+                if let Some(license) = Licenses::<T>::get(&institution_id) {
+                    Self::deposit_event(Event::LicenseExpiring(institution_id, license.expires_at));
+                }
+            }
+            
+            for institution_id in expiries {
+                let expired = Licenses::<T>::get(&institution_id)
+                    .is_some_and(|license| license.expires_at == now);
+                if expired {
+                    Self::lapse_certification(&institution_id, now);
+                }
+            }
+            
+            for institution_id in suspensions {
+                let due = Institutions::<T>::get(&institution_id).is_some_and(|institution| {
+                    matches!(institution.status, InstitutionStatus::Suspended { until, .. } if until == now)
+                });
+                if due {
+                    Self::end_suspension(&institution_id);
+                }
+            }
+            
+            T::DbWeight::get().reads_writes(3 + processed * 3, 3 + processed * 3)
+        }
+        
+        /// 利用区块剩余权重分批清理已删除机构的依赖数据
+        fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let batch = T::WeightInfo::clean_up_institution(T::CleanupBatchSize::get())
+                .saturating_add(T::DbWeight::get().reads(1));
+            let mut used = Weight::zero();
+            
+            while used.saturating_add(batch).all_lte(remaining_weight) {
+                let Some(institution_id) = PendingCleanups::<T>::iter_keys().next() else {
+                    return used.saturating_add(T::DbWeight::get().reads(1));
+                };
+                let removed = Self::clean_up(&institution_id, T::CleanupBatchSize::get());
+                used = used
+                    .saturating_add(T::WeightInfo::clean_up_institution(removed))
+                    .saturating_add(T::DbWeight::get().reads(1));
+            }
+            
+            used
+        }
+        
+        /// 通过及驳回阈值须能由登记员达到，否则审核案件永远无法结案
+        fn integrity_test() {
+            let max_registrars = T::MaxRegistrars::get();
+            assert!(
+                (1..=max_registrars).contains(&T::ApprovalThreshold::get()),
+                "ApprovalThreshold must be between 1 and MaxRegistrars"
+            );
+            assert!(
+                (1..=max_registrars).contains(&T::RejectionThreshold::get()),
+                "RejectionThreshold must be between 1 and MaxRegistrars"
+            );
+            // 目录分页每页至少检查一个机构，否则游标无法前进
+            assert!(T::MaxDirectoryPageSize::get() > 0, "MaxDirectoryPageSize must be positive");
+            assert!(T::MaxDirectoryScan::get() > 0, "MaxDirectoryScan must be positive");
+            // 清理批次为 0 时已删除机构的依赖数据永远无法清理完毕
+            assert!(T::CleanupBatchSize::get() > 0, "CleanupBatchSize must be positive");
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// 机构已创建 [机构ID, 创建者]
+        InstitutionCreated(BoundedVec<u8, T::MaxInstitutionIdLength>, T::AccountId),
+        /// 机构状态已更新 [机构ID, 新状态(u8)]
+        InstitutionStatusUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>, u8),
+        /// 机构信息已更新 [机构ID]
+        InstitutionInfoUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>),
+        /// 机构已删除 [机构ID]
+        InstitutionDeleted(BoundedVec<u8, T::MaxInstitutionIdLength>),
+        /// 机构已认证 [机构ID, 认证人]
+        InstitutionCertified(BoundedVec<u8, T::MaxInstitutionIdLength>, Option<T::AccountId>),
+        /// 机构认证已撤销 [机构ID, 撤销人]
+        CertificationRevoked(BoundedVec<u8, T::MaxInstitutionIdLength>, Option<T::AccountId>),
+        /// 登记员已添加 [登记员]
+        RegistrarAdded(T::AccountId),
+        /// 登记员已移除 [登记员]
+        RegistrarRemoved(T::AccountId),
+        /// 审核案件已立案 [机构ID]
+        AuditCaseOpened(BoundedVec<u8, T::MaxInstitutionIdLength>),
+        /// 登记员已通过审核 [机构ID, 登记员]
+        AuditApproved(BoundedVec<u8, T::MaxInstitutionIdLength>, T::AccountId),
+        /// 登记员已驳回审核 [机构ID, 登记员]
+        AuditRejected(BoundedVec<u8, T::MaxInstitutionIdLength>, T::AccountId),
+        /// 成员角色已授予 [机构ID, 成员, 角色]
+        RoleGranted(BoundedVec<u8, T::MaxInstitutionIdLength>, T::AccountId, InstitutionRole),
+        /// 成员角色已撤销 [机构ID, 成员, 角色]
+        RoleRevoked(BoundedVec<u8, T::MaxInstitutionIdLength>, T::AccountId, InstitutionRole),
+        /// 所有权转让已提议 [机构ID, 新所有者, 过期区块]
+        OwnerTransferProposed(BoundedVec<u8, T::MaxInstitutionIdLength>, T::AccountId, BlockNumberFor<T>),
+        /// 所有权转让已完成 [机构ID, 原所有者, 新所有者]
+        OwnerTransferred(BoundedVec<u8, T::MaxInstitutionIdLength>, T::AccountId, T::AccountId),
+        /// 所有权转让已取消 [机构ID]
+        OwnerTransferCancelled(BoundedVec<u8, T::MaxInstitutionIdLength>),
+        /// 分润已分配 [机构ID, 分配金额]
+        ProfitDistributed(BoundedVec<u8, T::MaxInstitutionIdLength>, BalanceOf<T>),
+        /// 营业执照已登记 [机构ID, 内容哈希, 到期区块]
+        LicenseRecorded(BoundedVec<u8, T::MaxInstitutionIdLength>, H256, BlockNumberFor<T>),
+        /// 营业执照即将到期 [机构ID, 到期区块]
+        LicenseExpiring(BoundedVec<u8, T::MaxInstitutionIdLength>, BlockNumberFor<T>),
+        /// 营业执照已到期 [机构ID]
+        LicenseExpired(BoundedVec<u8, T::MaxInstitutionIdLength>),
+        /// 上级机构已变更 [机构ID, 上级机构ID]
+        ParentUpdated(BoundedVec<u8, T::MaxInstitutionIdLength>, Option<BoundedVec<u8, T::MaxInstitutionIdLength>>),
+        /// 机构已暂停营业 [机构ID, 自动解除区块]
+        InstitutionSuspended(BoundedVec<u8, T::MaxInstitutionIdLength>, BlockNumberFor<T>),
+        /// 机构暂停已解除 [机构ID]
+        SuspensionLifted(BoundedVec<u8, T::MaxInstitutionIdLength>),
+        /// 商户已提交暂停申诉 [机构ID, 申诉人, 证据哈希]
+        SuspensionAppealed(BoundedVec<u8, T::MaxInstitutionIdLength>, T::AccountId, H256),
+        /// 机构信息已产生新修订 [机构ID, 修订号]
+        InstitutionRevised(BoundedVec<u8, T::MaxInstitutionIdLength>, u32),
+        /// 核验方已添加 [核验方公钥]
+        AttestorAdded(AttestorKey),
+        /// 核验方已移除 [核验方公钥]
+        AttestorRemoved(AttestorKey),
+        /// 核验声明已记录 [机构ID, 声明类型, 核验方公钥, 失效区块]
+        AttestationRecorded(BoundedVec<u8, T::MaxInstitutionIdLength>, u32, AttestorKey, BlockNumberFor<T>),
+        /// 已删除机构的依赖数据已清理完毕 [机构ID]
+        InstitutionCleanedUp(BoundedVec<u8, T::MaxInstitutionIdLength>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// 机构ID已存在
+        InstitutionIdAlreadyExists,
+        /// 机构不存在
+        InstitutionNotFound,
+        /// 无权操作此机构
+        NotAuthorized,
+        /// 字符串转换错误
+        StringConversionError,
+        /// 无效状态
+        InvalidStatus,
+        /// 机构已认证
+        AlreadyCertified,
+        /// 机构未认证
+        NotCertified,
+        /// 机构已注销
+        InstitutionDeactivated,
+        /// 认证状态只能由认证来源变更
+        CertificationRequiresGovernance,
+        /// 账户已是登记员
+        AlreadyRegistrar,
+        /// 调用者不是登记员
+        NotRegistrar,
+        /// 登记员数量超过限制
+        TooManyRegistrars,
+        /// 审核案件不存在
+        AuditCaseNotFound,
+        /// 已存在进行中的审核案件
+        AuditCaseAlreadyOpen,
+        /// 登记员已对该案件发表意见
+        AlreadyVoted,
+        /// 所有者角色不可授予或撤销
+        CannotAssignOwner,
+        /// 成员已拥有该角色
+        RoleAlreadyGranted,
+        /// 成员未拥有该角色
+        RoleNotGranted,
+        /// 没有待接受的所有权转让
+        NoPendingOwnerTransfer,
+        /// 所有权转让提议已过期
+        OwnerTransferExpired,
+        /// 机构ID已被删除的机构使用，不可重新注册
+        InstitutionIdRetired,
+        /// 不能将所有权转让给当前所有者
+        AlreadyOwner,
+        /// 分润比例之和必须为100%且受益人不能为空
+        InvalidProfitShares,
+        /// 分润合约的失效区块必须晚于生效区块
+        InvalidEffectivePeriod,
+        /// 分配金额超出余额类型范围
+        AmountOverflow,
+        /// 营业执照到期区块必须晚于当前区块
+        LicenseExpiryInPast,
+        /// 同一区块到期的营业执照数量超过限制
+        TooManyLicenseExpiries,
+        /// 上级机构不能是自身或自身的下级机构
+        CyclicHierarchy,
+        /// 超过总部/分支层级的最大深度
+        HierarchyTooDeep,
+        /// 直属分支数量超过限制
+        TooManyBranches,
+        /// 机构没有上级机构
+        NotABranch,
+        /// 机构仍有分支，不能删除
+        InstitutionHasBranches,
+        /// 机构已暂停营业
+        InstitutionSuspended,
+        /// 机构未处于暂停状态
+        NotSuspended,
+        /// 暂停解除区块必须晚于当前区块
+        SuspensionEndInPast,
+        /// 同一区块自动解除的暂停数量超过限制
+        TooManySuspensions,
+        /// 核验方已存在
+        AlreadyAttestor,
+        /// 核验方不存在
+        NotAttestor,
+        /// 核验方数量超过限制
+        TooManyAttestors,
+        /// 核验方不受信任
+        UntrustedAttestor,
+        /// 核验方签名无效
+        InvalidAttestationSignature,
+        /// 核验声明无法解码
+        InvalidAttestationClaim,
+        /// 核验声明已过期
+        AttestationExpired,
+        /// 已有更晚失效的同类型核验声明
+        AttestationOutdated,
+        /// 机构没有待清理的依赖数据
+        NoPendingCleanup,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// 创建新的机构
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::create_institution())]
+        pub fn create_institution(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            institution_name: Vec<u8>,
+            institution_full_name: Vec<u8>,
+            license_image_url: Vec<u8>,
+            responsible_person: Vec<u8>,
+            business_scope: Vec<u8>,
+            profit_contract: Option<RevenueSplit<T>>,
+        ) -> DispatchResult {
+            // 确认调用者身份
+            let who = ensure_signed(origin)?;
+            
+            // 转换为边界向量
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            // 检查机构ID是否已存在
+            ensure!(!Institutions::<T>::contains_key(&bounded_id), Error::<T>::InstitutionIdAlreadyExists);
+            ensure!(!InstitutionTombstones::<T>::contains_key(&bounded_id), Error::<T>::InstitutionIdRetired);
+            
+            // 转换其他字段为边界向量
+            let bounded_name = BoundedVec::<u8, T::MaxNameLength>::try_from(institution_name)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+                
+            let bounded_full_name = BoundedVec::<u8, T::MaxNameLength>::try_from(institution_full_name)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+                
+            let bounded_license_url = BoundedVec::<u8, T::MaxNameLength>::try_from(license_image_url)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+                
+            let bounded_responsible = BoundedVec::<u8, T::MaxResponsiblePersonLength>::try_from(responsible_person)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+                
+            let bounded_scope = BoundedVec::<u8, T::MaxBusinessScopeLength>::try_from(business_scope)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+                
+            // 校验可选的分润合约
+            if let Some(split) = &profit_contract {
+                Self::ensure_valid_split(split)?;
+            }
+            
+            // 创建机构信息
+            let institution = InstitutionInfo::<T> {
+                institution_name: bounded_name,
+                status: InstitutionStatus::NotCertified, // 默认为未认证
+                institution_full_name: bounded_full_name,
+                license_image_url: bounded_license_url,
+                responsible_person: bounded_responsible,
+                business_scope: bounded_scope,
+                profit_contract: profit_contract.map(ProfitContract::Split),
+                created_date: frame_system::Pallet::<T>::block_number(),
+                creator: who.clone(),
+                parent: None,
+            };
+            
+            // 按记录大小冻结存储押金
+            Self::update_deposit(&bounded_id, &who, institution.encoded_size())?;
+            
+            // 存储机构信息并写入目录索引
+            Self::index_institution(&bounded_id, &institution);
+            Institutions::<T>::insert(&bounded_id, institution);
+            
+            // 发出事件
+            Self::deposit_event(Event::InstitutionCreated(bounded_id.clone(), who));
+            
+            // 为新机构立案审核
+            Self::open_audit_case(&bounded_id);
+            
+            Ok(())
+        }
+        
+        /// 更新机构状态（创建者仅可注销自己的机构，认证状态由认证来源管理）
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::update_institution_status())]
+        pub fn update_institution_status(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            status: u8,
+        ) -> DispatchResult {
+            // 确认调用者身份
+            let who = ensure_signed(origin)?;
+            
+            // 转换为边界向量
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            // 获取并更新机构状态
+            Institutions::<T>::try_mutate(&bounded_id, |maybe_institution| -> DispatchResult {
+                let institution = maybe_institution.as_mut().ok_or(Error::<T>::InstitutionNotFound)?;
+                
+                // 检查权限（仅创建者可以更新）
+                ensure!(institution.creator == who, Error::<T>::NotAuthorized);
+                ensure!(
+                    !matches!(institution.status, InstitutionStatus::Suspended { .. }),
+                    Error::<T>::InstitutionSuspended
+                );
+                
+                // 将 u8 转换为 InstitutionStatus，创建者只能注销
+                let new_status = match status {
+                    0 | 1 => return Err(Error::<T>::CertificationRequiresGovernance.into()),
+                    2 => InstitutionStatus::Deactivated,
+                    _ => return Err(Error::<T>::InvalidStatus.into()),
+                };
+                
                 // 更新状态
-                institution.status = new_status;
+                Self::set_status(&bounded_id, institution, new_status, Some(&who));
                 
                 // 发出事件
                 Self::deposit_event(Event::InstitutionStatusUpdated(bounded_id.clone(), status));
@@ -214,7 +1215,7 @@ pub mod pallet {
         
         /// 更新机构信息
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::update_institution_info())]
         pub fn update_institution_info(
             origin: OriginFor<T>,
             institution_id: Vec<u8>,
@@ -223,7 +1224,7 @@ pub mod pallet {
             license_image_url: Option<Vec<u8>>,
             responsible_person: Option<Vec<u8>>,
             business_scope: Option<Vec<u8>>,
-            profit_contract: Option<Vec<u8>>,
+            profit_contract: Option<RevenueSplit<T>>,
         ) -> DispatchResult {
             // 确认调用者身份
             let who = ensure_signed(origin)?;
@@ -239,41 +1240,65 @@ pub mod pallet {
                 // 检查权限（仅创建者可以更新）
                 ensure!(institution.creator == who, Error::<T>::NotAuthorized);
                 
+                // 记录修改前的哈希及变更字段的原值
+                let previous_hash = T::Hashing::hash_of(&*institution);
+                let mut changes = Vec::new();
+                
                 // 更新各字段（如果提供）
                 if let Some(name) = institution_name {
                     let bounded_name = BoundedVec::<u8, T::MaxNameLength>::try_from(name)
                         .map_err(|_| Error::<T>::StringConversionError)?;
-                    institution.institution_name = bounded_name;
+                    if bounded_name != institution.institution_name {
+                        changes.push(InstitutionFieldChange::InstitutionName(core::mem::replace(&mut institution.institution_name, bounded_name)));
+                    }
                 }
                 
                 if let Some(full_name) = institution_full_name {
                     let bounded_full_name = BoundedVec::<u8, T::MaxNameLength>::try_from(full_name)
                         .map_err(|_| Error::<T>::StringConversionError)?;
-                    institution.institution_full_name = bounded_full_name;
+                    if bounded_full_name != institution.institution_full_name {
+                        changes.push(InstitutionFieldChange::InstitutionFullName(core::mem::replace(&mut institution.institution_full_name, bounded_full_name)));
+                    }
                 }
                 
                 if let Some(license_url) = license_image_url {
                     let bounded_license_url = BoundedVec::<u8, T::MaxNameLength>::try_from(license_url)
                         .map_err(|_| Error::<T>::StringConversionError)?;
-                    institution.license_image_url = bounded_license_url;
+                    if bounded_license_url != institution.license_image_url {
+                        changes.push(InstitutionFieldChange::LicenseImageUrl(core::mem::replace(&mut institution.license_image_url, bounded_license_url)));
+                    }
                 }
                 
                 if let Some(responsible) = responsible_person {
                     let bounded_responsible = BoundedVec::<u8, T::MaxResponsiblePersonLength>::try_from(responsible)
                         .map_err(|_| Error::<T>::StringConversionError)?;
-                    institution.responsible_person = bounded_responsible;
+                    if bounded_responsible != institution.responsible_person {
+                        changes.push(InstitutionFieldChange::ResponsiblePerson(core::mem::replace(&mut institution.responsible_person, bounded_responsible)));
+                    }
                 }
                 
                 if let Some(scope) = business_scope {
                     let bounded_scope = BoundedVec::<u8, T::MaxBusinessScopeLength>::try_from(scope)
                         .map_err(|_| Error::<T>::StringConversionError)?;
-                    institution.business_scope = bounded_scope;
+                    if bounded_scope != institution.business_scope {
+                        changes.push(InstitutionFieldChange::BusinessScope(core::mem::replace(&mut institution.business_scope, bounded_scope)));
+                    }
                 }
                 
-                if let Some(contract) = profit_contract {
-                    let bounded_contract = BoundedVec::<u8, T::MaxContractLength>::try_from(contract)
-                        .map_err(|_| Error::<T>::StringConversionError)?;
-                    institution.profit_contract = Some(bounded_contract);
+                if let Some(split) = profit_contract {
+                    Self::ensure_valid_split(&split)?;
+                    let contract = Some(ProfitContract::Split(split));
+                    if contract != institution.profit_contract {
+                        changes.push(InstitutionFieldChange::ProfitContract(core::mem::replace(&mut institution.profit_contract, contract)));
+                    }
+                }
+                
+                // 按新的记录大小调整押金
+                Self::update_deposit(&bounded_id, &who, institution.encoded_size())?;
+                
+                // 写入修订记录
+                if !changes.is_empty() {
+                    Self::record_revision(&bounded_id, previous_hash, changes, Some(&who));
                 }
                 
                 // 发出事件
@@ -284,12 +1309,19 @@ pub mod pallet {
         }
         
         /// 删除机构
+        ///
+        /// 机构记录立即删除，成员、核验声明及依赖模块的数据先清理一批，
+        /// 其余由 `on_idle` 或 `clean_up_institution` 分批清理。
         #[pallet::call_index(3)]
-        #[pallet::weight(5_000)]
+        #[pallet::weight(
+            T::WeightInfo::delete_institution()
+                .saturating_add(T::WeightInfo::clean_up_institution(T::CleanupBatchSize::get()))
+        )]
+        #[allow(clippy::useless_conversion)]
         pub fn delete_institution(
             origin: OriginFor<T>,
             institution_id: Vec<u8>,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             // 确认调用者身份
             let who = ensure_signed(origin)?;
             
@@ -303,13 +1335,1458 @@ pub mod pallet {
             
             ensure!(institution.creator == who, Error::<T>::NotAuthorized);
             
-            // 删除机构
+            // 暂停中或仍有分支的机构不能删除
+            ensure!(
+                !matches!(institution.status, InstitutionStatus::Suspended { .. }),
+                Error::<T>::InstitutionSuspended
+            );
+            ensure!(InstitutionBranches::<T>::get(&bounded_id).is_empty(), Error::<T>::InstitutionHasBranches);
+            
+            // 依赖模块检查（存在未结束的订单等情况时拒绝删除）
+            T::OnInstitutionRemoved::can_remove(&bounded_id)?;
+            
+            // 退还存储押金
+            Self::release_deposit(&bounded_id)?;
+            
+            // 删除机构、目录索引、认证记录及进行中的审核案件（历史案件保留）
             Institutions::<T>::remove(&bounded_id);
+            Self::unindex_institution(&bounded_id, &institution);
+            Certifications::<T>::remove(&bounded_id);
+            AuditCases::<T>::remove(&bounded_id);
+            PendingOwnerTransfers::<T>::remove(&bounded_id);
+            if let Some(license) = Licenses::<T>::take(&bounded_id) {
+                Self::unschedule_license(&bounded_id, license.expires_at);
+            }
+            if let Some(link) = &institution.parent {
+                Self::remove_branch(&link.parent_id, &bounded_id);
+            }
+            
+            // 写入墓碑记录，机构ID不可再被注册
+            InstitutionTombstones::<T>::insert(&bounded_id, (who, frame_system::Pallet::<T>::block_number()));
             
             // 发出事件
-            Self::deposit_event(Event::InstitutionDeleted(bounded_id));
+            Self::deposit_event(Event::InstitutionDeleted(bounded_id.clone()));
             
-            Ok(())
+            // 级联清理成员、核验声明及依赖模块中的数据，超出一批的部分延后清理
+            PendingCleanups::<T>::insert(&bounded_id, ());
+            let removed = Self::clean_up(&bounded_id, T::CleanupBatchSize::get());
+            
+            Ok(Some(T::WeightInfo::delete_institution().saturating_add(T::WeightInfo::clean_up_institution(removed))).into())
+        }
+        
+        /// 认证机构（仅认证来源）
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::certify_institution())]
+        pub fn certify_institution(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+        ) -> DispatchResult {
+            // 确认认证来源，签名来源时记录认证人
+            T::CertifyOrigin::ensure_origin(origin.clone())?;
+            let certifier = ensure_signed(origin).ok();
+            
+            // 转换为边界向量
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            Institutions::<T>::try_mutate(&bounded_id, |maybe_institution| -> DispatchResult {
+                let institution = maybe_institution.as_mut().ok_or(Error::<T>::InstitutionNotFound)?;
+                
+                match institution.status {
+                    InstitutionStatus::Certified => return Err(Error::<T>::AlreadyCertified.into()),
+                    InstitutionStatus::Deactivated => return Err(Error::<T>::InstitutionDeactivated.into()),
+                    InstitutionStatus::Suspended { .. } => return Err(Error::<T>::InstitutionSuspended.into()),
+                    InstitutionStatus::NotCertified => {},
+                }
+                
+                Self::set_status(&bounded_id, institution, InstitutionStatus::Certified, certifier.as_ref());
+                Ok(())
+            })?;
+            
+            // 认证来源直接认证时，进行中的审核案件随之结案
+            if AuditCases::<T>::contains_key(&bounded_id) {
+                Self::close_audit_case(&bounded_id, AuditStatus::Approved);
+            }
+            
+            Self::record_certification(&bounded_id, certifier);
+            
+            Ok(())
+        }
+        
+        /// 撤销机构认证（仅认证来源）
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::revoke_certification())]
+        pub fn revoke_certification(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            reason: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            // 确认认证来源，签名来源时记录撤销人
+            T::CertifyOrigin::ensure_origin(origin.clone())?;
+            let revoker = ensure_signed(origin).ok();
+            
+            // 转换为边界向量
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let bounded_reason = match reason {
+                Some(r) => Some(BoundedVec::<u8, T::MaxRevocationReasonLength>::try_from(r)
+                    .map_err(|_| Error::<T>::StringConversionError)?),
+                None => None,
+            };
+            
+            // 暂停中的机构保留暂停状态，仅撤销认证记录（解除暂停后恢复为未认证）
+            let status_changed = Institutions::<T>::try_mutate(&bounded_id, |maybe_institution| -> Result<bool, DispatchError> {
+                let institution = maybe_institution.as_mut().ok_or(Error::<T>::InstitutionNotFound)?;
+                match institution.status {
+                    InstitutionStatus::Certified => {
+                        Self::set_status(&bounded_id, institution, InstitutionStatus::NotCertified, revoker.as_ref());
+                        Ok(true)
+                    },
+                    InstitutionStatus::Suspended { .. } if Self::holds_certification(&bounded_id) => Ok(false),
+                    _ => Err(Error::<T>::NotCertified.into()),
+                }
+            })?;
+            
+            // 在认证记录中补充撤销信息
+            let now = frame_system::Pallet::<T>::block_number();
+            Certifications::<T>::mutate(&bounded_id, |maybe_record| {
+                let record = maybe_record.get_or_insert_with(|| CertificationRecord {
+                    certifier: None,
+                    certified_at: now,
+                    revoked_at: None,
+                    revocation_reason: None,
+                });
+                record.revoked_at = Some(now);
+                record.revocation_reason = bounded_reason;
+            });
+            
+            // 发出事件
+            if status_changed {
+                Self::deposit_event(Event::InstitutionStatusUpdated(bounded_id.clone(), InstitutionStatus::<T>::NotCertified.code()));
+            }
+            Self::deposit_event(Event::CertificationRevoked(bounded_id, revoker));
+            
+            Ok(())
+        }
+        
+        /// 添加登记员（仅特权来源）
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::add_registrar())]
+        pub fn add_registrar(
+            origin: OriginFor<T>,
+            registrar: T::AccountId,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            
+            Registrars::<T>::try_mutate(|registrars| -> DispatchResult {
+                ensure!(!registrars.contains(&registrar), Error::<T>::AlreadyRegistrar);
+                registrars.try_push(registrar.clone())
+                    .map_err(|_| Error::<T>::TooManyRegistrars)?;
+                Ok(())
+            })?;
+            
+            // 发出事件
+            Self::deposit_event(Event::RegistrarAdded(registrar));
+            
+            Ok(())
+        }
+        
+        /// 移除登记员（仅特权来源）
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::remove_registrar())]
+        pub fn remove_registrar(
+            origin: OriginFor<T>,
+            registrar: T::AccountId,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+            
+            Registrars::<T>::try_mutate(|registrars| -> DispatchResult {
+                let index = registrars.iter().position(|r| r == &registrar)
+                    .ok_or(Error::<T>::NotRegistrar)?;
+                registrars.remove(index);
+                Ok(())
+            })?;
+            
+            // 发出事件
+            Self::deposit_event(Event::RegistrarRemoved(registrar));
+            
+            Ok(())
+        }
+        
+        /// 登记员通过机构审核，达到通过阈值后机构自动认证
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::approve_audit())]
+        pub fn approve_audit(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            comment: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let approvals = Self::cast_audit_vote(&bounded_id, &who, true, comment)?;
+            Self::deposit_event(Event::AuditApproved(bounded_id.clone(), who.clone()));
+            
+            // 达到阈值：结案并认证
+            if approvals >= T::ApprovalThreshold::get() {
+                Institutions::<T>::try_mutate(&bounded_id, |maybe_institution| -> DispatchResult {
+                    let institution = maybe_institution.as_mut().ok_or(Error::<T>::InstitutionNotFound)?;
+                    ensure!(
+                        !matches!(institution.status, InstitutionStatus::Suspended { .. }),
+                        Error::<T>::InstitutionSuspended
+                    );
+                    Self::set_status(&bounded_id, institution, InstitutionStatus::Certified, Some(&who));
+                    Ok(())
+                })?;
+                Self::close_audit_case(&bounded_id, AuditStatus::Approved);
+                Self::record_certification(&bounded_id, Some(who));
+            }
+            
+            Ok(())
+        }
+        
+        /// 登记员驳回机构审核，达到驳回阈值后案件结案并保留在历史中
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::reject_audit())]
+        pub fn reject_audit(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            comment: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let rejections = Self::cast_audit_vote(&bounded_id, &who, false, comment)?;
+            Self::deposit_event(Event::AuditRejected(bounded_id.clone(), who));
+            
+            // 达到阈值：结案
+            if rejections >= T::RejectionThreshold::get() {
+                Self::close_audit_case(&bounded_id, AuditStatus::Rejected);
+            }
+            
+            Ok(())
+        }
+        
+        /// 创建者为未认证的机构重新申请审核
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::request_audit())]
+        pub fn request_audit(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let institution = Institutions::<T>::get(&bounded_id)
+                .ok_or(Error::<T>::InstitutionNotFound)?;
+            ensure!(institution.creator == who, Error::<T>::NotAuthorized);
+            
+            match institution.status {
+                InstitutionStatus::Certified => return Err(Error::<T>::AlreadyCertified.into()),
+                InstitutionStatus::Deactivated => return Err(Error::<T>::InstitutionDeactivated.into()),
+                InstitutionStatus::Suspended { .. } => return Err(Error::<T>::InstitutionSuspended.into()),
+                InstitutionStatus::NotCertified => {},
+            }
+            ensure!(!AuditCases::<T>::contains_key(&bounded_id), Error::<T>::AuditCaseAlreadyOpen);
+            
+            Self::open_audit_case(&bounded_id);
+            
+            Ok(())
+        }
+        
+        /// 授予机构成员角色（所有者可授予任意角色，管理员仅可授予非管理员角色）
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::grant_role())]
+        pub fn grant_role(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            member: T::AccountId,
+            role: InstitutionRole,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            Self::ensure_can_assign(&bounded_id, &who, role)?;
+            
+            InstitutionMembers::<T>::try_mutate(&bounded_id, &member, |roles| -> DispatchResult {
+                ensure!(!roles.contains(&role), Error::<T>::RoleAlreadyGranted);
+                roles.try_push(role).map_err(|_| Error::<T>::RoleAlreadyGranted)?;
+                Ok(())
+            })?;
+            
+            // 发出事件
+            Self::deposit_event(Event::RoleGranted(bounded_id, member, role));
+            
+            Ok(())
+        }
+        
+        /// 撤销机构成员角色，成员也可主动放弃自己的角色
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::revoke_role())]
+        pub fn revoke_role(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            member: T::AccountId,
+            role: InstitutionRole,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            if who != member {
+                Self::ensure_can_assign(&bounded_id, &who, role)?;
+            }
+            
+            InstitutionMembers::<T>::try_mutate_exists(&bounded_id, &member, |maybe_roles| -> DispatchResult {
+                let roles = maybe_roles.as_mut().ok_or(Error::<T>::RoleNotGranted)?;
+                let index = roles.iter().position(|r| r == &role).ok_or(Error::<T>::RoleNotGranted)?;
+                roles.remove(index);
+                if roles.is_empty() {
+                    *maybe_roles = None;
+                }
+                Ok(())
+            })?;
+            
+            // 发出事件
+            Self::deposit_event(Event::RoleRevoked(bounded_id, member, role));
+            
+            Ok(())
+        }
+        
+        /// 提议将机构所有权转让给新账户（仅所有者），新账户需在有效期内接受
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::propose_owner_transfer())]
+        pub fn propose_owner_transfer(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            new_owner: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let institution = Institutions::<T>::get(&bounded_id)
+                .ok_or(Error::<T>::InstitutionNotFound)?;
+            ensure!(institution.creator == who, Error::<T>::NotAuthorized);
+            ensure!(new_owner != who, Error::<T>::AlreadyOwner);
+            
+            // 新提议覆盖尚未接受的旧提议
+            let expires_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::OwnerTransferPeriod::get());
+            PendingOwnerTransfers::<T>::insert(&bounded_id, PendingOwnerTransfer {
+                new_owner: new_owner.clone(),
+                expires_at,
+            });
+            
+            // 发出事件
+            Self::deposit_event(Event::OwnerTransferProposed(bounded_id, new_owner, expires_at));
+            
+            Ok(())
+        }
+        
+        /// 接受机构所有权转让（仅被提议的新所有者）
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::accept_owner_transfer())]
+        pub fn accept_owner_transfer(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let pending = PendingOwnerTransfers::<T>::get(&bounded_id)
+                .ok_or(Error::<T>::NoPendingOwnerTransfer)?;
+            ensure!(pending.new_owner == who, Error::<T>::NotAuthorized);
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= pending.expires_at,
+                Error::<T>::OwnerTransferExpired
+            );
+            
+            let old_owner = Institutions::<T>::try_mutate(&bounded_id, |maybe_institution| -> Result<T::AccountId, DispatchError> {
+                let institution = maybe_institution.as_mut().ok_or(Error::<T>::InstitutionNotFound)?;
+                let previous_hash = T::Hashing::hash_of(&*institution);
+                let old_owner = core::mem::replace(&mut institution.creator, who.clone());
+                Self::record_revision(&bounded_id, previous_hash, vec![InstitutionFieldChange::Creator(old_owner.clone())], Some(&who));
+                Ok(old_owner)
+            })?;
+            InstitutionsByCreator::<T>::remove(&old_owner, &bounded_id);
+            InstitutionsByCreator::<T>::insert(&who, &bounded_id, ());
+            PendingOwnerTransfers::<T>::remove(&bounded_id);
+            
+            // 所有者隐含全部角色，移除新所有者原有的成员角色记录
+            InstitutionMembers::<T>::remove(&bounded_id, &who);
+            
+            // 发出事件
+            Self::deposit_event(Event::OwnerTransferred(bounded_id, old_owner, who));
+            
+            Ok(())
+        }
+        
+        /// 取消待接受的所有权转让（所有者或被提议的新所有者）
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::cancel_owner_transfer())]
+        pub fn cancel_owner_transfer(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let pending = PendingOwnerTransfers::<T>::get(&bounded_id)
+                .ok_or(Error::<T>::NoPendingOwnerTransfer)?;
+            let institution = Institutions::<T>::get(&bounded_id)
+                .ok_or(Error::<T>::InstitutionNotFound)?;
+            ensure!(institution.creator == who || pending.new_owner == who, Error::<T>::NotAuthorized);
+            
+            PendingOwnerTransfers::<T>::remove(&bounded_id);
+            
+            // 发出事件
+            Self::deposit_event(Event::OwnerTransferCancelled(bounded_id));
+            
+            Ok(())
+        }
+        
+        /// 登记或更新机构营业执照（仅所有者）
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::set_license())]
+        pub fn set_license(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            content_hash: H256,
+            authority_code: Vec<u8>,
+            registration_number: Vec<u8>,
+            expires_at: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let institution = Institutions::<T>::get(&bounded_id)
+                .ok_or(Error::<T>::InstitutionNotFound)?;
+            ensure!(institution.creator == who, Error::<T>::NotAuthorized);
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(expires_at > now, Error::<T>::LicenseExpiryInPast);
+            
+            let bounded_authority = BoundedVec::<u8, T::MaxAuthorityCodeLength>::try_from(authority_code)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            let bounded_registration = BoundedVec::<u8, T::MaxRegistrationNumberLength>::try_from(registration_number)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            // 移除旧执照的到期调度
+            if let Some(old) = Licenses::<T>::get(&bounded_id) {
+                Self::unschedule_license(&bounded_id, old.expires_at);
+            }
+            
+            // 调度到期失效；预警区块已过时立即发出预警
+            LicenseExpiries::<T>::try_mutate(expires_at, |ids| ids.try_push(bounded_id.clone()))
+                .map_err(|_| Error::<T>::TooManyLicenseExpiries)?;
+            let warn_at = expires_at.saturating_sub(T::LicenseExpiryWarningPeriod::get());
+            if warn_at > now {
+                LicenseExpiryWarnings::<T>::try_mutate(warn_at, |ids| ids.try_push(bounded_id.clone()))
+                    .map_err(|_| Error::<T>::TooManyLicenseExpiries)?;
+            } else {
+                Self::deposit_event(Event::LicenseExpiring(bounded_id.clone(), expires_at));
+            }
+            
+            Licenses::<T>::insert(&bounded_id, LicenseRecord {
+                content_hash,
+                authority_code: bounded_authority,
+                registration_number: bounded_registration,
+                expires_at,
+            });
+            
+            // 发出事件
+            Self::deposit_event(Event::LicenseRecorded(bounded_id, content_hash, expires_at));
+            
+            Ok(())
+        }
+        
+        /// 设置或解除上级机构
+        ///
+        /// 挂靠到总部时，调用者须同时是本机构所有者和总部管理员；
+        /// 解除挂靠可由本机构所有者或原总部管理员发起。
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::set_parent())]
+        pub fn set_parent(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            parent_id: Option<Vec<u8>>,
+            inherit_certification: bool,
+            inherit_profit_contract: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let mut institution = Institutions::<T>::get(&bounded_id)
+                .ok_or(Error::<T>::InstitutionNotFound)?;
+            
+            let new_link = match parent_id {
+                Some(parent_id) => {
+                    let bounded_parent = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(parent_id)
+                        .map_err(|_| Error::<T>::StringConversionError)?;
+                    ensure!(Institutions::<T>::contains_key(&bounded_parent), Error::<T>::InstitutionNotFound);
+                    ensure!(
+                        institution.creator == who && Self::member_has_role(&bounded_parent, &who, InstitutionRole::Admin),
+                        Error::<T>::NotAuthorized
+                    );
+                    
+                    // 检查环路与层级深度
+                    let parent_ancestors = Self::ancestors(&bounded_parent);
+                    ensure!(
+                        bounded_parent != bounded_id && !parent_ancestors.contains(&bounded_id),
+                        Error::<T>::CyclicHierarchy
+                    );
+                    let depth = (parent_ancestors.len() as u32)
+                        .saturating_add(1)
+                        .saturating_add(Self::subtree_height(&bounded_id));
+                    ensure!(depth <= T::MaxHierarchyDepth::get(), Error::<T>::HierarchyTooDeep);
+                    
+                    Some(ParentLink { parent_id: bounded_parent, inherit_certification, inherit_profit_contract })
+                },
+                None => {
+                    let link = institution.parent.as_ref().ok_or(Error::<T>::NotABranch)?;
+                    ensure!(
+                        institution.creator == who || Self::member_has_role(&link.parent_id, &who, InstitutionRole::Admin),
+                        Error::<T>::NotAuthorized
+                    );
+                    None
+                },
+            };
+            
+            // 更新分支索引
+            if let Some(old_link) = &institution.parent {
+                Self::remove_branch(&old_link.parent_id, &bounded_id);
+            }
+            if let Some(link) = &new_link {
+                InstitutionBranches::<T>::try_mutate(&link.parent_id, |branches| branches.try_push(bounded_id.clone()))
+                    .map_err(|_| Error::<T>::TooManyBranches)?;
+            }
+            
+            let parent = new_link.as_ref().map(|link| link.parent_id.clone());
+            let previous_hash = T::Hashing::hash_of(&institution);
+            let old_link = core::mem::replace(&mut institution.parent, new_link);
+            Self::record_revision(&bounded_id, previous_hash, vec![InstitutionFieldChange::Parent(old_link)], Some(&who));
+            
+            // 按新的记录大小调整押金
+            Self::update_deposit(&bounded_id, &institution.creator, institution.encoded_size())?;
+            Institutions::<T>::insert(&bounded_id, institution);
+            
+            // 发出事件
+            Self::deposit_event(Event::ParentUpdated(bounded_id, parent));
+            
+            Ok(())
+        }
+        
+        /// 暂停机构营业至指定区块（仅特权来源，可覆盖进行中的暂停）
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::suspend_institution())]
+        pub fn suspend_institution(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            until: BlockNumberFor<T>,
+            reason: Vec<u8>,
+        ) -> DispatchResult {
+            T::SuspendOrigin::ensure_origin(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            let bounded_reason = BoundedVec::<u8, T::MaxSuspensionReasonLength>::try_from(reason)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(until > now, Error::<T>::SuspensionEndInPast);
+            
+            let code = Institutions::<T>::try_mutate(&bounded_id, |maybe_institution| -> Result<u8, DispatchError> {
+                let institution = maybe_institution.as_mut().ok_or(Error::<T>::InstitutionNotFound)?;
+                
+                match institution.status {
+                    InstitutionStatus::Deactivated => return Err(Error::<T>::InstitutionDeactivated.into()),
+                    InstitutionStatus::Suspended { until: old_until, .. } => {
+                        SuspensionExpiries::<T>::mutate(old_until, |ids| ids.retain(|id| id != &bounded_id));
+                    },
+                    InstitutionStatus::Certified | InstitutionStatus::NotCertified => {},
+                }
+                
+                SuspensionExpiries::<T>::try_mutate(until, |ids| ids.try_push(bounded_id.clone()))
+                    .map_err(|_| Error::<T>::TooManySuspensions)?;
+                Self::set_status(&bounded_id, institution, InstitutionStatus::Suspended { until, reason: bounded_reason }, None);
+                
+                Ok(institution.status.code())
+            })?;
+            
+            // 发出事件
+            Self::deposit_event(Event::InstitutionStatusUpdated(bounded_id.clone(), code));
+            Self::deposit_event(Event::InstitutionSuspended(bounded_id, until));
+            
+            Ok(())
+        }
+        
+        /// 提前解除机构暂停（仅特权来源）
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::lift_suspension())]
+        pub fn lift_suspension(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+        ) -> DispatchResult {
+            T::SuspendOrigin::ensure_origin(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let institution = Institutions::<T>::get(&bounded_id)
+                .ok_or(Error::<T>::InstitutionNotFound)?;
+            let InstitutionStatus::Suspended { until, .. } = institution.status else {
+                return Err(Error::<T>::NotSuspended.into());
+            };
+            
+            SuspensionExpiries::<T>::mutate(until, |ids| ids.retain(|id| id != &bounded_id));
+            Self::end_suspension(&bounded_id);
+            
+            Ok(())
+        }
+        
+        /// 商户对暂停提出申诉并附上证据哈希（所有者或管理员）
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::appeal_suspension())]
+        pub fn appeal_suspension(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            evidence_hash: H256,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let institution = Institutions::<T>::get(&bounded_id)
+                .ok_or(Error::<T>::InstitutionNotFound)?;
+            ensure!(Self::member_has_role(&bounded_id, &who, InstitutionRole::Admin), Error::<T>::NotAuthorized);
+            ensure!(
+                matches!(institution.status, InstitutionStatus::Suspended { .. }),
+                Error::<T>::NotSuspended
+            );
+            
+            SuspensionAppeals::<T>::insert(&bounded_id, SuspensionAppeal {
+                appellant: who.clone(),
+                evidence_hash,
+                submitted_at: frame_system::Pallet::<T>::block_number(),
+            });
+            
+            // 发出事件
+            Self::deposit_event(Event::SuspensionAppealed(bounded_id, who, evidence_hash));
+            
+            Ok(())
+        }
+        
+        /// 添加受信任的核验方公钥（仅特权来源）
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::add_attestor())]
+        pub fn add_attestor(
+            origin: OriginFor<T>,
+            attestor: AttestorKey,
+        ) -> DispatchResult {
+            T::AttestorOrigin::ensure_origin(origin)?;
+            
+            Attestors::<T>::try_mutate(|attestors| -> DispatchResult {
+                ensure!(!attestors.contains(&attestor), Error::<T>::AlreadyAttestor);
+                attestors.try_push(attestor.clone())
+                    .map_err(|_| Error::<T>::TooManyAttestors)?;
+                Ok(())
+            })?;
+            
+            // 发出事件
+            Self::deposit_event(Event::AttestorAdded(attestor));
+            
+            Ok(())
+        }
+        
+        /// 移除受信任的核验方公钥（仅特权来源），其已签署的声明随之失效
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::remove_attestor())]
+        pub fn remove_attestor(
+            origin: OriginFor<T>,
+            attestor: AttestorKey,
+        ) -> DispatchResult {
+            T::AttestorOrigin::ensure_origin(origin)?;
+            
+            Attestors::<T>::try_mutate(|attestors| -> DispatchResult {
+                let index = attestors.iter().position(|a| a == &attestor)
+                    .ok_or(Error::<T>::NotAttestor)?;
+                attestors.remove(index);
+                Ok(())
+            })?;
+            
+            // 发出事件
+            Self::deposit_event(Event::AttestorRemoved(attestor));
+            
+            Ok(())
+        }
+        
+        /// 提交核验方签署的机构声明
+        ///
+        /// `claim` 为 SCALE 编码的 `AttestationClaim`，签名内容为 `ATTESTATION_CONTEXT` 与 `claim` 的拼接。
+        /// 同一机构、同一类型的声明仅能被更晚失效的声明替换。
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::submit_attestation())]
+        pub fn submit_attestation(
+            origin: OriginFor<T>,
+            claim: Vec<u8>,
+            attestor: AttestorKey,
+            signature: AttestorSignature,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // 检查核验方及签名
+            ensure!(Attestors::<T>::get().contains(&attestor), Error::<T>::UntrustedAttestor);
+            let message = [ATTESTATION_CONTEXT, &claim[..]].concat();
+            ensure!(signature.verify(&message, &attestor), Error::<T>::InvalidAttestationSignature);
+            
+            let claim = AttestationClaim::<T>::decode_all(&mut &claim[..])
+                .map_err(|_| Error::<T>::InvalidAttestationClaim)?;
+            ensure!(Institutions::<T>::contains_key(&claim.institution_id), Error::<T>::InstitutionNotFound);
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(claim.expires_at > now, Error::<T>::AttestationExpired);
+            if let Some(existing) = Attestations::<T>::get(&claim.institution_id, claim.claim_type) {
+                ensure!(claim.expires_at > existing.expires_at, Error::<T>::AttestationOutdated);
+            }
+            
+            Attestations::<T>::insert(&claim.institution_id, claim.claim_type, Attestation {
+                attestor: attestor.clone(),
+                evidence_hash: claim.evidence_hash,
+                expires_at: claim.expires_at,
+                submitted_at: now,
+                submitter: who,
+            });
+            
+            // 发出事件
+            Self::deposit_event(Event::AttestationRecorded(
+                claim.institution_id,
+                claim.claim_type,
+                attestor,
+                claim.expires_at,
+            ));
+            
+            Ok(())
+        }
+        
+        /// 分批清理已删除机构的依赖数据（任何人可调用），最多删除 `limit` 条，按实际删除条数计费
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::clean_up_institution(*limit))]
+        #[allow(clippy::useless_conversion)]
+        pub fn clean_up_institution(
+            origin: OriginFor<T>,
+            institution_id: Vec<u8>,
+            limit: u32,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            ensure!(PendingCleanups::<T>::contains_key(&bounded_id), Error::<T>::NoPendingCleanup);
+            
+            let removed = Self::clean_up(&bounded_id, limit);
+            
+            Ok(Some(T::WeightInfo::clean_up_institution(removed)).into())
+        }
+    }
+    
+    // 辅助函数
+    impl<T: Config> Pallet<T> {
+        /// 清理已删除机构的成员、核验声明及依赖模块数据，最多删除 `limit` 条，返回实际删除的条数；
+        /// 全部清理完毕时移出待清理列表
+        fn clean_up(institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>, limit: u32) -> u32 {
+            let mut removed = InstitutionMembers::<T>::drain_prefix(institution_id).take(limit as usize).count() as u32;
+            removed += Attestations::<T>::drain_prefix(institution_id).take((limit - removed) as usize).count() as u32;
+            if removed < limit {
+                removed += T::OnInstitutionRemoved::on_institution_removed(institution_id, limit - removed);
+            }
+            
+            if removed < limit {
+                PendingCleanups::<T>::remove(institution_id);
+                Self::deposit_event(Event::InstitutionCleanedUp(institution_id.clone()));
+            }
+            removed
+        }
+        
+        /// 计算存储押金：基础押金 + 每字节押金 × 编码长度
+        fn deposit_for(encoded_len: usize) -> BalanceOf<T> {
+            T::DepositBase::get()
+                .saturating_add(T::DepositPerByte::get().saturating_mul((encoded_len as u32).into()))
+        }
+        
+        /// 按记录的编码长度冻结或调整押金；调用者不是原押金账户时，退还原押金并由调用者重新缴纳
+        fn update_deposit(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            who: &T::AccountId,
+            encoded_len: usize,
+        ) -> DispatchResult {
+            let reason = HoldReason::InstitutionDeposit.into();
+            let new_deposit = Self::deposit_for(encoded_len);
+            
+            match InstitutionDeposits::<T>::get(institution_id) {
+                Some((depositor, old_deposit)) if &depositor == who => {
+                    if new_deposit > old_deposit {
+                        T::Currency::hold(&reason, who, new_deposit.saturating_sub(old_deposit))?;
+                    } else if old_deposit > new_deposit {
+                        T::Currency::release(&reason, who, old_deposit.saturating_sub(new_deposit), Precision::BestEffort)?;
+                    }
+                },
+                Some((depositor, old_deposit)) => {
+                    T::Currency::release(&reason, &depositor, old_deposit, Precision::BestEffort)?;
+                    T::Currency::hold(&reason, who, new_deposit)?;
+                },
+                None => T::Currency::hold(&reason, who, new_deposit)?,
+            }
+            
+            InstitutionDeposits::<T>::insert(institution_id, (who.clone(), new_deposit));
+            Ok(())
+        }
+        
+        /// 退还机构的存储押金
+        fn release_deposit(institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>) -> DispatchResult {
+            if let Some((depositor, deposit)) = InstitutionDeposits::<T>::take(institution_id) {
+                T::Currency::release(&HoldReason::InstitutionDeposit.into(), &depositor, deposit, Precision::BestEffort)?;
+            }
+            Ok(())
+        }
+        
+        /// 检查账户是否拥有机构的指定角色
+        pub fn member_has_role(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            who: &T::AccountId,
+            role: InstitutionRole,
+        ) -> bool {
+            let Some(institution) = Institutions::<T>::get(institution_id) else {
+                return false;
+            };
+            if &institution.creator == who {
+                return true;
+            }
+            if role == InstitutionRole::Owner {
+                return false;
+            }
+            
+            let roles = InstitutionMembers::<T>::get(institution_id, who);
+            roles.contains(&InstitutionRole::Admin) || roles.contains(&role)
+        }
+        
+        /// 确认调用者有权授予或撤销指定角色
+        fn ensure_can_assign(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            who: &T::AccountId,
+            role: InstitutionRole,
+        ) -> DispatchResult {
+            ensure!(role != InstitutionRole::Owner, Error::<T>::CannotAssignOwner);
+            ensure!(Institutions::<T>::contains_key(institution_id), Error::<T>::InstitutionNotFound);
+            
+            let required = match role {
+                InstitutionRole::Admin => InstitutionRole::Owner,
+                _ => InstitutionRole::Admin,
+            };
+            ensure!(Self::member_has_role(institution_id, who, required), Error::<T>::NotAuthorized);
+            Ok(())
+        }
+        
+        /// 检查账户是否为登记员
+        pub fn is_registrar(who: &T::AccountId) -> bool {
+            Registrars::<T>::get().contains(who)
+        }
+        
+        /// 为机构立案审核
+        fn open_audit_case(institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>) {
+            AuditCases::<T>::insert(institution_id, AuditCase {
+                status: AuditStatus::Pending,
+                opened_at: frame_system::Pallet::<T>::block_number(),
+                closed_at: None,
+                votes: BoundedVec::default(),
+            });
+            Self::deposit_event(Event::AuditCaseOpened(institution_id.clone()));
+        }
+        
+        /// 记录登记员意见，返回与本次意见相同的票数（通过数或驳回数）
+        fn cast_audit_vote(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            who: &T::AccountId,
+            approve: bool,
+            comment: Option<Vec<u8>>,
+        ) -> Result<u32, DispatchError> {
+            ensure!(Self::is_registrar(who), Error::<T>::NotRegistrar);
+            
+            let bounded_comment = match comment {
+                Some(c) => Some(BoundedVec::<u8, T::MaxAuditCommentLength>::try_from(c)
+                    .map_err(|_| Error::<T>::StringConversionError)?),
+                None => None,
+            };
+            
+            let institution = Institutions::<T>::get(institution_id)
+                .ok_or(Error::<T>::InstitutionNotFound)?;
+            ensure!(institution.status != InstitutionStatus::Deactivated, Error::<T>::InstitutionDeactivated);
+            
+            AuditCases::<T>::try_mutate(institution_id, |maybe_case| -> Result<u32, DispatchError> {
+                let case = maybe_case.as_mut().ok_or(Error::<T>::AuditCaseNotFound)?;
+                ensure!(!case.votes.iter().any(|v| &v.registrar == who), Error::<T>::AlreadyVoted);
+                
+                case.votes.try_push(AuditVote {
+                    registrar: who.clone(),
+                    approve,
+                    comment: bounded_comment,
+                    voted_at: frame_system::Pallet::<T>::block_number(),
+                }).map_err(|_| Error::<T>::TooManyRegistrars)?;
+                
+                Ok(case.votes.iter().filter(|v| v.approve == approve).count() as u32)
+            })
+        }
+        
+        /// 结案并将案件移入历史
+        fn close_audit_case(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            status: AuditStatus,
+        ) {
+            if let Some(mut case) = AuditCases::<T>::take(institution_id) {
+                case.status = status;
+                case.closed_at = Some(frame_system::Pallet::<T>::block_number());
+                AuditHistory::<T>::mutate(institution_id, |history| {
+                    if history.is_full() && !history.is_empty() {
+                        history.remove(0);
+                    }
+                    let _ = history.try_push(case);
+                });
+            }
+        }
+        
+        /// 写入认证记录并发出认证事件
+        fn record_certification(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            certifier: Option<T::AccountId>,
+        ) {
+            Certifications::<T>::insert(institution_id, CertificationRecord {
+                certifier: certifier.clone(),
+                certified_at: frame_system::Pallet::<T>::block_number(),
+                revoked_at: None,
+                revocation_reason: None,
+            });
+            
+            Self::deposit_event(Event::InstitutionStatusUpdated(institution_id.clone(), InstitutionStatus::<T>::Certified.code()));
+            Self::deposit_event(Event::InstitutionCertified(institution_id.clone(), certifier));
+        }
+        
+        /// 从到期及预警队列中移除机构的营业执照
+        fn unschedule_license(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            expires_at: BlockNumberFor<T>,
+        ) {
+            let warn_at = expires_at.saturating_sub(T::LicenseExpiryWarningPeriod::get());
+            LicenseExpiries::<T>::mutate(expires_at, |ids| ids.retain(|id| id != institution_id));
+            LicenseExpiryWarnings::<T>::mutate(warn_at, |ids| ids.retain(|id| id != institution_id));
+        }
+        
+        /// 营业执照到期：已认证的机构恢复为未认证
+        fn lapse_certification(institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>, now: BlockNumberFor<T>) {
+            Self::deposit_event(Event::LicenseExpired(institution_id.clone()));
+            
+            let Some(status) = Institutions::<T>::get(institution_id).map(|institution| institution.status) else {
+                return;
+            };
+            
+            // 暂停中的机构仅撤销认证记录，解除暂停后恢复为未认证
+            let lapsed = match status {
+                InstitutionStatus::Certified => {
+                    Institutions::<T>::mutate(institution_id, |maybe_institution| {
+                        if let Some(institution) = maybe_institution {
+                            Self::set_status(institution_id, institution, InstitutionStatus::NotCertified, None);
+                        }
+                    });
+                    Self::deposit_event(Event::InstitutionStatusUpdated(institution_id.clone(), InstitutionStatus::<T>::NotCertified.code()));
+                    true
+                },
+                InstitutionStatus::Suspended { .. } => Self::holds_certification(institution_id),
+                _ => false,
+            };
+            
+            if lapsed {
+                Certifications::<T>::mutate(institution_id, |maybe_record| {
+                    if let Some(record) = maybe_record {
+                        record.revoked_at = Some(now);
+                    }
+                });
+                Self::deposit_event(Event::CertificationRevoked(institution_id.clone(), None));
+            }
+        }
+        
+        /// 追加机构信息修订记录
+        fn record_revision(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            previous_hash: T::Hash,
+            changes: Vec<InstitutionFieldChange<T>>,
+            signer: Option<&T::AccountId>,
+        ) {
+            let revision = InstitutionRevisionCount::<T>::mutate(institution_id, |count| {
+                *count = count.saturating_add(1);
+                *count
+            });
+            
+            InstitutionRevisions::<T>::mutate(institution_id, |revisions| {
+                if revisions.is_full() && !revisions.is_empty() {
+                    revisions.remove(0);
+                }
+                let _ = revisions.try_push(InstitutionRevision {
+                    revision,
+                    previous_hash,
+                    changes: BoundedVec::truncate_from(changes),
+                    block: frame_system::Pallet::<T>::block_number(),
+                    signer: signer.cloned(),
+                });
+            });
+            
+            Self::deposit_event(Event::InstitutionRevised(institution_id.clone(), revision));
+        }
+        
+        /// 重建机构信息在指定修订号时的记录
+        ///
+        /// 从当前记录依次回滚之后的修订，还原信息字段、状态、所有者及上级机构；
+        /// 所需修订已超出保留范围或修订号不存在时返回 `None`。
+        pub fn institution_at_revision(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            revision: u32,
+        ) -> Option<InstitutionInfo<T>> {
+            let mut institution = Institutions::<T>::get(institution_id)?;
+            let current = InstitutionRevisionCount::<T>::get(institution_id);
+            if revision > current {
+                return None;
+            }
+            
+            let revisions = InstitutionRevisions::<T>::get(institution_id);
+            if revision < current && revisions.first().is_none_or(|oldest| oldest.revision > revision + 1) {
+                return None;
+            }
+            
+            for entry in revisions.iter().rev().take_while(|entry| entry.revision > revision) {
+                for change in entry.changes.clone() {
+                    match change {
+                        InstitutionFieldChange::InstitutionName(value) => institution.institution_name = value,
+                        InstitutionFieldChange::InstitutionFullName(value) => institution.institution_full_name = value,
+                        InstitutionFieldChange::LicenseImageUrl(value) => institution.license_image_url = value,
+                        InstitutionFieldChange::ResponsiblePerson(value) => institution.responsible_person = value,
+                        InstitutionFieldChange::BusinessScope(value) => institution.business_scope = value,
+                        InstitutionFieldChange::ProfitContract(value) => institution.profit_contract = value,
+                        InstitutionFieldChange::Status(value) => institution.status = value,
+                        InstitutionFieldChange::Creator(value) => institution.creator = value,
+                        InstitutionFieldChange::Parent(value) => institution.parent = value,
+                    }
+                }
+            }
+            
+            Some(institution)
+        }
+        
+        /// 变更机构状态，同步状态索引并记录修订
+        fn set_status(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            institution: &mut InstitutionInfo<T>,
+            status: InstitutionStatus<T>,
+            signer: Option<&T::AccountId>,
+        ) {
+            if institution.status == status {
+                return;
+            }
+            let previous_hash = T::Hashing::hash_of(&*institution);
+            InstitutionsByStatus::<T>::remove(institution.status.code(), institution_id);
+            InstitutionsByStatus::<T>::insert(status.code(), institution_id, ());
+            let old_status = core::mem::replace(&mut institution.status, status);
+            Self::record_revision(institution_id, previous_hash, vec![InstitutionFieldChange::Status(old_status)], signer);
+        }
+        
+        /// 写入机构的状态及所有者索引
+        pub(crate) fn index_institution(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            institution: &InstitutionInfo<T>,
+        ) {
+            InstitutionsByStatus::<T>::insert(institution.status.code(), institution_id, ());
+            InstitutionsByCreator::<T>::insert(&institution.creator, institution_id, ());
+        }
+        
+        /// 移除机构的状态及所有者索引
+        fn unindex_institution(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            institution: &InstitutionInfo<T>,
+        ) {
+            InstitutionsByStatus::<T>::remove(institution.status.code(), institution_id);
+            InstitutionsByCreator::<T>::remove(&institution.creator, institution_id);
+        }
+        
+        /// 从机构迭代器中收集一页结果
+        ///
+        /// 页满或检查数达到 `MaxDirectoryScan` 时停止，并以最后检查的机构ID作为下一页游标；
+        /// 未检查任何机构（如 `limit` 为 0）时原样返回传入的游标。
+        fn collect_page(
+            mut institutions: impl Iterator<Item = (BoundedVec<u8, T::MaxInstitutionIdLength>, InstitutionInfo<T>)>,
+            cursor: Option<BoundedVec<u8, T::MaxInstitutionIdLength>>,
+            limit: u32,
+            matches: impl Fn(&InstitutionInfo<T>) -> bool,
+        ) -> InstitutionPage<InstitutionInfo<T>> {
+            let limit = limit.min(T::MaxDirectoryPageSize::get()) as usize;
+            let mut items = Vec::new();
+            let mut scanned = 0u32;
+            let mut last = cursor;
+            
+            while items.len() < limit && scanned < T::MaxDirectoryScan::get() {
+                let Some((id, institution)) = institutions.next() else {
+                    return InstitutionPage { items, next_cursor: None };
+                };
+                scanned += 1;
+                if matches(&institution) {
+                    items.push((id.to_vec(), institution));
+                }
+                last = Some(id);
+            }
+            
+            InstitutionPage { items, next_cursor: last.map(|id| id.into_inner()) }
+        }
+        
+        /// 查询机构指定类型的有效核验声明（未过期且核验方仍受信任）
+        pub fn valid_attestation(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            claim_type: u32,
+        ) -> Option<Attestation<T>> {
+            Attestations::<T>::get(institution_id, claim_type).filter(|attestation| {
+                attestation.expires_at > frame_system::Pallet::<T>::block_number() &&
+                    Attestors::<T>::get().contains(&attestation.attestor)
+            })
+        }
+        
+        /// 机构是否持有未撤销的认证记录
+        fn holds_certification(institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>) -> bool {
+            Certifications::<T>::get(institution_id).is_some_and(|record| record.revoked_at.is_none())
+        }
+        
+        /// 解除暂停：按认证记录恢复为已认证或未认证，并清除申诉
+        fn end_suspension(institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>) {
+            let restored = if Self::holds_certification(institution_id) {
+                InstitutionStatus::Certified
+            } else {
+                InstitutionStatus::NotCertified
+            };
+            let code = restored.code();
+            
+            Institutions::<T>::mutate(institution_id, |maybe_institution| {
+                if let Some(institution) = maybe_institution {
+                    Self::set_status(institution_id, institution, restored, None);
+                }
+            });
+            SuspensionAppeals::<T>::remove(institution_id);
+            
+            Self::deposit_event(Event::InstitutionStatusUpdated(institution_id.clone(), code));
+            Self::deposit_event(Event::SuspensionLifted(institution_id.clone()));
+        }
+        
+        /// 机构的全部上级机构（由近及远）
+        pub fn ancestors(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+        ) -> Vec<BoundedVec<u8, T::MaxInstitutionIdLength>> {
+            let mut ancestors = Vec::new();
+            let mut current = Institutions::<T>::get(institution_id).and_then(|institution| institution.parent);
+            while let Some(link) = current {
+                if ancestors.len() as u32 >= T::MaxHierarchyDepth::get() {
+                    break;
+                }
+                current = Institutions::<T>::get(&link.parent_id).and_then(|institution| institution.parent);
+                ancestors.push(link.parent_id);
+            }
+            ancestors
+        }
+        
+        /// 机构的全部下级机构（按层级广度优先）
+        pub fn descendants(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+        ) -> Vec<BoundedVec<u8, T::MaxInstitutionIdLength>> {
+            let mut descendants = Vec::new();
+            let mut level = Vec::from([institution_id.clone()]);
+            for _ in 0..T::MaxHierarchyDepth::get() {
+                level = level.iter().flat_map(|id| InstitutionBranches::<T>::get(id).into_inner()).collect();
+                if level.is_empty() {
+                    break;
+                }
+                descendants.extend(level.iter().cloned());
+            }
+            descendants
+        }
+        
+        /// 机构之下的分支层数
+        fn subtree_height(institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>) -> u32 {
+            let mut height = 0;
+            let mut level = Vec::from([institution_id.clone()]);
+            while height < T::MaxHierarchyDepth::get() {
+                level = level.iter().flat_map(|id| InstitutionBranches::<T>::get(id).into_inner()).collect();
+                if level.is_empty() {
+                    break;
+                }
+                height += 1;
+            }
+            height
+        }
+        
+        /// 从上级机构的分支索引中移除机构
+        fn remove_branch(
+            parent_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+        ) {
+            InstitutionBranches::<T>::mutate_exists(parent_id, |maybe_branches| {
+                if let Some(branches) = maybe_branches {
+                    branches.retain(|id| id != institution_id);
+                    if branches.is_empty() {
+                        *maybe_branches = None;
+                    }
+                }
+            });
+        }
+        
+        /// 机构是否已认证（含沿层级继承的总部认证）
+        pub fn is_effectively_certified(institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>) -> bool {
+            let mut current = Institutions::<T>::get(institution_id);
+            for _ in 0..=T::MaxHierarchyDepth::get() {
+                let Some(institution) = current else {
+                    return false;
+                };
+                match institution.status {
+                    InstitutionStatus::Certified => return true,
+                    InstitutionStatus::Deactivated | InstitutionStatus::Suspended { .. } => return false,
+                    InstitutionStatus::NotCertified => {},
+                }
+                current = match institution.parent {
+                    Some(link) if link.inherit_certification => Institutions::<T>::get(&link.parent_id),
+                    _ => return false,
+                };
+            }
+            false
+        }
+        
+        /// 机构当前适用的分润合约（自身未设置时可沿层级继承总部合约）
+        pub fn effective_profit_contract(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+        ) -> Option<ProfitContract<T>> {
+            let mut current = Institutions::<T>::get(institution_id);
+            for _ in 0..=T::MaxHierarchyDepth::get() {
+                let institution = current?;
+                if institution.profit_contract.is_some() {
+                    return institution.profit_contract;
+                }
+                current = match institution.parent {
+                    Some(link) if link.inherit_profit_contract => Institutions::<T>::get(&link.parent_id),
+                    _ => return None,
+                };
+            }
+            None
+        }
+        
+        /// 校验分润定义：受益人非空、比例之和为100%、有效期合法
+        fn ensure_valid_split(split: &RevenueSplit<T>) -> DispatchResult {
+            let total = split.shares.iter()
+                .fold(0u64, |total, share| total.saturating_add(share.share.deconstruct() as u64));
+            ensure!(
+                !split.shares.is_empty() && total == Perbill::one().deconstruct() as u64,
+                Error::<T>::InvalidProfitShares
+            );
+            
+            if let Some(until) = split.effective_until {
+                ensure!(until > split.effective_from, Error::<T>::InvalidEffectivePeriod);
+            }
+            
+            Ok(())
+        }
+        
+        /// 按机构当前生效的分润合约分配订单收入
+        ///
+        /// 每笔款项交由 `pay` 支付（零金额跳过）：先扣除平台分成，剩余部分按受益人比例分配，
+        /// 舍入产生的余数归最后一位受益人。没有生效的结构化合约时全额支付给机构所有者。
+        pub fn distribute(
+            institution_id: &BoundedVec<u8, T::MaxInstitutionIdLength>,
+            amount: BalanceOf<T>,
+            pay: &mut dyn FnMut(&T::AccountId, BalanceOf<T>) -> DispatchResult,
+        ) -> DispatchResult {
+            let institution = Institutions::<T>::get(institution_id).ok_or(Error::<T>::InstitutionNotFound)?;
+            if amount.is_zero() {
+                return Ok(());
+            }
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            let split = match Self::effective_profit_contract(institution_id) {
+                Some(ProfitContract::Split(split))
+                    if now >= split.effective_from && split.effective_until.is_none_or(|until| now < until) =>
+                    split,
+                _ => {
+                    pay(&institution.creator, amount)?;
+                    Self::deposit_event(Event::ProfitDistributed(institution_id.clone(), amount));
+                    return Ok(());
+                },
+            };
+            
+            let mut remaining = amount;
+            
+            // 平台分成
+            if let Some(cut) = split.platform_cut {
+                let platform_amount = cut.mul_floor(amount);
+                if !platform_amount.is_zero() {
+                    pay(&T::PlatformAccount::get(), platform_amount)?;
+                }
+                remaining = remaining.saturating_sub(platform_amount);
+            }
+            
+            // 受益人分成
+            let distributable = remaining;
+            let last = split.shares.len().saturating_sub(1);
+            for (index, share) in split.shares.iter().enumerate() {
+                let payout = if index == last { remaining } else { share.share.mul_floor(distributable) };
+                if !payout.is_zero() {
+                    pay(&share.beneficiary, payout)?;
+                }
+                remaining = remaining.saturating_sub(payout);
+            }
+            
+            Self::deposit_event(Event::ProfitDistributed(institution_id.clone(), amount));
+            
+            Ok(())
+        }
+    }
+
+    impl<T: Config> crate::ProfitDistributor<T::AccountId, BalanceOf<T>> for Pallet<T> {
+        fn distribute(
+            institution_id: &[u8],
+            amount: BalanceOf<T>,
+            pay: &mut dyn FnMut(&T::AccountId, BalanceOf<T>) -> DispatchResult,
+        ) -> DispatchResult {
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec())
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            Self::distribute(&bounded_id, amount, pay)
+        }
+    }
+
+    #[pallet::view_functions_experimental]
+    impl<T: Config> Pallet<T> {
+        /// 查询单个机构
+        pub fn institution(institution_id: Vec<u8>) -> Option<InstitutionInfo<T>> {
+            let bounded_id = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id).ok()?;
+            Institutions::<T>::get(&bounded_id)
+        }
+        
+        /// 按条件分页查询机构目录
+        ///
+        /// `cursor` 为上一页返回的 `next_cursor`，从该机构之后继续；每页最多 `MaxDirectoryPageSize` 条，
+        /// `limit` 为 0 时返回空页并原样返回 `cursor`。
+        /// 按状态和所有者查询走二级索引，全部及名称前缀查询按存储顺序遍历机构。
+        pub fn institution_page(
+            filter: InstitutionFilter<T::AccountId>,
+            cursor: Option<Vec<u8>>,
+            limit: u32,
+        ) -> InstitutionPage<InstitutionInfo<T>> {
+            let cursor = match cursor.map(BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from) {
+                Some(Ok(cursor)) => Some(cursor),
+                Some(Err(_)) => return InstitutionPage { items: Vec::new(), next_cursor: None },
+                None => None,
+            };
+            let with_info = |id: BoundedVec<u8, T::MaxInstitutionIdLength>| {
+                Institutions::<T>::get(&id).map(|institution| (id, institution))
+            };
+            
+            match filter {
+                InstitutionFilter::Status(code) => {
+                    let ids = match &cursor {
+                        Some(cursor) => InstitutionsByStatus::<T>::iter_key_prefix_from(
+                            code,
+                            InstitutionsByStatus::<T>::hashed_key_for(code, cursor),
+                        ),
+                        None => InstitutionsByStatus::<T>::iter_key_prefix(code),
+                    };
+                    Self::collect_page(ids.filter_map(with_info), cursor, limit, |_| true)
+                },
+                InstitutionFilter::Creator(creator) => {
+                    let ids = match &cursor {
+                        Some(cursor) => InstitutionsByCreator::<T>::iter_key_prefix_from(
+                            &creator,
+                            InstitutionsByCreator::<T>::hashed_key_for(&creator, cursor),
+                        ),
+                        None => InstitutionsByCreator::<T>::iter_key_prefix(&creator),
+                    };
+                    Self::collect_page(ids.filter_map(with_info), cursor, limit, |_| true)
+                },
+                InstitutionFilter::All | InstitutionFilter::NamePrefix(_) => {
+                    let institutions = match &cursor {
+                        Some(cursor) => Institutions::<T>::iter_from(Institutions::<T>::hashed_key_for(cursor)),
+                        None => Institutions::<T>::iter(),
+                    };
+                    let prefix = match filter {
+                        InstitutionFilter::NamePrefix(prefix) => prefix,
+                        _ => Vec::new(),
+                    };
+                    Self::collect_page(institutions, cursor, limit, |institution| {
+                        institution.institution_name.starts_with(&prefix)
+                    })
+                },
+            }
+        }
+    }
+
+    impl<T: Config> crate::InstitutionInspect<T::AccountId> for Pallet<T> {
+        fn exists(institution_id: &[u8]) -> bool {
+            BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec())
+                .map(|id| Institutions::<T>::contains_key(&id))
+                .unwrap_or(false)
+        }
+        
+        fn is_certified(institution_id: &[u8]) -> bool {
+            BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec())
+                .map(|id| Self::is_effectively_certified(&id))
+                .unwrap_or(false)
+        }
+        
+        fn is_suspended(institution_id: &[u8]) -> bool {
+            let now = frame_system::Pallet::<T>::block_number();
+            BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec())
+                .ok()
+                .and_then(|id| Institutions::<T>::get(&id))
+                .is_some_and(|institution| {
+                    matches!(institution.status, InstitutionStatus::Suspended { until, .. } if until > now)
+                })
+        }
+        
+        fn is_active(institution_id: &[u8]) -> bool {
+            let now = frame_system::Pallet::<T>::block_number();
+            BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec())
+                .ok()
+                .and_then(|id| Institutions::<T>::get(&id))
+                .is_some_and(|institution| match institution.status {
+                    InstitutionStatus::Deactivated => false,
+                    InstitutionStatus::Suspended { until, .. } => until <= now,
+                    InstitutionStatus::Certified | InstitutionStatus::NotCertified => true,
+                })
+        }
+        
+        fn has_attestation(institution_id: &[u8], claim_type: u32) -> bool {
+            BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec())
+                .map(|id| Self::valid_attestation(&id, claim_type).is_some())
+                .unwrap_or(false)
+        }
+        
+        fn has_role(institution_id: &[u8], who: &T::AccountId, role: InstitutionRole) -> bool {
+            BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec())
+                .map(|id| {
+                    // 总部管理员可管理分支（所有者角色除外）
+                    Self::member_has_role(&id, who, role) ||
+                        (role != InstitutionRole::Owner &&
+                            Self::ancestors(&id)
+                                .iter()
+                                .any(|ancestor| Self::member_has_role(ancestor, who, InstitutionRole::Admin)))
+                })
+                .unwrap_or(false)
+        }
+
+        fn institutions_of(who: &T::AccountId) -> Vec<Vec<u8>> {
+            InstitutionsByCreator::<T>::iter_key_prefix(who).map(|id| id.into_inner()).collect()
         }
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
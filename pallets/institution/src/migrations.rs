@@ -0,0 +1,284 @@
+//! 机构模块存储迁移
+
+/// v1：将自由格式的分润合约字节迁移为 `ProfitContract::Legacy`
+pub mod v1 {
+    use crate::pallet::{Config, InstitutionStatus, Pallet, ProfitContract};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        storage_alias,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    use frame_system::pallet_prelude::BlockNumberFor;
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// v0 版本的机构信息结构
+    #[derive(Encode, Decode)]
+    pub struct OldInstitutionInfo<T: Config> {
+        pub institution_name: BoundedVec<u8, T::MaxNameLength>,
+        pub status: InstitutionStatus<T>,
+        pub institution_full_name: BoundedVec<u8, T::MaxNameLength>,
+        pub license_image_url: BoundedVec<u8, T::MaxNameLength>,
+        pub responsible_person: BoundedVec<u8, T::MaxResponsiblePersonLength>,
+        pub business_scope: BoundedVec<u8, T::MaxBusinessScopeLength>,
+        pub profit_contract: Option<BoundedVec<u8, T::MaxContractLength>>,
+        pub created_date: BlockNumberFor<T>,
+        pub creator: T::AccountId,
+    }
+
+    /// v1 版本的机构信息结构
+    #[derive(Encode, Decode)]
+    pub struct InstitutionInfoV1<T: Config> {
+        pub institution_name: BoundedVec<u8, T::MaxNameLength>,
+        pub status: InstitutionStatus<T>,
+        pub institution_full_name: BoundedVec<u8, T::MaxNameLength>,
+        pub license_image_url: BoundedVec<u8, T::MaxNameLength>,
+        pub responsible_person: BoundedVec<u8, T::MaxResponsiblePersonLength>,
+        pub business_scope: BoundedVec<u8, T::MaxBusinessScopeLength>,
+        pub profit_contract: Option<ProfitContract<T>>,
+        pub created_date: BlockNumberFor<T>,
+        pub creator: T::AccountId,
+    }
+
+    /// v1 版本的机构存储
+    #[storage_alias]
+    pub type Institutions<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        BoundedVec<u8, <T as Config>::MaxInstitutionIdLength>,
+        InstitutionInfoV1<T>,
+        OptionQuery,
+    >;
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((Institutions::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            Institutions::<T>::translate::<OldInstitutionInfo<T>, _>(|_, old| {
+                translated += 1;
+                Some(InstitutionInfoV1 {
+                    institution_name: old.institution_name,
+                    status: old.status,
+                    institution_full_name: old.institution_full_name,
+                    license_image_url: old.license_image_url,
+                    responsible_person: old.responsible_person,
+                    business_scope: old.business_scope,
+                    profit_contract: old.profit_contract.map(ProfitContract::Legacy),
+                    created_date: old.created_date,
+                    creator: old.creator,
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after = Institutions::<T>::iter_values().count() as u32;
+            ensure!(before == after, "institutions lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v0 迁移到 v1，仅在链上存储版本为 0 时执行
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// v2：为机构信息增加上级机构字段，已有机构均为独立机构
+pub mod v2 {
+    use super::v1::InstitutionInfoV1;
+    use crate::pallet::{Config, InstitutionInfo, Institutions, Pallet};
+    #[cfg(feature = "try-runtime")]
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV1ToV2<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((Institutions::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            Institutions::<T>::translate::<InstitutionInfoV1<T>, _>(|_, old| {
+                translated += 1;
+                Some(InstitutionInfo {
+                    institution_name: old.institution_name,
+                    status: old.status,
+                    institution_full_name: old.institution_full_name,
+                    license_image_url: old.license_image_url,
+                    responsible_person: old.responsible_person,
+                    business_scope: old.business_scope,
+                    profit_contract: old.profit_contract,
+                    created_date: old.created_date,
+                    creator: old.creator,
+                    parent: None,
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after = Institutions::<T>::iter_values().count() as u32;
+            ensure!(before == after, "institutions lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v1 迁移到 v2，仅在链上存储版本为 1 时执行
+    pub type MigrateV1ToV2<T> = VersionedMigration<
+        1,
+        2,
+        InnerMigrateV1ToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// v3：为已有机构建立按状态及所有者的目录索引
+pub mod v3 {
+    use crate::pallet::{Config, Institutions, Pallet};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    #[cfg(feature = "try-runtime")]
+    use crate::pallet::{InstitutionsByCreator, InstitutionsByStatus};
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV2ToV3<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV2ToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut indexed = 0u64;
+            for (institution_id, institution) in Institutions::<T>::iter() {
+                Pallet::<T>::index_institution(&institution_id, &institution);
+                indexed += 1;
+            }
+            T::DbWeight::get().reads_writes(indexed, indexed.saturating_mul(2))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            for (institution_id, institution) in Institutions::<T>::iter() {
+                ensure!(
+                    InstitutionsByStatus::<T>::contains_key(institution.status.code(), &institution_id),
+                    "institution missing from status index"
+                );
+                ensure!(
+                    InstitutionsByCreator::<T>::contains_key(&institution.creator, &institution_id),
+                    "institution missing from creator index"
+                );
+            }
+            Ok(())
+        }
+    }
+
+    /// 从 v2 迁移到 v3，仅在链上存储版本为 2 时执行
+    pub type MigrateV2ToV3<T> = VersionedMigration<
+        2,
+        3,
+        InnerMigrateV2ToV3<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// v4：修订记录的修改人改为可选，治理来源或自动变更时为空
+pub mod v4 {
+    use crate::pallet::{Config, InstitutionFieldChange, InstitutionRevision, InstitutionRevisions, Pallet};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    use frame_system::pallet_prelude::BlockNumberFor;
+    use sp_std::vec::Vec;
+
+    /// v3 版本的修订记录结构
+    #[derive(Encode, Decode)]
+    pub struct InstitutionRevisionV3<T: Config> {
+        pub revision: u32,
+        pub previous_hash: T::Hash,
+        pub changes: BoundedVec<InstitutionFieldChange<T>, ConstU32<6>>,
+        pub block: BlockNumberFor<T>,
+        pub signer: T::AccountId,
+    }
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV3ToV4<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV3ToV4<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            let total: u32 = InstitutionRevisions::<T>::iter_values().map(|revisions| revisions.len() as u32).sum();
+            Ok(total.encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            InstitutionRevisions::<T>::translate::<BoundedVec<InstitutionRevisionV3<T>, T::MaxRevisions>, _>(|_, old| {
+                translated += 1;
+                let revisions = old
+                    .into_iter()
+                    .map(|revision| InstitutionRevision {
+                        revision: revision.revision,
+                        previous_hash: revision.previous_hash,
+                        changes: revision.changes,
+                        block: revision.block,
+                        signer: Some(revision.signer),
+                    })
+                    .collect::<Vec<_>>();
+                Some(BoundedVec::truncate_from(revisions))
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after: u32 = InstitutionRevisions::<T>::iter_values().map(|revisions| revisions.len() as u32).sum();
+            ensure!(before == after, "revisions lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v3 迁移到 v4，仅在链上存储版本为 3 时执行
+    pub type MigrateV3ToV4<T> = VersionedMigration<
+        3,
+        4,
+        InnerMigrateV3ToV4<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
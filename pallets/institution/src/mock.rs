@@ -0,0 +1,137 @@
+use crate as pallet_institution;
+use frame_support::{derive_impl, parameter_types, traits::{ConstU32, ConstU64}};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    #[runtime::pallet_index(1)]
+    pub type Institution = pallet_institution::Pallet<Test>;
+
+    #[runtime::pallet_index(2)]
+    pub type Balances = pallet_balances::Pallet<Test>;
+}
+
+parameter_types! {
+    /// 存在未结束依赖数据（如订单）的机构ID
+    pub static BlockedInstitutions: Vec<Vec<u8>> = Vec::new();
+    /// 已被级联清理的机构ID
+    pub static RemovedInstitutions: Vec<Vec<u8>> = Vec::new();
+}
+
+/// 模拟依赖模块的机构删除钩子
+pub struct MockDependents;
+
+impl crate::OnInstitutionRemoved for MockDependents {
+    fn can_remove(institution_id: &[u8]) -> frame_support::dispatch::DispatchResult {
+        if BlockedInstitutions::get().iter().any(|id| id.as_slice() == institution_id) {
+            return Err(sp_runtime::DispatchError::Other("open orders"));
+        }
+        Ok(())
+    }
+
+    fn on_institution_removed(institution_id: &[u8], _limit: u32) -> u32 {
+        RemovedInstitutions::mutate(|ids| ids.push(institution_id.to_vec()));
+        0
+    }
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+}
+
+impl pallet_institution::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxInstitutionIdLength = ConstU32<64>;
+    type MaxNameLength = ConstU32<256>;
+    type MaxResponsiblePersonLength = ConstU32<128>;
+    type MaxBusinessScopeLength = ConstU32<512>;
+    type MaxContractLength = ConstU32<1024>;
+    type MaxBeneficiaries = ConstU32<4>;
+    type PlatformAccount = ConstU64<99>;
+    type CertifyOrigin = EnsureRoot<u64>;
+    type MaxRevocationReasonLength = ConstU32<256>;
+    type RegistrarOrigin = EnsureRoot<u64>;
+    type MaxRegistrars = ConstU32<5>;
+    type ApprovalThreshold = ConstU32<2>;
+    type RejectionThreshold = ConstU32<2>;
+    type MaxAuditCommentLength = ConstU32<256>;
+    type MaxAuditHistory = ConstU32<2>;
+    type OwnerTransferPeriod = ConstU64<10>;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type DepositBase = ConstU64<100>;
+    type DepositPerByte = ConstU64<1>;
+    type OnInstitutionRemoved = MockDependents;
+    type MaxAuthorityCodeLength = ConstU32<32>;
+    type MaxRegistrationNumberLength = ConstU32<64>;
+    type LicenseExpiryWarningPeriod = ConstU64<5>;
+    type MaxLicenseExpiriesPerBlock = ConstU32<2>;
+    type MaxHierarchyDepth = ConstU32<2>;
+    type MaxBranches = ConstU32<4>;
+    type MaxRevisions = ConstU32<2>;
+    type SuspendOrigin = EnsureRoot<u64>;
+    type MaxSuspensionReasonLength = ConstU32<128>;
+    type MaxSuspensionsPerBlock = ConstU32<2>;
+    type AttestorOrigin = EnsureRoot<u64>;
+    type MaxAttestors = ConstU32<2>;
+    type MaxDirectoryPageSize = ConstU32<3>;
+    type MaxDirectoryScan = ConstU32<4>;
+    type CleanupBatchSize = ConstU32<2>;
+    type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: (1..=5).map(|who| (who, 10_000)).collect(),
+        ..Default::default()
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = storage.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// 以指定账户创建一个测试机构
+pub fn create_institution(who: u64, id: &[u8]) {
+    frame_support::assert_ok!(Institution::create_institution(
+        RuntimeOrigin::signed(who),
+        id.to_vec(),
+        b"Shop".to_vec(),
+        b"Shop Co., Ltd.".to_vec(),
+        b"https://example.com/license.png".to_vec(),
+        b"Zhang San".to_vec(),
+        b"Retail".to_vec(),
+        None,
+    ));
+}
@@ -0,0 +1,23 @@
+//! 机构目录运行时 API
+
+use crate::{InstitutionFilter, InstitutionPage};
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// 机构目录查询
+    pub trait InstitutionDirectoryApi<AccountId, InstitutionInfo>
+    where
+        AccountId: Codec,
+        InstitutionInfo: Codec,
+    {
+        /// 查询单个机构
+        fn institution(institution_id: Vec<u8>) -> Option<InstitutionInfo>;
+        /// 按条件分页查询机构目录，`cursor` 为上一页返回的游标
+        fn institution_page(
+            filter: InstitutionFilter<AccountId>,
+            cursor: Option<Vec<u8>>,
+            limit: u32,
+        ) -> InstitutionPage<InstitutionInfo>;
+    }
+}
@@ -0,0 +1,1057 @@
+use crate::{
+    mock::*, AttestationClaim, Attestations, AttestorKey, AttestorSignature, AuditCases, AuditHistory, AuditStatus, Certifications, Error, Event, InstitutionInspect,
+    InstitutionBranches, InstitutionDeposits, InstitutionFieldChange, InstitutionFilter, InstitutionMembers, InstitutionsByCreator, InstitutionsByStatus, InstitutionRevisionCount, InstitutionRevisions, InstitutionRole, InstitutionTombstones, InstitutionStatus, Institutions, LicenseExpiries, Licenses,
+    PendingCleanups, PendingOwnerTransfers, ProfitContract, ProfitShare, Registrars, RevenueSplit, SuspensionAppeals,
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{fungible::Mutate, tokens::Preservation, Hooks},
+    weights::Weight,
+    BoundedVec,
+};
+use sp_core::{ed25519, sr25519, Pair, H256};
+use sp_runtime::{
+    traits::{BlakeTwo256, Hash},
+    BuildStorage, DispatchError, Perbill,
+};
+
+fn bounded(id: &[u8]) -> BoundedVec<u8, frame_support::traits::ConstU32<64>> {
+    BoundedVec::try_from(id.to_vec()).unwrap()
+}
+
+#[test]
+fn creator_cannot_certify_own_institution() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_noop!(
+            Institution::update_institution_status(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 0),
+            Error::<Test>::CertificationRequiresGovernance
+        );
+        assert_noop!(
+            Institution::certify_institution(RuntimeOrigin::signed(1), b"INST_001".to_vec()),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn certify_origin_certifies_and_revokes() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_ok!(Institution::certify_institution(RuntimeOrigin::root(), b"INST_001".to_vec()));
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().status, InstitutionStatus::Certified);
+        let record = Certifications::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_eq!(record.certifier, None);
+        assert_eq!(record.certified_at, 1);
+        System::assert_last_event(Event::InstitutionCertified(bounded(b"INST_001"), None).into());
+
+        System::set_block_number(5);
+        assert_ok!(Institution::revoke_certification(
+            RuntimeOrigin::root(),
+            b"INST_001".to_vec(),
+            Some(b"license expired".to_vec()),
+        ));
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().status, InstitutionStatus::NotCertified);
+        let record = Certifications::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_eq!(record.revoked_at, Some(5));
+        assert_eq!(record.revocation_reason.unwrap().into_inner(), b"license expired".to_vec());
+    });
+}
+
+#[test]
+fn creator_can_only_deactivate() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_noop!(
+            Institution::update_institution_status(RuntimeOrigin::signed(2), b"INST_001".to_vec(), 2),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Institution::update_institution_status(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2));
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().status, InstitutionStatus::Deactivated);
+
+        assert_noop!(
+            Institution::certify_institution(RuntimeOrigin::root(), b"INST_001".to_vec()),
+            Error::<Test>::InstitutionDeactivated
+        );
+    });
+}
+
+fn add_registrars(registrars: &[u64]) {
+    for r in registrars {
+        assert_ok!(Institution::add_registrar(RuntimeOrigin::root(), *r));
+    }
+}
+
+#[test]
+fn registrar_set_is_managed_by_privileged_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Institution::add_registrar(RuntimeOrigin::signed(1), 10), DispatchError::BadOrigin);
+
+        add_registrars(&[10, 11]);
+        assert_eq!(Registrars::<Test>::get().into_inner(), vec![10, 11]);
+        assert_noop!(
+            Institution::add_registrar(RuntimeOrigin::root(), 10),
+            Error::<Test>::AlreadyRegistrar
+        );
+
+        assert_ok!(Institution::remove_registrar(RuntimeOrigin::root(), 10));
+        assert_eq!(Registrars::<Test>::get().into_inner(), vec![11]);
+        assert_noop!(
+            Institution::remove_registrar(RuntimeOrigin::root(), 10),
+            Error::<Test>::NotRegistrar
+        );
+    });
+}
+
+#[test]
+fn institution_is_certified_after_threshold_approvals() {
+    new_test_ext().execute_with(|| {
+        add_registrars(&[10, 11, 12]);
+        create_institution(1, b"INST_001");
+        assert_eq!(AuditCases::<Test>::get(bounded(b"INST_001")).unwrap().status, AuditStatus::Pending);
+
+        assert_noop!(
+            Institution::approve_audit(RuntimeOrigin::signed(1), b"INST_001".to_vec(), None),
+            Error::<Test>::NotRegistrar
+        );
+
+        assert_ok!(Institution::approve_audit(
+            RuntimeOrigin::signed(10),
+            b"INST_001".to_vec(),
+            Some(b"license checked".to_vec()),
+        ));
+        assert_noop!(
+            Institution::approve_audit(RuntimeOrigin::signed(10), b"INST_001".to_vec(), None),
+            Error::<Test>::AlreadyVoted
+        );
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().status, InstitutionStatus::NotCertified);
+
+        assert_ok!(Institution::approve_audit(RuntimeOrigin::signed(11), b"INST_001".to_vec(), None));
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().status, InstitutionStatus::Certified);
+        assert_eq!(Certifications::<Test>::get(bounded(b"INST_001")).unwrap().certifier, Some(11));
+        assert!(AuditCases::<Test>::get(bounded(b"INST_001")).is_none());
+
+        let history = AuditHistory::<Test>::get(bounded(b"INST_001"));
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].status, AuditStatus::Approved);
+        assert_eq!(history[0].votes.len(), 2);
+    });
+}
+
+#[test]
+fn rejected_case_is_kept_in_history_and_can_be_reopened() {
+    new_test_ext().execute_with(|| {
+        add_registrars(&[10, 11, 12, 13]);
+        create_institution(1, b"INST_001");
+
+        // 单个登记员驳回不能结案
+        assert_ok!(Institution::reject_audit(
+            RuntimeOrigin::signed(10),
+            b"INST_001".to_vec(),
+            Some(b"license unreadable".to_vec()),
+        ));
+        System::assert_last_event(Event::AuditRejected(bounded(b"INST_001"), 10).into());
+        assert_eq!(AuditCases::<Test>::get(bounded(b"INST_001")).unwrap().status, AuditStatus::Pending);
+        assert_noop!(
+            Institution::reject_audit(RuntimeOrigin::signed(10), b"INST_001".to_vec(), None),
+            Error::<Test>::AlreadyVoted
+        );
+
+        // 通过票与驳回票分别计数
+        assert_ok!(Institution::approve_audit(RuntimeOrigin::signed(11), b"INST_001".to_vec(), None));
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().status, InstitutionStatus::NotCertified);
+
+        assert_ok!(Institution::reject_audit(RuntimeOrigin::signed(12), b"INST_001".to_vec(), None));
+        assert!(AuditCases::<Test>::get(bounded(b"INST_001")).is_none());
+        let history = AuditHistory::<Test>::get(bounded(b"INST_001"));
+        assert_eq!(history[0].status, AuditStatus::Rejected);
+        assert_eq!(history[0].votes.len(), 3);
+        assert_eq!(history[0].votes[0].comment.clone().unwrap().into_inner(), b"license unreadable".to_vec());
+
+        assert_noop!(
+            Institution::approve_audit(RuntimeOrigin::signed(13), b"INST_001".to_vec(), None),
+            Error::<Test>::AuditCaseNotFound
+        );
+        assert_noop!(
+            Institution::request_audit(RuntimeOrigin::signed(2), b"INST_001".to_vec()),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert_ok!(Institution::request_audit(RuntimeOrigin::signed(1), b"INST_001".to_vec()));
+        assert_noop!(
+            Institution::request_audit(RuntimeOrigin::signed(1), b"INST_001".to_vec()),
+            Error::<Test>::AuditCaseAlreadyOpen
+        );
+        assert_eq!(AuditCases::<Test>::get(bounded(b"INST_001")).unwrap().status, AuditStatus::Pending);
+    });
+}
+
+#[test]
+fn owner_and_admin_manage_staff_roles() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_noop!(
+            Institution::grant_role(RuntimeOrigin::signed(2), b"INST_001".to_vec(), 3, InstitutionRole::ProductManager),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            Institution::grant_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2, InstitutionRole::Owner),
+            Error::<Test>::CannotAssignOwner
+        );
+
+        assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2, InstitutionRole::Admin));
+        assert_ok!(Institution::grant_role(
+            RuntimeOrigin::signed(2),
+            b"INST_001".to_vec(),
+            3,
+            InstitutionRole::ProductManager,
+        ));
+        assert_noop!(
+            Institution::grant_role(RuntimeOrigin::signed(2), b"INST_001".to_vec(), 4, InstitutionRole::Admin),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert!(Institution::has_role(b"INST_001", &1, InstitutionRole::Finance));
+        assert!(Institution::has_role(b"INST_001", &2, InstitutionRole::OrderClerk));
+        assert!(!Institution::has_role(b"INST_001", &2, InstitutionRole::Owner));
+        assert!(Institution::has_role(b"INST_001", &3, InstitutionRole::ProductManager));
+        assert!(!Institution::has_role(b"INST_001", &3, InstitutionRole::Finance));
+
+        assert_ok!(Institution::revoke_role(
+            RuntimeOrigin::signed(3),
+            b"INST_001".to_vec(),
+            3,
+            InstitutionRole::ProductManager,
+        ));
+        assert!(!Institution::has_role(b"INST_001", &3, InstitutionRole::ProductManager));
+    });
+}
+
+#[test]
+fn inspect_reports_existence_and_certification() {
+    new_test_ext().execute_with(|| {
+        assert!(!Institution::exists(b"INST_001"));
+        create_institution(1, b"INST_001");
+        assert!(Institution::exists(b"INST_001"));
+        assert!(!Institution::is_certified(b"INST_001"));
+
+        assert_ok!(Institution::certify_institution(RuntimeOrigin::root(), b"INST_001".to_vec()));
+        assert!(Institution::is_certified(b"INST_001"));
+    });
+}
+
+#[test]
+fn two_step_owner_transfer() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_noop!(
+            Institution::propose_owner_transfer(RuntimeOrigin::signed(2), b"INST_001".to_vec(), 3),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Institution::propose_owner_transfer(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2));
+        assert_eq!(PendingOwnerTransfers::<Test>::get(bounded(b"INST_001")).unwrap().expires_at, 11);
+
+        assert_noop!(
+            Institution::accept_owner_transfer(RuntimeOrigin::signed(3), b"INST_001".to_vec()),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Institution::accept_owner_transfer(RuntimeOrigin::signed(2), b"INST_001".to_vec()));
+
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().creator, 2);
+        assert!(PendingOwnerTransfers::<Test>::get(bounded(b"INST_001")).is_none());
+        assert!(Institution::has_role(b"INST_001", &2, InstitutionRole::Owner));
+        assert!(!Institution::has_role(b"INST_001", &1, InstitutionRole::ProductManager));
+        System::assert_last_event(Event::OwnerTransferred(bounded(b"INST_001"), 1, 2).into());
+    });
+}
+
+#[test]
+fn owner_transfer_expires_and_can_be_cancelled() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_ok!(Institution::propose_owner_transfer(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2));
+        System::set_block_number(12);
+        assert_noop!(
+            Institution::accept_owner_transfer(RuntimeOrigin::signed(2), b"INST_001".to_vec()),
+            Error::<Test>::OwnerTransferExpired
+        );
+
+        assert_ok!(Institution::cancel_owner_transfer(RuntimeOrigin::signed(1), b"INST_001".to_vec()));
+        assert_noop!(
+            Institution::accept_owner_transfer(RuntimeOrigin::signed(2), b"INST_001".to_vec()),
+            Error::<Test>::NoPendingOwnerTransfer
+        );
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().creator, 1);
+    });
+}
+
+fn expected_deposit(id: &[u8]) -> u64 {
+    100 + Institutions::<Test>::get(bounded(id)).unwrap().encoded_size() as u64
+}
+
+#[test]
+fn genesis_seeds_institutions_with_deposits_and_indexes() {
+    let institution = |id: &[u8], creator: u64, certified: bool| {
+        (
+            id.to_vec(),
+            creator,
+            b"Shop".to_vec(),
+            b"Shop Co., Ltd.".to_vec(),
+            b"https://example.com/license.png".to_vec(),
+            b"Zhang San".to_vec(),
+            b"Retail".to_vec(),
+            certified,
+        )
+    };
+    let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 10_000), (2, 10_000)], ..Default::default() }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+    crate::GenesisConfig::<Test> {
+        registrars: vec![3],
+        institutions: vec![institution(b"INST_001", 1, true), institution(b"INST_002", 2, false)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    sp_io::TestExternalities::from(storage).execute_with(|| {
+        assert_eq!(Registrars::<Test>::get().into_inner(), vec![3]);
+
+        // 已认证机构写入认证记录，未认证机构立案审核
+        assert!(Institution::is_certified(b"INST_001"));
+        assert!(Certifications::<Test>::contains_key(bounded(b"INST_001")));
+        assert!(!Institution::is_certified(b"INST_002"));
+        assert!(AuditCases::<Test>::contains_key(bounded(b"INST_002")));
+
+        assert_eq!(Balances::reserved_balance(1), expected_deposit(b"INST_001"));
+        assert_eq!(Balances::reserved_balance(2), expected_deposit(b"INST_002"));
+        assert!(InstitutionsByStatus::<Test>::contains_key(0, bounded(b"INST_001")));
+        assert!(InstitutionsByCreator::<Test>::contains_key(2, bounded(b"INST_002")));
+    });
+}
+
+#[test]
+fn deposit_is_held_adjusted_and_released() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        let deposit = expected_deposit(b"INST_001");
+        assert_eq!(Balances::reserved_balance(1), deposit);
+        assert_eq!(InstitutionDeposits::<Test>::get(bounded(b"INST_001")), Some((1, deposit)));
+
+        assert_ok!(Institution::update_institution_info(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            None,
+            None,
+            None,
+            None,
+            Some(b"Retail and wholesale of groceries".to_vec()),
+            None,
+        ));
+        let grown = expected_deposit(b"INST_001");
+        assert!(grown > deposit);
+        assert_eq!(Balances::reserved_balance(1), grown);
+
+        assert_ok!(Institution::delete_institution(RuntimeOrigin::signed(1), b"INST_001".to_vec()));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert!(InstitutionDeposits::<Test>::get(bounded(b"INST_001")).is_none());
+    });
+}
+
+#[test]
+fn create_fails_without_funds_for_deposit() {
+    new_test_ext().execute_with(|| {
+        assert!(Institution::create_institution(
+            RuntimeOrigin::signed(9),
+            b"INST_001".to_vec(),
+            b"Shop".to_vec(),
+            b"Shop Co., Ltd.".to_vec(),
+            b"https://example.com/license.png".to_vec(),
+            b"Zhang San".to_vec(),
+            b"Retail".to_vec(),
+            None,
+        )
+        .is_err());
+        assert!(Institutions::<Test>::get(bounded(b"INST_001")).is_none());
+    });
+}
+
+#[test]
+fn deletion_is_refused_while_dependents_block_it() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        BlockedInstitutions::set(vec![b"INST_001".to_vec()]);
+
+        assert_noop!(
+            Institution::delete_institution(RuntimeOrigin::signed(1), b"INST_001".to_vec()),
+            DispatchError::Other("open orders")
+        );
+        assert!(Institutions::<Test>::contains_key(bounded(b"INST_001")));
+        assert!(RemovedInstitutions::get().is_empty());
+    });
+}
+
+#[test]
+fn deletion_cleans_up_dependents_in_batches() {
+    new_test_ext().execute_with(|| {
+        for id in [&b"INST_001"[..], b"INST_002"] {
+            create_institution(1, id);
+            for member in [2, 3, 4] {
+                assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), id.to_vec(), member, InstitutionRole::Admin));
+            }
+        }
+
+        // 每批最多清理 2 条，剩余成员及依赖模块数据延后清理
+        assert_ok!(Institution::delete_institution(RuntimeOrigin::signed(1), b"INST_001".to_vec()));
+        assert!(PendingCleanups::<Test>::contains_key(bounded(b"INST_001")));
+        assert_eq!(InstitutionMembers::<Test>::iter_prefix(bounded(b"INST_001")).count(), 1);
+        assert!(RemovedInstitutions::get().is_empty());
+
+        // 任何人都可以继续清理
+        assert_ok!(Institution::clean_up_institution(RuntimeOrigin::signed(5), b"INST_001".to_vec(), 10));
+        System::assert_last_event(Event::InstitutionCleanedUp(bounded(b"INST_001")).into());
+        assert_eq!(InstitutionMembers::<Test>::iter_prefix(bounded(b"INST_001")).count(), 0);
+        assert_eq!(RemovedInstitutions::get(), vec![b"INST_001".to_vec()]);
+        assert_noop!(
+            Institution::clean_up_institution(RuntimeOrigin::signed(5), b"INST_001".to_vec(), 10),
+            Error::<Test>::NoPendingCleanup
+        );
+
+        // 区块空闲时自动清理
+        assert_ok!(Institution::delete_institution(RuntimeOrigin::signed(1), b"INST_002".to_vec()));
+        Institution::on_idle(1, Weight::MAX);
+        assert!(!PendingCleanups::<Test>::contains_key(bounded(b"INST_002")));
+        assert_eq!(InstitutionMembers::<Test>::iter_prefix(bounded(b"INST_002")).count(), 0);
+        assert_eq!(RemovedInstitutions::get(), vec![b"INST_001".to_vec(), b"INST_002".to_vec()]);
+    });
+}
+
+#[test]
+fn deletion_cascades_and_tombstones_the_id() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_ok!(Institution::delete_institution(RuntimeOrigin::signed(1), b"INST_001".to_vec()));
+        assert_eq!(RemovedInstitutions::get(), vec![b"INST_001".to_vec()]);
+        assert_eq!(InstitutionTombstones::<Test>::get(bounded(b"INST_001")), Some((1, 1)));
+
+        // 机构ID不可被任何人重新注册
+        for who in [1, 2] {
+            assert_noop!(
+                Institution::create_institution(
+                    RuntimeOrigin::signed(who),
+                    b"INST_001".to_vec(),
+                    b"Shop".to_vec(),
+                    b"Shop Co., Ltd.".to_vec(),
+                    b"https://example.com/license.png".to_vec(),
+                    b"Zhang San".to_vec(),
+                    b"Retail".to_vec(),
+                    None,
+                ),
+                Error::<Test>::InstitutionIdRetired
+            );
+        }
+    });
+}
+
+fn revenue_split(shares: &[(u64, u32)], platform_cut: Option<u32>) -> RevenueSplit<Test> {
+    RevenueSplit {
+        shares: BoundedVec::try_from(
+            shares
+                .iter()
+                .map(|&(beneficiary, percent)| ProfitShare { beneficiary, share: Perbill::from_percent(percent) })
+                .collect::<Vec<_>>(),
+        )
+        .unwrap(),
+        platform_cut: platform_cut.map(Perbill::from_percent),
+        effective_from: 1,
+        effective_until: Some(10),
+    }
+}
+
+fn set_profit_contract(who: u64, id: &[u8], split: RevenueSplit<Test>) -> sp_runtime::DispatchResult {
+    Institution::update_institution_info(RuntimeOrigin::signed(who), id.to_vec(), None, None, None, None, None, Some(split))
+}
+
+#[test]
+fn profit_shares_must_sum_to_one_hundred_percent() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_noop!(set_profit_contract(1, b"INST_001", revenue_split(&[(2, 60), (3, 30)], None)), Error::<Test>::InvalidProfitShares);
+        assert_noop!(set_profit_contract(1, b"INST_001", revenue_split(&[], None)), Error::<Test>::InvalidProfitShares);
+
+        let mut split = revenue_split(&[(2, 100)], None);
+        split.effective_until = Some(1);
+        assert_noop!(set_profit_contract(1, b"INST_001", split), Error::<Test>::InvalidEffectivePeriod);
+
+        assert_ok!(set_profit_contract(1, b"INST_001", revenue_split(&[(2, 70), (3, 30)], Some(10))));
+        assert!(matches!(
+            Institutions::<Test>::get(bounded(b"INST_001")).unwrap().profit_contract,
+            Some(ProfitContract::Split(_))
+        ));
+    });
+}
+
+/// 从指定账户转账支付分润款项
+fn pay_from(payer: u64) -> impl FnMut(&u64, u64) -> sp_runtime::DispatchResult {
+    move |to, amount| Balances::transfer(&payer, to, amount, Preservation::Preserve).map(|_| ())
+}
+
+#[test]
+fn distribute_pays_platform_cut_and_beneficiaries() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(set_profit_contract(1, b"INST_001", revenue_split(&[(2, 70), (3, 30)], Some(10))));
+
+        let owner_balance = Balances::free_balance(1);
+
+        // 款项由付款账户支付，机构所有者不承担
+        assert_ok!(Institution::distribute(&bounded(b"INST_001"), 1_000, &mut pay_from(4)));
+        assert_eq!(Balances::free_balance(4), 10_000 - 1_000);
+        assert_eq!(Balances::free_balance(99), 100);
+        assert_eq!(Balances::free_balance(2), 10_000 + 630);
+        assert_eq!(Balances::free_balance(3), 10_000 + 270);
+        assert_eq!(Balances::free_balance(1), owner_balance);
+        System::assert_last_event(Event::ProfitDistributed(bounded(b"INST_001"), 1_000).into());
+
+        // 合约失效后全额支付给机构所有者
+        System::set_block_number(10);
+        assert_ok!(Institution::distribute(&bounded(b"INST_001"), 1_000, &mut pay_from(4)));
+        assert_eq!(Balances::free_balance(2), 10_000 + 630);
+        assert_eq!(Balances::free_balance(1), owner_balance + 1_000);
+    });
+}
+
+#[test]
+fn migration_wraps_free_form_contracts_as_legacy() {
+    use crate::migrations::{v1::{MigrateV0ToV1, OldInstitutionInfo}, v2::MigrateV1ToV2, v3::MigrateV2ToV3};
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let old = OldInstitutionInfo::<Test> {
+            institution_name: BoundedVec::try_from(b"Shop".to_vec()).unwrap(),
+            status: InstitutionStatus::NotCertified,
+            institution_full_name: BoundedVec::try_from(b"Shop Co., Ltd.".to_vec()).unwrap(),
+            license_image_url: BoundedVec::try_from(b"https://example.com/license.png".to_vec()).unwrap(),
+            responsible_person: BoundedVec::try_from(b"Zhang San".to_vec()).unwrap(),
+            business_scope: BoundedVec::try_from(b"Retail".to_vec()).unwrap(),
+            profit_contract: Some(BoundedVec::try_from(b"70/30".to_vec()).unwrap()),
+            created_date: 1,
+            creator: 1,
+        };
+        frame_support::storage::unhashed::put(&Institutions::<Test>::hashed_key_for(bounded(b"INST_001")), &old);
+        StorageVersion::new(0).put::<Institution>();
+
+        MigrateV0ToV1::<Test>::on_runtime_upgrade();
+        assert_eq!(Institution::on_chain_storage_version(), StorageVersion::new(1));
+        MigrateV1ToV2::<Test>::on_runtime_upgrade();
+        assert_eq!(Institution::on_chain_storage_version(), StorageVersion::new(2));
+        MigrateV2ToV3::<Test>::on_runtime_upgrade();
+
+        let institution = Institutions::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_eq!(institution.profit_contract, Some(ProfitContract::Legacy(BoundedVec::try_from(b"70/30".to_vec()).unwrap())));
+        assert_eq!(institution.creator, 1);
+        assert_eq!(institution.parent, None);
+        assert!(InstitutionsByStatus::<Test>::contains_key(1, bounded(b"INST_001")));
+        assert!(InstitutionsByCreator::<Test>::contains_key(1, bounded(b"INST_001")));
+        assert_eq!(Institution::on_chain_storage_version(), StorageVersion::new(3));
+    });
+}
+
+fn set_license(who: u64, id: &[u8], expires_at: u64) -> sp_runtime::DispatchResult {
+    Institution::set_license(
+        RuntimeOrigin::signed(who),
+        id.to_vec(),
+        H256::repeat_byte(1),
+        b"SAMR-310000".to_vec(),
+        b"91310000MA1FL0000X".to_vec(),
+        expires_at,
+    )
+}
+
+#[test]
+fn license_is_recorded_by_owner_only() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+
+        assert_noop!(set_license(2, b"INST_001", 20), Error::<Test>::NotAuthorized);
+        assert_noop!(set_license(1, b"INST_001", 1), Error::<Test>::LicenseExpiryInPast);
+
+        assert_ok!(set_license(1, b"INST_001", 20));
+        let license = Licenses::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_eq!(license.content_hash, H256::repeat_byte(1));
+        assert_eq!(license.expires_at, 20);
+        System::assert_last_event(Event::LicenseRecorded(bounded(b"INST_001"), H256::repeat_byte(1), 20).into());
+
+        // 更新执照后旧的到期调度被移除
+        assert_ok!(set_license(1, b"INST_001", 30));
+        assert!(LicenseExpiries::<Test>::get(20).is_empty());
+        assert_eq!(LicenseExpiries::<Test>::get(30).into_inner(), vec![bounded(b"INST_001")]);
+    });
+}
+
+#[test]
+fn expired_license_lapses_certification_after_warning() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(Institution::certify_institution(RuntimeOrigin::root(), b"INST_001".to_vec()));
+        assert_ok!(set_license(1, b"INST_001", 20));
+
+        System::set_block_number(15);
+        Institution::on_initialize(15);
+        System::assert_last_event(Event::LicenseExpiring(bounded(b"INST_001"), 20).into());
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().status, InstitutionStatus::Certified);
+
+        System::set_block_number(20);
+        Institution::on_initialize(20);
+        System::assert_has_event(Event::LicenseExpired(bounded(b"INST_001")).into());
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().status, InstitutionStatus::NotCertified);
+        assert_eq!(Certifications::<Test>::get(bounded(b"INST_001")).unwrap().revoked_at, Some(20));
+    });
+}
+
+#[test]
+fn license_expiries_per_block_are_bounded() {
+    new_test_ext().execute_with(|| {
+        for id in [&b"INST_001"[..], b"INST_002", b"INST_003"] {
+            create_institution(1, id);
+        }
+
+        assert_ok!(set_license(1, b"INST_001", 20));
+        assert_ok!(set_license(1, b"INST_002", 20));
+        assert_noop!(set_license(1, b"INST_003", 20), Error::<Test>::TooManyLicenseExpiries);
+    });
+}
+
+fn set_parent(who: u64, id: &[u8], parent: Option<&[u8]>, inherit: bool) -> sp_runtime::DispatchResult {
+    Institution::set_parent(RuntimeOrigin::signed(who), id.to_vec(), parent.map(|p| p.to_vec()), inherit, inherit)
+}
+
+#[test]
+fn branches_attach_to_headquarters_with_bounded_depth() {
+    new_test_ext().execute_with(|| {
+        for id in [&b"HQ"[..], b"BRANCH_A", b"BRANCH_B", b"OUTLET"] {
+            create_institution(1, id);
+        }
+        create_institution(2, b"OTHER");
+
+        // 挂靠需要同时是分支所有者和总部管理员
+        assert_noop!(set_parent(2, b"OTHER", Some(b"HQ"), true), Error::<Test>::NotAuthorized);
+
+        assert_ok!(set_parent(1, b"BRANCH_A", Some(b"HQ"), true));
+        assert_ok!(set_parent(1, b"BRANCH_B", Some(b"HQ"), true));
+        assert_ok!(set_parent(1, b"OUTLET", Some(b"BRANCH_A"), true));
+        assert_eq!(InstitutionBranches::<Test>::get(bounded(b"HQ")).len(), 2);
+        assert_eq!(
+            Institution::descendants(&bounded(b"HQ")),
+            vec![bounded(b"BRANCH_A"), bounded(b"BRANCH_B"), bounded(b"OUTLET")]
+        );
+        assert_eq!(Institution::ancestors(&bounded(b"OUTLET")), vec![bounded(b"BRANCH_A"), bounded(b"HQ")]);
+
+        // 环路与深度限制（mock 中最大深度为 2）
+        assert_noop!(set_parent(1, b"HQ", Some(b"OUTLET"), true), Error::<Test>::CyclicHierarchy);
+        assert_noop!(set_parent(1, b"BRANCH_B", Some(b"OUTLET"), true), Error::<Test>::HierarchyTooDeep);
+
+        // 有分支的机构不能删除
+        assert_noop!(
+            Institution::delete_institution(RuntimeOrigin::signed(1), b"BRANCH_A".to_vec()),
+            Error::<Test>::InstitutionHasBranches
+        );
+
+        assert_ok!(set_parent(1, b"OUTLET", None, false));
+        assert!(Institution::descendants(&bounded(b"BRANCH_A")).is_empty());
+        assert_noop!(set_parent(1, b"OUTLET", None, false), Error::<Test>::NotABranch);
+    });
+}
+
+#[test]
+fn branches_inherit_certification_profit_contract_and_admins() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"HQ");
+        create_institution(1, b"BRANCH_A");
+        create_institution(1, b"BRANCH_B");
+        assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), b"HQ".to_vec(), 2, InstitutionRole::Admin));
+        assert_ok!(Institution::certify_institution(RuntimeOrigin::root(), b"HQ".to_vec()));
+        assert_ok!(set_profit_contract(1, b"HQ", revenue_split(&[(3, 100)], None)));
+
+        assert_ok!(set_parent(1, b"BRANCH_A", Some(b"HQ"), true));
+        assert_ok!(set_parent(1, b"BRANCH_B", Some(b"HQ"), false));
+
+        assert!(<Institution as InstitutionInspect<u64>>::is_certified(b"BRANCH_A"));
+        assert!(!<Institution as InstitutionInspect<u64>>::is_certified(b"BRANCH_B"));
+        assert!(Institution::effective_profit_contract(&bounded(b"BRANCH_A")).is_some());
+        assert!(Institution::effective_profit_contract(&bounded(b"BRANCH_B")).is_none());
+
+        // 总部管理员可管理分支的商品与订单，但不是分支所有者
+        for branch in [&b"BRANCH_A"[..], b"BRANCH_B"] {
+            assert!(<Institution as InstitutionInspect<u64>>::has_role(branch, &2, InstitutionRole::ProductManager));
+            assert!(<Institution as InstitutionInspect<u64>>::has_role(branch, &2, InstitutionRole::OrderClerk));
+            assert!(!<Institution as InstitutionInspect<u64>>::has_role(branch, &2, InstitutionRole::Owner));
+        }
+    });
+}
+
+fn suspend(id: &[u8], until: u64) -> sp_runtime::DispatchResult {
+    Institution::suspend_institution(RuntimeOrigin::root(), id.to_vec(), until, b"fraud report".to_vec())
+}
+
+#[test]
+fn suspension_is_set_by_privileged_origin_and_lifts_automatically() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(Institution::certify_institution(RuntimeOrigin::root(), b"INST_001".to_vec()));
+
+        assert_noop!(
+            Institution::suspend_institution(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 10, Vec::new()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(suspend(b"INST_001", 1), Error::<Test>::SuspensionEndInPast);
+
+        assert_ok!(suspend(b"INST_001", 10));
+        System::assert_last_event(Event::InstitutionSuspended(bounded(b"INST_001"), 10).into());
+        assert!(<Institution as InstitutionInspect<u64>>::is_suspended(b"INST_001"));
+        assert!(!<Institution as InstitutionInspect<u64>>::is_certified(b"INST_001"));
+
+        // 暂停期间商户不能自行变更状态或删除机构
+        assert_noop!(
+            Institution::update_institution_status(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2),
+            Error::<Test>::InstitutionSuspended
+        );
+        assert_noop!(
+            Institution::delete_institution(RuntimeOrigin::signed(1), b"INST_001".to_vec()),
+            Error::<Test>::InstitutionSuspended
+        );
+
+        System::set_block_number(10);
+        Institution::on_initialize(10);
+        System::assert_has_event(Event::SuspensionLifted(bounded(b"INST_001")).into());
+        assert!(!<Institution as InstitutionInspect<u64>>::is_suspended(b"INST_001"));
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().status, InstitutionStatus::Certified);
+    });
+}
+
+#[test]
+fn merchant_appeals_and_operations_lift_early() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_noop!(
+            Institution::appeal_suspension(RuntimeOrigin::signed(1), b"INST_001".to_vec(), H256::repeat_byte(7)),
+            Error::<Test>::NotSuspended
+        );
+
+        assert_ok!(suspend(b"INST_001", 10));
+        assert_noop!(
+            Institution::appeal_suspension(RuntimeOrigin::signed(2), b"INST_001".to_vec(), H256::repeat_byte(7)),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Institution::appeal_suspension(RuntimeOrigin::signed(1), b"INST_001".to_vec(), H256::repeat_byte(7)));
+        assert_eq!(SuspensionAppeals::<Test>::get(bounded(b"INST_001")).unwrap().evidence_hash, H256::repeat_byte(7));
+
+        assert_ok!(Institution::lift_suspension(RuntimeOrigin::root(), b"INST_001".to_vec()));
+        assert_eq!(Institutions::<Test>::get(bounded(b"INST_001")).unwrap().status, InstitutionStatus::NotCertified);
+        assert!(SuspensionAppeals::<Test>::get(bounded(b"INST_001")).is_none());
+        assert_noop!(
+            Institution::lift_suspension(RuntimeOrigin::root(), b"INST_001".to_vec()),
+            Error::<Test>::NotSuspended
+        );
+    });
+}
+
+fn rename(who: u64, id: &[u8], name: &[u8]) -> sp_runtime::DispatchResult {
+    Institution::update_institution_info(RuntimeOrigin::signed(who), id.to_vec(), Some(name.to_vec()), None, None, None, None, None)
+}
+
+#[test]
+fn updates_are_logged_as_revisions() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        let original = Institutions::<Test>::get(bounded(b"INST_001")).unwrap();
+
+        // 值未变化的更新不产生修订
+        assert_ok!(rename(1, b"INST_001", b"Shop"));
+        assert_eq!(InstitutionRevisionCount::<Test>::get(bounded(b"INST_001")), 0);
+
+        System::set_block_number(3);
+        assert_ok!(Institution::update_institution_info(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            Some(b"Shop v1".to_vec()),
+            None,
+            None,
+            None,
+            Some(b"Retail".to_vec()),
+            None,
+        ));
+        System::assert_has_event(Event::InstitutionRevised(bounded(b"INST_001"), 1).into());
+
+        let revisions = InstitutionRevisions::<Test>::get(bounded(b"INST_001"));
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].revision, 1);
+        assert_eq!(revisions[0].previous_hash, BlakeTwo256::hash_of(&original));
+        assert_eq!(revisions[0].block, 3);
+        assert_eq!(revisions[0].signer, Some(1));
+        assert_eq!(
+            revisions[0].changes.to_vec(),
+            vec![InstitutionFieldChange::InstitutionName(BoundedVec::try_from(b"Shop".to_vec()).unwrap())]
+        );
+    });
+}
+
+#[test]
+fn record_is_reconstructed_within_retention() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        let original = Institutions::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_ok!(rename(1, b"INST_001", b"Shop v1"));
+        let first = Institutions::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_ok!(set_profit_contract(1, b"INST_001", revenue_split(&[(2, 100)], None)));
+
+        assert_eq!(Institution::institution_at_revision(&bounded(b"INST_001"), 0), Some(original));
+        assert_eq!(Institution::institution_at_revision(&bounded(b"INST_001"), 1), Some(first.clone()));
+        assert_eq!(
+            Institution::institution_at_revision(&bounded(b"INST_001"), 2),
+            Institutions::<Test>::get(bounded(b"INST_001"))
+        );
+        assert_eq!(Institution::institution_at_revision(&bounded(b"INST_001"), 3), None);
+
+        // 超出保留上限后最早的修订被丢弃
+        assert_ok!(rename(1, b"INST_001", b"Shop v3"));
+        let revisions = InstitutionRevisions::<Test>::get(bounded(b"INST_001"));
+        assert_eq!(revisions.iter().map(|entry| entry.revision).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(Institution::institution_at_revision(&bounded(b"INST_001"), 0), None);
+        assert_eq!(Institution::institution_at_revision(&bounded(b"INST_001"), 1), Some(first));
+    });
+}
+
+#[test]
+fn status_and_owner_changes_are_logged_as_revisions() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        let original = Institutions::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_ok!(Institution::certify_institution(RuntimeOrigin::root(), b"INST_001".to_vec()));
+        let certified = Institutions::<Test>::get(bounded(b"INST_001")).unwrap();
+        assert_ok!(Institution::propose_owner_transfer(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2));
+        assert_ok!(Institution::accept_owner_transfer(RuntimeOrigin::signed(2), b"INST_001".to_vec()));
+
+        let revisions = InstitutionRevisions::<Test>::get(bounded(b"INST_001"));
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].signer, None);
+        assert_eq!(revisions[0].changes.to_vec(), vec![InstitutionFieldChange::Status(InstitutionStatus::NotCertified)]);
+        assert_eq!(revisions[1].signer, Some(2));
+        assert_eq!(revisions[1].changes.to_vec(), vec![InstitutionFieldChange::Creator(1)]);
+
+        // 回滚后的记录与修订中保存的哈希一致
+        assert_eq!(Institution::institution_at_revision(&bounded(b"INST_001"), 0), Some(original.clone()));
+        assert_eq!(Institution::institution_at_revision(&bounded(b"INST_001"), 1), Some(certified.clone()));
+        assert_eq!(revisions[0].previous_hash, BlakeTwo256::hash_of(&original));
+        assert_eq!(revisions[1].previous_hash, BlakeTwo256::hash_of(&certified));
+    });
+}
+
+fn attestation_claim(id: &[u8], claim_type: u32, expires_at: u64) -> Vec<u8> {
+    AttestationClaim::<Test> {
+        institution_id: BoundedVec::try_from(id.to_vec()).unwrap(),
+        claim_type,
+        expires_at,
+        evidence_hash: H256::repeat_byte(9),
+    }
+    .encode()
+}
+
+fn sign_claim(pair: &sr25519::Pair, claim: &[u8]) -> AttestorSignature {
+    AttestorSignature::Sr25519(pair.sign(&[crate::ATTESTATION_CONTEXT, claim].concat()))
+}
+
+#[test]
+fn attestors_are_managed_by_privileged_origin() {
+    new_test_ext().execute_with(|| {
+        let attestor = AttestorKey::Sr25519(sr25519::Pair::from_seed(&[1; 32]).public());
+        assert_noop!(
+            Institution::add_attestor(RuntimeOrigin::signed(1), attestor.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Institution::add_attestor(RuntimeOrigin::root(), attestor.clone()));
+        assert_noop!(
+            Institution::add_attestor(RuntimeOrigin::root(), attestor.clone()),
+            Error::<Test>::AlreadyAttestor
+        );
+        assert_ok!(Institution::remove_attestor(RuntimeOrigin::root(), attestor.clone()));
+        assert_noop!(
+            Institution::remove_attestor(RuntimeOrigin::root(), attestor),
+            Error::<Test>::NotAttestor
+        );
+    });
+}
+
+#[test]
+fn signed_attestation_is_verified_and_stored() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        let pair = sr25519::Pair::from_seed(&[1; 32]);
+        let attestor = AttestorKey::Sr25519(pair.public());
+        let claim = attestation_claim(b"INST_001", 1, 100);
+
+        assert_noop!(
+            Institution::submit_attestation(RuntimeOrigin::signed(2), claim.clone(), attestor.clone(), sign_claim(&pair, &claim)),
+            Error::<Test>::UntrustedAttestor
+        );
+        assert_ok!(Institution::add_attestor(RuntimeOrigin::root(), attestor.clone()));
+
+        // 签名与声明不匹配
+        let other = attestation_claim(b"INST_001", 1, 200);
+        assert_noop!(
+            Institution::submit_attestation(RuntimeOrigin::signed(2), other, attestor.clone(), sign_claim(&pair, &claim)),
+            Error::<Test>::InvalidAttestationSignature
+        );
+
+        assert_ok!(Institution::submit_attestation(RuntimeOrigin::signed(2), claim.clone(), attestor.clone(), sign_claim(&pair, &claim)));
+        System::assert_last_event(Event::AttestationRecorded(bounded(b"INST_001"), 1, attestor.clone(), 100).into());
+        let attestation = Attestations::<Test>::get(bounded(b"INST_001"), 1).unwrap();
+        assert_eq!(attestation.evidence_hash, H256::repeat_byte(9));
+        assert_eq!(attestation.submitter, 2);
+        assert!(<Institution as InstitutionInspect<u64>>::has_attestation(b"INST_001", 1));
+        assert!(!<Institution as InstitutionInspect<u64>>::has_attestation(b"INST_001", 2));
+
+        // 重放同一声明不会覆盖
+        assert_noop!(
+            Institution::submit_attestation(RuntimeOrigin::signed(2), claim.clone(), attestor.clone(), sign_claim(&pair, &claim)),
+            Error::<Test>::AttestationOutdated
+        );
+
+        // 过期或核验方被移除后声明失效
+        System::set_block_number(100);
+        assert!(!<Institution as InstitutionInspect<u64>>::has_attestation(b"INST_001", 1));
+        assert_noop!(
+            Institution::submit_attestation(RuntimeOrigin::signed(2), claim.clone(), attestor.clone(), sign_claim(&pair, &claim)),
+            Error::<Test>::AttestationExpired
+        );
+        let renewed = attestation_claim(b"INST_001", 1, 300);
+        assert_ok!(Institution::submit_attestation(RuntimeOrigin::signed(2), renewed.clone(), attestor.clone(), sign_claim(&pair, &renewed)));
+        assert!(<Institution as InstitutionInspect<u64>>::has_attestation(b"INST_001", 1));
+        assert_ok!(Institution::remove_attestor(RuntimeOrigin::root(), attestor));
+        assert!(!<Institution as InstitutionInspect<u64>>::has_attestation(b"INST_001", 1));
+    });
+}
+
+#[test]
+fn ed25519_attestor_signs_claims() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        let pair = ed25519::Pair::from_seed(&[2; 32]);
+        let attestor = AttestorKey::Ed25519(pair.public());
+        assert_ok!(Institution::add_attestor(RuntimeOrigin::root(), attestor.clone()));
+
+        let claim = attestation_claim(b"INST_001", 7, 50);
+        let signature = AttestorSignature::Ed25519(pair.sign(&[crate::ATTESTATION_CONTEXT, &claim[..]].concat()));
+        assert_ok!(Institution::submit_attestation(RuntimeOrigin::signed(1), claim, attestor, signature));
+        assert!(<Institution as InstitutionInspect<u64>>::has_attestation(b"INST_001", 7));
+
+        // 未注册机构及无法解码的声明被拒绝
+        let unknown = attestation_claim(b"INST_404", 7, 50);
+        let signature = AttestorSignature::Ed25519(pair.sign(&[crate::ATTESTATION_CONTEXT, &unknown[..]].concat()));
+        assert_noop!(
+            Institution::submit_attestation(RuntimeOrigin::signed(1), unknown, AttestorKey::Ed25519(pair.public()), signature),
+            Error::<Test>::InstitutionNotFound
+        );
+        let garbage = b"not a claim".to_vec();
+        let signature = AttestorSignature::Ed25519(pair.sign(&[crate::ATTESTATION_CONTEXT, &garbage[..]].concat()));
+        assert_noop!(
+            Institution::submit_attestation(RuntimeOrigin::signed(1), garbage, AttestorKey::Ed25519(pair.public()), signature),
+            Error::<Test>::InvalidAttestationClaim
+        );
+    });
+}
+
+fn directory(filter: InstitutionFilter<u64>) -> Vec<Vec<u8>> {
+    let mut ids = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = Institution::institution_page(filter.clone(), cursor, 10);
+        assert!(page.items.len() <= 3);
+        ids.extend(page.items.into_iter().map(|(id, _)| id));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    ids.sort();
+    ids
+}
+
+fn ids(ids: &[&[u8]]) -> Vec<Vec<u8>> {
+    ids.iter().map(|id| id.to_vec()).collect()
+}
+
+#[test]
+fn directory_pages_through_status_and_owner_indexes() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        create_institution(1, b"INST_002");
+        create_institution(2, b"INST_003");
+        create_institution(3, b"INST_004");
+        create_institution(3, b"INST_005");
+        assert_ok!(Institution::certify_institution(RuntimeOrigin::root(), b"INST_002".to_vec()));
+
+        assert_eq!(
+            directory(InstitutionFilter::All),
+            ids(&[b"INST_001", b"INST_002", b"INST_003", b"INST_004", b"INST_005"])
+        );
+        assert_eq!(directory(InstitutionFilter::Status(0)), ids(&[b"INST_002"]));
+        assert_eq!(
+            directory(InstitutionFilter::Status(1)),
+            ids(&[b"INST_001", b"INST_003", b"INST_004", b"INST_005"])
+        );
+        assert_eq!(directory(InstitutionFilter::Creator(1)), ids(&[b"INST_001", b"INST_002"]));
+
+        // 所有权转让及删除同步更新索引
+        assert_ok!(Institution::propose_owner_transfer(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2));
+        assert_ok!(Institution::accept_owner_transfer(RuntimeOrigin::signed(2), b"INST_001".to_vec()));
+        assert_eq!(directory(InstitutionFilter::Creator(1)), ids(&[b"INST_002"]));
+        assert_eq!(directory(InstitutionFilter::Creator(2)), ids(&[b"INST_001", b"INST_003"]));
+
+        assert_ok!(Institution::delete_institution(RuntimeOrigin::signed(3), b"INST_004".to_vec()));
+        assert_eq!(directory(InstitutionFilter::Status(1)), ids(&[b"INST_001", b"INST_003", b"INST_005"]));
+        assert!(InstitutionsByCreator::<Test>::iter_key_prefix(3).eq([bounded(b"INST_005")]));
+        assert!(Institution::institution(b"INST_004".to_vec()).is_none());
+    });
+}
+
+#[test]
+fn directory_filters_by_name_prefix_within_scan_budget() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        create_institution(1, b"INST_002");
+        create_institution(2, b"INST_003");
+        create_institution(3, b"INST_004");
+        create_institution(3, b"INST_005");
+        assert_ok!(rename(2, b"INST_003", b"Mart One"));
+        assert_ok!(rename(3, b"INST_005", b"Mart Two"));
+
+        // 单页最多检查 4 个机构，未遍历完时返回游标
+        let first = Institution::institution_page(InstitutionFilter::NamePrefix(b"Mart".to_vec()), None, 3);
+        assert!(first.next_cursor.is_some());
+
+        // 每页条数为 0 时不检查任何机构，游标保持不变
+        let empty = Institution::institution_page(InstitutionFilter::NamePrefix(b"Mart".to_vec()), first.next_cursor.clone(), 0);
+        assert!(empty.items.is_empty());
+        assert_eq!(empty.next_cursor, first.next_cursor);
+        assert_eq!(directory(InstitutionFilter::NamePrefix(b"Mart".to_vec())), ids(&[b"INST_003", b"INST_005"]));
+
+        // 挂起后移入暂停状态索引
+        assert_ok!(suspend(b"INST_003", 10));
+        assert_eq!(directory(InstitutionFilter::Status(3)), ids(&[b"INST_003"]));
+        assert!(!InstitutionsByStatus::<Test>::contains_key(1, bounded(b"INST_003")));
+        assert_eq!(
+            Institution::institution(b"INST_003".to_vec()).map(|institution| institution.status.code()),
+            Some(3)
+        );
+    });
+}
@@ -0,0 +1,360 @@
+
+//! Weights for pallet_institution
+//!
+//! 尚未运行基准测试，以下权重为按存储访问次数手工估算的值，
+//! 接入基准测试后由 benchmark CLI 生成的结果替换。
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_institution.
+pub trait WeightInfo {
+	fn create_institution() -> Weight;
+	fn update_institution_status() -> Weight;
+	fn update_institution_info() -> Weight;
+	fn delete_institution() -> Weight;
+	fn certify_institution() -> Weight;
+	fn revoke_certification() -> Weight;
+	fn add_registrar() -> Weight;
+	fn remove_registrar() -> Weight;
+	fn approve_audit() -> Weight;
+	fn reject_audit() -> Weight;
+	fn request_audit() -> Weight;
+	fn grant_role() -> Weight;
+	fn revoke_role() -> Weight;
+	fn propose_owner_transfer() -> Weight;
+	fn accept_owner_transfer() -> Weight;
+	fn cancel_owner_transfer() -> Weight;
+	fn set_license() -> Weight;
+	fn set_parent() -> Weight;
+	fn suspend_institution() -> Weight;
+	fn lift_suspension() -> Weight;
+	fn appeal_suspension() -> Weight;
+	fn add_attestor() -> Weight;
+	fn remove_attestor() -> Weight;
+	fn submit_attestation() -> Weight;
+	fn clean_up_institution(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_institution using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Institution Institutions (r:1 w:1), InstitutionTombstones (r:1), InstitutionDeposits (r:1 w:1), Balances Holds (r:2 w:2), InstitutionsByStatus/InstitutionsByCreator (w:2), AuditCases (w:1)
+	fn create_institution() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), InstitutionsByStatus (w:2)
+	fn update_institution_status() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), InstitutionDeposits (r:1 w:1), Balances Holds (r:2 w:2), InstitutionRevisionCount (r:1 w:1), InstitutionRevisions (r:1 w:1)
+	fn update_institution_info() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), InstitutionBranches (r:2 w:1), InstitutionDeposits (r:1 w:1), Balances Holds (r:2 w:2), Certifications/AuditCases/PendingOwnerTransfers/Licenses (r:4 w:6), InstitutionTombstones (w:1), PendingCleanups (w:1)
+	fn delete_institution() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), AuditCases (r:1 w:1), AuditHistory (r:1 w:1), Certifications (w:1), InstitutionsByStatus (w:1)
+	fn certify_institution() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), Certifications (r:1 w:1), InstitutionsByStatus (w:1)
+	fn revoke_certification() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Institution Registrars (r:1 w:1)
+	fn add_registrar() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Registrars (r:1 w:1)
+	fn remove_registrar() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Registrars (r:1), AuditCases (r:1 w:1), Institutions (r:1 w:1), AuditHistory (r:1 w:1), Certifications (w:1), InstitutionsByStatus (w:2)
+	fn approve_audit() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Institution Registrars (r:1), AuditCases (r:1 w:1), AuditHistory (r:1 w:1)
+	fn reject_audit() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Institution Institutions (r:1), InstitutionMembers (r:1), AuditCases (r:1 w:1)
+	fn request_audit() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Institutions (r:1), InstitutionMembers (r:1 w:1)
+	fn grant_role() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Institutions (r:1), InstitutionMembers (r:1 w:1)
+	fn revoke_role() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Institutions (r:1), PendingOwnerTransfers (w:1)
+	fn propose_owner_transfer() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution PendingOwnerTransfers (r:1 w:1), Institutions (r:1 w:1), InstitutionsByCreator (w:2), InstitutionMembers (w:1)
+	fn accept_owner_transfer() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Institution PendingOwnerTransfers (r:1 w:1), Institutions (r:1)
+	fn cancel_owner_transfer() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Institutions (r:1), Licenses (r:1 w:1), LicenseExpiries (r:1 w:2), LicenseExpiryWarnings (w:1)
+	fn set_license() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Institution Institutions (r:4 w:1), InstitutionMembers (r:2), InstitutionBranches (r:2 w:2), InstitutionDeposits (w:1), Balances Holds (w:2)
+	fn set_parent() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), SuspensionExpiries (r:1 w:2), InstitutionsByStatus (w:1)
+	fn suspend_institution() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), SuspensionExpiries (r:1 w:1), SuspensionAppeals (r:1 w:1), InstitutionsByStatus (w:1)
+	fn lift_suspension() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Institution Institutions (r:1), InstitutionMembers (r:1), SuspensionAppeals (w:1)
+	fn appeal_suspension() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Attestors (r:1 w:1)
+	fn add_attestor() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Attestors (r:1 w:1)
+	fn remove_attestor() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Attestors (r:1), Institutions (r:1), Attestations (r:1 w:1), Certifications (r:1)
+	fn submit_attestation() -> Weight {
+		Weight::from_parts(20_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution PendingCleanups (r:1 w:1), dependent per-institution indexes (r:6 w:6)
+	/// Per item: Balances Holds and deposit records (r:2 w:2), item record and its indexes (r:1 w:3)
+	/// The range of component `n` is `[0, CleanupBatchSize]`.
+	fn clean_up_institution(n: u32, ) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Institution Institutions (r:1 w:1), InstitutionTombstones (r:1), InstitutionDeposits (r:1 w:1), Balances Holds (r:2 w:2), InstitutionsByStatus/InstitutionsByCreator (w:2), AuditCases (w:1)
+	fn create_institution() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), InstitutionsByStatus (w:2)
+	fn update_institution_status() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), InstitutionDeposits (r:1 w:1), Balances Holds (r:2 w:2), InstitutionRevisionCount (r:1 w:1), InstitutionRevisions (r:1 w:1)
+	fn update_institution_info() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), InstitutionBranches (r:2 w:1), InstitutionDeposits (r:1 w:1), Balances Holds (r:2 w:2), Certifications/AuditCases/PendingOwnerTransfers/Licenses (r:4 w:6), InstitutionTombstones (w:1), PendingCleanups (w:1)
+	fn delete_institution() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), AuditCases (r:1 w:1), AuditHistory (r:1 w:1), Certifications (w:1), InstitutionsByStatus (w:1)
+	fn certify_institution() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), Certifications (r:1 w:1), InstitutionsByStatus (w:1)
+	fn revoke_certification() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Institution Registrars (r:1 w:1)
+	fn add_registrar() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Registrars (r:1 w:1)
+	fn remove_registrar() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Registrars (r:1), AuditCases (r:1 w:1), Institutions (r:1 w:1), AuditHistory (r:1 w:1), Certifications (w:1), InstitutionsByStatus (w:2)
+	fn approve_audit() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Institution Registrars (r:1), AuditCases (r:1 w:1), AuditHistory (r:1 w:1)
+	fn reject_audit() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Institution Institutions (r:1), InstitutionMembers (r:1), AuditCases (r:1 w:1)
+	fn request_audit() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Institutions (r:1), InstitutionMembers (r:1 w:1)
+	fn grant_role() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Institutions (r:1), InstitutionMembers (r:1 w:1)
+	fn revoke_role() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Institutions (r:1), PendingOwnerTransfers (w:1)
+	fn propose_owner_transfer() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution PendingOwnerTransfers (r:1 w:1), Institutions (r:1 w:1), InstitutionsByCreator (w:2), InstitutionMembers (w:1)
+	fn accept_owner_transfer() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Institution PendingOwnerTransfers (r:1 w:1), Institutions (r:1)
+	fn cancel_owner_transfer() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Institutions (r:1), Licenses (r:1 w:1), LicenseExpiries (r:1 w:2), LicenseExpiryWarnings (w:1)
+	fn set_license() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Institution Institutions (r:4 w:1), InstitutionMembers (r:2), InstitutionBranches (r:2 w:2), InstitutionDeposits (w:1), Balances Holds (w:2)
+	fn set_parent() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), SuspensionExpiries (r:1 w:2), InstitutionsByStatus (w:1)
+	fn suspend_institution() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Institution Institutions (r:1 w:1), SuspensionExpiries (r:1 w:1), SuspensionAppeals (r:1 w:1), InstitutionsByStatus (w:1)
+	fn lift_suspension() -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Institution Institutions (r:1), InstitutionMembers (r:1), SuspensionAppeals (w:1)
+	fn appeal_suspension() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Attestors (r:1 w:1)
+	fn add_attestor() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Attestors (r:1 w:1)
+	fn remove_attestor() -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution Attestors (r:1), Institutions (r:1), Attestations (r:1 w:1), Certifications (r:1)
+	fn submit_attestation() -> Weight {
+		Weight::from_parts(20_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Institution PendingCleanups (r:1 w:1), dependent per-institution indexes (r:6 w:6)
+	/// Per item: Balances Holds and deposit records (r:2 w:2), item record and its indexes (r:1 w:3)
+	/// The range of component `n` is `[0, CleanupBatchSize]`.
+	fn clean_up_institution(n: u32, ) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+	}
+}
@@ -3,14 +3,41 @@
 /// 订单管理模块
 pub use pallet::*;
 
+pub mod migrations;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::{pallet_prelude::*, traits::{Get, ConstU32}};
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::{CheckedMul, Saturating, Zero},
+        traits::{
+            fungible::{Inspect, MutateHold},
+            tokens::{Fortitude, Precision, Restriction},
+            ConstU32, Get,
+        },
+    };
     use frame_system::pallet_prelude::*;
+    use pallet_carrier::CarrierInspect;
+    use pallet_institution::{CommerceOutcome, InstitutionInspect, InstitutionRole, OnCommerceOutcome, ProfitDistributor};
+    use pallet_institution_freight_template::{FreightCalculator, FreightItem};
+    use pallet_product::{ProductInspect, PurchaseRecorder};
     use scale_info::TypeInfo;
     use sp_std::prelude::*;
     use sp_std::vec::Vec;
 
+    /// 托管款项余额类型
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// 当前存储版本
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::config]
     pub trait Config: frame_system::Config + scale_info::TypeInfo {
         /// 事件类型
@@ -32,19 +59,56 @@ pub mod pallet {
         #[pallet::constant]
         type MaxOrderItems: Get<u32>;
         
-        /// 快递公司名称最大长度
+        /// 承运商编码最大长度
         #[pallet::constant]
-        type MaxExpressCompanyLength: Get<u32>;
+        type MaxCarrierCodeLength: Get<u32>;
         
         /// 快递单号最大长度
         #[pallet::constant]
         type MaxExpressNumberLength: Get<u32>;
+        
+        /// 机构信息及成员权限查询
+        type Institutions: InstitutionInspect<Self::AccountId>;
+        
+        /// 用于托管订单款项的货币
+        type Currency: MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+        
+        /// 运行时冻结原因
+        type RuntimeHoldReason: From<HoldReason>;
+        
+        /// 每单位订单金额对应的链上余额，支付时按此折算托管款项
+        #[pallet::constant]
+        type AmountUnit: Get<BalanceOf<Self>>;
+        
+        /// 订单完成时将托管款项按机构分润合约分配
+        type ProfitDistributor: ProfitDistributor<Self::AccountId, BalanceOf<Self>>;
+        
+        /// 机构结算、退款或取消已支付订单时通知信誉模块
+        type OnCommerceOutcome: OnCommerceOutcome;
+        
+        /// 按机构运费模板计算订单运费
+        type FreightCalculator: FreightCalculator;
+        
+        /// 商品现价、重量及包装尺寸查询和购买记录，用于订单计价、运费计算及在订单结算后授予买家评价资格
+        type Products: ProductInspect + PurchaseRecorder<Self::AccountId>;
+        
+        /// 承运商名录查询，用于校验快递单号
+        type Carriers: CarrierInspect;
     }
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
+    /// 本模块的资金冻结原因
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// 订单托管款项
+        #[codec(index = 0)]
+        OrderEscrow,
+    }
+
     /// 订单状态枚举
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[repr(u8)]
@@ -70,8 +134,8 @@ pub mod pallet {
     pub struct OrderItem {
         pub product_code: BoundedVec<u8, ConstU32<64>>, // 商品ID
         pub quantity: u32,         // 商品数量
-        pub price_per_unit: u32,   // 单价，单位为人民币
-        pub weight: u32,           // 商品重量
+        pub price_per_unit: u32,   // 下单时的商品现价，单位为人民币
+        pub weight: u32,           // 下单时的商品重量
     }
 
     /// 用户的订单
@@ -83,14 +147,16 @@ pub mod pallet {
         pub status: OrderStatus,                                         // 订单状态
         pub created_time: BlockNumberFor<T>,                             // 创建时间
         pub updated_time: BlockNumberFor<T>,                             // 更新时间
-        pub total_amount: u32,                                          // 总金额
+        pub total_amount: u32,                                          // 总金额（订单金额单位）
         pub total_weight: u32,                                          // 总重量
         pub freight: u32,                                               // 运费
+        pub freight_template: Option<(BoundedVec<u8, ConstU32<128>>, u32)>, // 计价所用运费模板ID及版本
         pub contact_information: ContactInformation,                     // 联系信息
         pub items: BoundedVec<OrderItem, T::MaxOrderItems>,            // 订单明细
-        pub express_company: BoundedVec<u8, T::MaxExpressCompanyLength>, // 快递公司名称
+        pub carrier_code: BoundedVec<u8, T::MaxCarrierCodeLength>,       // 承运商编码
         pub express_number: BoundedVec<u8, T::MaxExpressNumberLength>,   // 快递单号
         pub creator: T::AccountId,                                       // 创建者
+        pub legacy_express_company: Option<BoundedVec<u8, T::MaxExpressNumberLength>>, // 承运商名录启用前登记的快递公司名称
     }
 
     /// 订单存储映射
@@ -126,6 +192,17 @@ pub mod pallet {
         ValueQuery,                                                  // 查询策略：如果键不存在，返回空列表
     >;
 
+    /// 订单托管款项 [付款账户, 托管金额]，订单完成时结算给机构，取消或退款时退还
+    #[pallet::storage]
+    #[pallet::storage_prefix = "OrderEscrows"]
+    pub type OrderEscrows<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxOrderCodeLength>,  // 主键：订单编码
+        (T::AccountId, BalanceOf<T>),           // 值：付款账户及托管金额
+        OptionQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -139,6 +216,12 @@ pub mod pallet {
         OrderCancelled(BoundedVec<u8, T::MaxOrderCodeLength>),
         /// 订单已删除 [订单编码]
         OrderDeleted(BoundedVec<u8, T::MaxOrderCodeLength>),
+        /// 订单已支付，款项已托管 [订单编码, 付款账户, 托管金额]
+        OrderPaid(BoundedVec<u8, T::MaxOrderCodeLength>, T::AccountId, BalanceOf<T>),
+        /// 订单托管款项已结算给机构 [订单编码, 结算金额]
+        OrderSettled(BoundedVec<u8, T::MaxOrderCodeLength>, BalanceOf<T>),
+        /// 订单托管款项已退还买家 [订单编码, 退还金额]
+        OrderEscrowRefunded(BoundedVec<u8, T::MaxOrderCodeLength>, BalanceOf<T>),
     }
 
     #[pallet::error]
@@ -163,6 +246,28 @@ pub mod pallet {
         UserOrderListFull,
         /// 机构订单列表已满
         InstitutionOrderListFull,
+        /// 机构不存在
+        InstitutionNotFound,
+        /// 机构已暂停营业
+        InstitutionSuspended,
+        /// 机构存在未结束的订单
+        InstitutionHasOpenOrders,
+        /// 运费超出订单金额范围
+        FreightOverflow,
+        /// 承运商未登记
+        CarrierNotFound,
+        /// 快递单号不符合承运商的单号规则
+        InvalidTrackingNumber,
+        /// 订单金额折算后超出余额类型范围
+        AmountOverflow,
+        /// 订单款项仍在托管中
+        OrderInEscrow,
+        /// 商品不存在或已下架
+        ProductNotFound,
+        /// 商品数量必须大于0
+        InvalidQuantity,
+        /// 机构已设置运费模板，下单须指定运费模板及目的地区
+        ShippingRequired,
     }
 
     #[pallet::call]
@@ -175,11 +280,11 @@ pub mod pallet {
             order_code: Vec<u8>,
             member_code: Vec<u8>,
             institution_code: Vec<u8>,
-            freight: u32,
+            shipping: Option<(Vec<u8>, Vec<u8>)>, // (运费模板ID, 目的地区编码)，机构未设置运费模板时可为 None
             phone: Option<Vec<u8>>,
             email: Option<Vec<u8>>,
             address: Option<Vec<u8>>,
-            items: Vec<(Vec<u8>, u32, u32, u32)>, // (product_code, quantity, price_per_unit, weight)
+            items: Vec<(Vec<u8>, u32)>, // (product_code, quantity)，单价及重量取自链上商品信息
         ) -> DispatchResult {
             // 确认调用者身份
             let who = ensure_signed(origin)?;
@@ -201,6 +306,10 @@ pub mod pallet {
             let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_code)
                 .map_err(|_| Error::<T>::StringConversionError)?;
             
+            // 检查机构是否存在
+            ensure!(T::Institutions::exists(&bounded_institution_code), Error::<T>::InstitutionNotFound);
+            ensure!(!T::Institutions::is_suspended(&bounded_institution_code), Error::<T>::InstitutionSuspended);
+            
             // 创建联系信息
             let contact_information = ContactInformation {
                 phone: match phone {
@@ -224,8 +333,16 @@ pub mod pallet {
             let mut order_items = Vec::new();
             let mut total_amount = 0u32;
             let mut total_weight = 0u32;
+            let mut freight_items = Vec::new();
             
-            for (product_code, quantity, price_per_unit, weight) in items {
+            for (product_code, quantity) in items {
+                ensure!(quantity > 0, Error::<T>::InvalidQuantity);
+                
+                // 单价、重量及包装体积取自链上商品信息，商品必须可售
+                let sale_info = T::Products::sale_info(&product_code, &bounded_institution_code)
+                    .ok_or(Error::<T>::ProductNotFound)?;
+                let price_per_unit = u32::try_from(sale_info.unit_price).map_err(|_| Error::<T>::AmountOverflow)?;
+                let weight = sale_info.unit_weight;
                 let item = OrderItem {
                     product_code: BoundedVec::<u8, ConstU32<64>>::try_from(product_code)
                         .map_err(|_| Error::<T>::StringConversionError)?,
@@ -234,8 +351,17 @@ pub mod pallet {
                     weight,
                 };
                 
-                total_amount = total_amount.saturating_add(price_per_unit.saturating_mul(quantity));
+                total_amount = price_per_unit
+                    .checked_mul(quantity)
+                    .and_then(|amount| total_amount.checked_add(amount))
+                    .ok_or(Error::<T>::AmountOverflow)?;
                 total_weight = total_weight.saturating_add(weight.saturating_mul(quantity));
+                freight_items.push(FreightItem {
+                    quantity,
+                    unit_weight: weight.into(),
+                    unit_volume: sale_info.unit_volume,
+                    unit_price: price_per_unit.into(),
+                });
                 
                 order_items.push(item);
             }
@@ -243,8 +369,27 @@ pub mod pallet {
             let bounded_items = BoundedVec::<OrderItem, T::MaxOrderItems>::try_from(order_items)
                 .map_err(|_| Error::<T>::TooManyOrderItems)?;
             
-            // 加上运费
-            total_amount = total_amount.saturating_add(freight);
+            // 按机构运费模板计算运费并计入总金额，目的地区不在配送范围内时下单失败；
+            // 机构设置了运费模板时必须指定模板及目的地区
+            let (freight, freight_template) = match shipping {
+                Some((template_id, destination_region)) => {
+                    let bounded_template_id = BoundedVec::<u8, ConstU32<128>>::try_from(template_id)
+                        .map_err(|_| Error::<T>::StringConversionError)?;
+                    let quote = T::FreightCalculator::calculate_freight(
+                        &bounded_institution_code,
+                        &bounded_template_id,
+                        &destination_region,
+                        &freight_items,
+                    )?;
+                    let fee = u32::try_from(quote.fee).map_err(|_| Error::<T>::FreightOverflow)?;
+                    (fee, Some((bounded_template_id, quote.version)))
+                },
+                None => {
+                    ensure!(!T::FreightCalculator::has_templates(&bounded_institution_code), Error::<T>::ShippingRequired);
+                    (0, None)
+                },
+            };
+            total_amount = total_amount.checked_add(freight).ok_or(Error::<T>::AmountOverflow)?;
             
             // 创建订单
             let order = Order {
@@ -257,13 +402,20 @@ pub mod pallet {
                 total_amount,
                 total_weight,
                 freight,
+                freight_template,
                 contact_information,
                 items: bounded_items,
-                express_company: BoundedVec::default(),
+                carrier_code: BoundedVec::default(),
                 express_number: BoundedVec::default(),
                 creator: who.clone(),
+                legacy_express_company: None,
             };
             
+            // 订单未结束前保留计价所用的运费模板版本
+            if let Some((template_id, version)) = &order.freight_template {
+                T::FreightCalculator::retain_version(&bounded_institution_code, template_id, *version);
+            }
+            
             // 存储订单
             Orders::<T>::insert(&bounded_order_code, &order);
             
@@ -306,9 +458,6 @@ pub mod pallet {
             Orders::<T>::try_mutate(&bounded_order_code, |maybe_order| -> DispatchResult {
                 let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
                 
-                // 检查权限（仅创建者可以更新）
-                ensure!(order.creator == who, Error::<T>::NotAuthorized);
-                
                 // 将 u8 转换为 OrderStatus
                 let new_status = match status {
                     0 => OrderStatus::Pending,
@@ -320,9 +469,39 @@ pub mod pallet {
                     _ => return Err(Error::<T>::InvalidStatus.into()),
                 };
                 
+                // 检查权限：取消可由订单创建者或机构订单处理人员操作，发货、完成及退款仅限机构订单处理人员
+                if new_status == OrderStatus::Cancelled {
+                    Self::ensure_order_operator(order, &who)?;
+                } else {
+                    Self::ensure_order_clerk(order, &who)?;
+                }
+                
                 // 检查状态转换是否有效
                 Self::validate_status_transition(&order.status, &new_status)?;
                 
+                // 订单完成时结算托管款项，取消或退款时退还买家
+                let escrowed = match new_status {
+                    OrderStatus::Completed => Self::settle_escrow(order)?,
+                    OrderStatus::Cancelled | OrderStatus::Refunded => Self::refund_escrow(&order.order_code)?,
+                    _ => false,
+                };
+                
+                // 记录影响机构信誉的交易结果：仅计入机构确认且涉及托管款项的结算、退款及机构取消已支付订单
+                let outcome = match new_status {
+                    OrderStatus::Completed if escrowed => Some(CommerceOutcome::OrderCompleted),
+                    OrderStatus::Refunded if escrowed => Some(CommerceOutcome::OrderRefunded),
+                    OrderStatus::Cancelled if escrowed && order.creator != who => Some(CommerceOutcome::OrderCancelled),
+                    _ => None,
+                };
+                if let Some(outcome) = outcome {
+                    T::OnCommerceOutcome::on_commerce_outcome(&order.institution_code, outcome);
+                }
+                
+                // 订单结束时释放运费模板版本
+                if !Self::is_open(&new_status) {
+                    Self::release_freight_template(order);
+                }
+                
                 // 更新状态和时间
                 order.status = new_status;
                 order.updated_time = frame_system::Pallet::<T>::block_number();
@@ -340,7 +519,7 @@ pub mod pallet {
         pub fn update_express_info(
             origin: OriginFor<T>,
             order_code: Vec<u8>,
-            express_company: Vec<u8>,
+            carrier_code: Vec<u8>,
             express_number: Vec<u8>,
         ) -> DispatchResult {
             // 确认调用者身份
@@ -350,22 +529,30 @@ pub mod pallet {
             let bounded_order_code = BoundedVec::<u8, T::MaxOrderCodeLength>::try_from(order_code)
                 .map_err(|_| Error::<T>::StringConversionError)?;
                 
-            let bounded_express_company = BoundedVec::<u8, T::MaxExpressCompanyLength>::try_from(express_company)
+            let bounded_carrier_code = BoundedVec::<u8, T::MaxCarrierCodeLength>::try_from(carrier_code)
                 .map_err(|_| Error::<T>::StringConversionError)?;
                 
             let bounded_express_number = BoundedVec::<u8, T::MaxExpressNumberLength>::try_from(express_number)
                 .map_err(|_| Error::<T>::StringConversionError)?;
             
+            // 承运商必须已登记，快递单号必须符合承运商的单号规则
+            ensure!(T::Carriers::exists(&bounded_carrier_code), Error::<T>::CarrierNotFound);
+            ensure!(
+                T::Carriers::is_valid_tracking_number(&bounded_carrier_code, &bounded_express_number),
+                Error::<T>::InvalidTrackingNumber
+            );
+            
             // 获取并更新订单
             Orders::<T>::try_mutate(&bounded_order_code, |maybe_order| -> DispatchResult {
                 let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
                 
-                // 检查权限（仅创建者可以更新）
-                ensure!(order.creator == who, Error::<T>::NotAuthorized);
+                // 检查权限（订单创建者或机构订单处理人员）
+                Self::ensure_order_operator(order, &who)?;
                 
                 // 更新快递信息
-                order.express_company = bounded_express_company;
+                order.carrier_code = bounded_carrier_code;
                 order.express_number = bounded_express_number;
+                order.legacy_express_company = None;
                 order.updated_time = frame_system::Pallet::<T>::block_number();
                 
                 // 发出事件
@@ -402,7 +589,9 @@ pub mod pallet {
                     Error::<T>::InvalidStatusTransition
                 );
                 
-                // 更新状态
+                // 退还托管款项，更新状态并释放运费模板版本
+                Self::refund_escrow(&order.order_code)?;
+                Self::release_freight_template(order);
                 order.status = OrderStatus::Cancelled;
                 order.updated_time = frame_system::Pallet::<T>::block_number();
                 
@@ -433,6 +622,12 @@ pub mod pallet {
             
             ensure!(order.creator == who, Error::<T>::NotAuthorized);
             
+            // 款项仍在托管中的订单须先完成、取消或退款
+            ensure!(!OrderEscrows::<T>::contains_key(&bounded_order_code), Error::<T>::OrderInEscrow);
+            
+            // 未结束的订单删除时释放运费模板版本
+            Self::release_freight_template(&order);
+            
             // 从用户订单索引中移除
             UserOrders::<T>::mutate(&order.member_code, |orders| {
                 orders.retain(|code| code != &bounded_order_code);
@@ -451,24 +646,145 @@ pub mod pallet {
             
             Ok(())
         }
+        
+        /// 支付订单
+        ///
+        /// 按订单总金额从买家余额中冻结托管款项，订单完成时结算给机构，取消或退款时退还买家。
+        #[pallet::call_index(5)]
+        #[pallet::weight(5_000)]
+        pub fn pay_order(
+            origin: OriginFor<T>,
+            order_code: Vec<u8>,
+        ) -> DispatchResult {
+            // 确认调用者身份
+            let who = ensure_signed(origin)?;
+            
+            // 转换为边界向量
+            let bounded_order_code = BoundedVec::<u8, T::MaxOrderCodeLength>::try_from(order_code)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            // 获取并更新订单
+            Orders::<T>::try_mutate(&bounded_order_code, |maybe_order| -> DispatchResult {
+                let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+                
+                // 检查权限（仅创建者可以支付），只有待支付的订单可以支付
+                ensure!(order.creator == who, Error::<T>::NotAuthorized);
+                ensure!(order.status == OrderStatus::Pending, Error::<T>::InvalidStatusTransition);
+                
+                // 按订单总金额冻结托管款项
+                let amount = T::AmountUnit::get()
+                    .checked_mul(&order.total_amount.into())
+                    .ok_or(Error::<T>::AmountOverflow)?;
+                T::Currency::hold(&HoldReason::OrderEscrow.into(), &who, amount)?;
+                OrderEscrows::<T>::insert(&bounded_order_code, (who.clone(), amount));
+                
+                // 更新状态和时间
+                order.status = OrderStatus::Paid;
+                order.updated_time = frame_system::Pallet::<T>::block_number();
+                
+                // 发出事件
+                Self::deposit_event(Event::OrderPaid(bounded_order_code.clone(), who, amount));
+                
+                Ok(())
+            })
+        }
     }
     
     // 辅助函数
     impl<T: Config> Pallet<T> {
+        /// 确认调用者为订单创建者或拥有机构的订单处理权限
+        fn ensure_order_operator(order: &Order<T>, who: &T::AccountId) -> DispatchResult {
+            ensure!(
+                &order.creator == who
+                    || T::Institutions::has_role(&order.institution_code, who, InstitutionRole::OrderClerk),
+                Error::<T>::NotAuthorized
+            );
+            Ok(())
+        }
+        
+        /// 确认调用者拥有机构的订单处理权限
+        fn ensure_order_clerk(order: &Order<T>, who: &T::AccountId) -> DispatchResult {
+            ensure!(
+                T::Institutions::has_role(&order.institution_code, who, InstitutionRole::OrderClerk),
+                Error::<T>::NotAuthorized
+            );
+            Ok(())
+        }
+        
+        /// 按机构分润合约从托管款项直接向各收款人结算，为买家授予所购商品的评价资格，返回是否存在托管款项；
+        /// 升级前已标记为已支付的订单没有托管款项，不做结算
+        fn settle_escrow(order: &Order<T>) -> Result<bool, DispatchError> {
+            let Some((payer, amount)) = OrderEscrows::<T>::take(&order.order_code) else {
+                return Ok(false);
+            };
+            let reason = HoldReason::OrderEscrow.into();
+            
+            // 托管款项已专用于本订单，直接转出，不受买家账户其他冻结的限制
+            let mut paid = BalanceOf::<T>::zero();
+            T::ProfitDistributor::distribute(&order.institution_code, amount, &mut |beneficiary, payout| {
+                T::Currency::transfer_on_hold(
+                    &reason,
+                    &payer,
+                    beneficiary,
+                    payout,
+                    Precision::Exact,
+                    Restriction::Free,
+                    Fortitude::Force,
+                )?;
+                paid = paid.saturating_add(payout);
+                Ok(())
+            })?;
+            
+            // 未分配的部分退还买家
+            let remainder = amount.saturating_sub(paid);
+            if !remainder.is_zero() {
+                T::Currency::release(&reason, &payer, remainder, Precision::Exact)?;
+            }
+
+            for item in order.items.iter() {
+                T::Products::record_purchase(&item.product_code, &order.institution_code, &payer);
+            }
+            Self::deposit_event(Event::OrderSettled(order.order_code.clone(), amount));
+            Ok(true)
+        }
+        
+        /// 将托管款项退还买家，返回是否存在托管款项
+        fn refund_escrow(order_code: &BoundedVec<u8, T::MaxOrderCodeLength>) -> Result<bool, DispatchError> {
+            let Some((payer, amount)) = OrderEscrows::<T>::take(order_code) else {
+                return Ok(false);
+            };
+            T::Currency::release(&HoldReason::OrderEscrow.into(), &payer, amount, Precision::Exact)?;
+            Self::deposit_event(Event::OrderEscrowRefunded(order_code.clone(), amount));
+            Ok(true)
+        }
+        
+        /// 待支付、已支付或已发货的订单尚未结束
+        fn is_open(status: &OrderStatus) -> bool {
+            matches!(status, OrderStatus::Pending | OrderStatus::Paid | OrderStatus::Delivered)
+        }
+        
+        /// 未结束的订单释放对计价运费模板版本的引用
+        fn release_freight_template(order: &Order<T>) {
+            if !Self::is_open(&order.status) {
+                return;
+            }
+            if let Some((template_id, version)) = &order.freight_template {
+                T::FreightCalculator::release_version(&order.institution_code, template_id, *version);
+            }
+        }
+        
         /// 验证状态转换是否有效
         fn validate_status_transition(from: &OrderStatus, to: &OrderStatus) -> DispatchResult {
             use OrderStatus::*;
             
             let valid_transition = match (from, to) {
-                // 待支付可以转换为已支付或已取消
-                (Pending, Paid) | (Pending, Cancelled) => true,
+                // 待支付可以转换为已取消（支付须通过 `pay_order` 托管款项）
+                (Pending, Cancelled) => true,
                 // 已支付可以转换为已发货、已退款或已取消
                 (Paid, Delivered) | (Paid, Refunded) | (Paid, Cancelled) => true,
-                // 已发货可以转换为已完成
-                (Delivered, Completed) => true,
-                // 已完成可以转换为已退款
-                (Completed, Refunded) => true,
-                // 其他转换无效
+                // 已发货可以转换为已完成或已退款
+                (Delivered, Completed) | (Delivered, Refunded) => true,
+                // 已完成的订单款项已结算，不能再退款；其他转换无效
                 _ => false,
             };
             
@@ -476,4 +792,24 @@ pub mod pallet {
             Ok(())
         }
     }
-} 
\ No newline at end of file
+    
+    impl<T: Config> pallet_institution::OnInstitutionRemoved for Pallet<T> {
+        /// 机构存在待支付、已支付或已发货的订单时拒绝删除
+        fn can_remove(institution_id: &[u8]) -> DispatchResult {
+            let Ok(bounded_institution_code) = BoundedVec::<u8, T::MaxInstitutionIdLength>::try_from(institution_id.to_vec()) else {
+                return Ok(());
+            };
+            
+            let has_open_orders = InstitutionOrders::<T>::get(&bounded_institution_code).iter().any(|code| {
+                Orders::<T>::get(code).is_some_and(|order| Self::is_open(&order.status))
+            });
+            ensure!(!has_open_orders, Error::<T>::InstitutionHasOpenOrders);
+            Ok(())
+        }
+        
+        /// 已结束的订单作为买家的历史记录保留
+        fn on_institution_removed(_institution_id: &[u8], _limit: u32) -> u32 {
+            0
+        }
+    }
+}
\ No newline at end of file
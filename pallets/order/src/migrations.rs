@@ -0,0 +1,191 @@
+//! 订单模块存储迁移
+
+/// v1：订单记录计价所用的运费模板及版本，已有订单均未记录
+///
+/// 原快递公司名称的长度上限与 `MaxExpressNumberLength` 相同。
+pub mod v1 {
+    use crate::pallet::{Config, ContactInformation, OrderItem, OrderStatus, Pallet};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        storage_alias,
+        traits::{ConstU32, UncheckedOnRuntimeUpgrade},
+    };
+    use frame_system::pallet_prelude::BlockNumberFor;
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// v0 版本的订单结构
+    #[derive(Encode, Decode)]
+    pub struct OldOrder<T: Config> {
+        pub order_code: BoundedVec<u8, T::MaxOrderCodeLength>,
+        pub member_code: BoundedVec<u8, T::MaxMemberCodeLength>,
+        pub institution_code: BoundedVec<u8, T::MaxInstitutionIdLength>,
+        pub status: OrderStatus,
+        pub created_time: BlockNumberFor<T>,
+        pub updated_time: BlockNumberFor<T>,
+        pub total_amount: u32,
+        pub total_weight: u32,
+        pub freight: u32,
+        pub contact_information: ContactInformation,
+        pub items: BoundedVec<OrderItem, T::MaxOrderItems>,
+        pub express_company: BoundedVec<u8, T::MaxExpressNumberLength>,
+        pub express_number: BoundedVec<u8, T::MaxExpressNumberLength>,
+        pub creator: T::AccountId,
+    }
+
+    /// v1 版本的订单结构
+    #[derive(Encode, Decode)]
+    pub struct OrderV1<T: Config> {
+        pub order_code: BoundedVec<u8, T::MaxOrderCodeLength>,
+        pub member_code: BoundedVec<u8, T::MaxMemberCodeLength>,
+        pub institution_code: BoundedVec<u8, T::MaxInstitutionIdLength>,
+        pub status: OrderStatus,
+        pub created_time: BlockNumberFor<T>,
+        pub updated_time: BlockNumberFor<T>,
+        pub total_amount: u32,
+        pub total_weight: u32,
+        pub freight: u32,
+        pub freight_template: Option<(BoundedVec<u8, ConstU32<128>>, u32)>,
+        pub contact_information: ContactInformation,
+        pub items: BoundedVec<OrderItem, T::MaxOrderItems>,
+        pub express_company: BoundedVec<u8, T::MaxExpressNumberLength>,
+        pub express_number: BoundedVec<u8, T::MaxExpressNumberLength>,
+        pub creator: T::AccountId,
+    }
+
+    /// v1 版本的订单存储
+    #[storage_alias]
+    pub type Orders<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        BoundedVec<u8, <T as Config>::MaxOrderCodeLength>,
+        OrderV1<T>,
+        OptionQuery,
+    >;
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((Orders::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            Orders::<T>::translate::<OldOrder<T>, _>(|_, old| {
+                translated += 1;
+                Some(OrderV1 {
+                    order_code: old.order_code,
+                    member_code: old.member_code,
+                    institution_code: old.institution_code,
+                    status: old.status,
+                    created_time: old.created_time,
+                    updated_time: old.updated_time,
+                    total_amount: old.total_amount,
+                    total_weight: old.total_weight,
+                    freight: old.freight,
+                    freight_template: None,
+                    contact_information: old.contact_information,
+                    items: old.items,
+                    express_company: old.express_company,
+                    express_number: old.express_number,
+                    creator: old.creator,
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after = Orders::<T>::iter_values().count() as u32;
+            ensure!(before == after, "orders lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v0 迁移到 v1，仅在链上存储版本为 0 时执行
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// v2：订单的快递公司名称改为承运商编码
+///
+/// 承运商名录在同一次升级中启用，迁移时名录为空，因此承运商编码一律置空，
+/// 原名称保留在 `legacy_express_company` 中，待重新填写快递信息时按承运商名录校验。
+pub mod v2 {
+    use super::v1::OrderV1;
+    use crate::pallet::{Config, Order, Orders, Pallet};
+    #[cfg(feature = "try-runtime")]
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV1ToV2<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((Orders::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            Orders::<T>::translate::<OrderV1<T>, _>(|_, old| {
+                translated += 1;
+                let legacy_express_company = Some(old.express_company).filter(|name| !name.is_empty());
+                Some(Order {
+                    order_code: old.order_code,
+                    member_code: old.member_code,
+                    institution_code: old.institution_code,
+                    status: old.status,
+                    created_time: old.created_time,
+                    updated_time: old.updated_time,
+                    total_amount: old.total_amount,
+                    total_weight: old.total_weight,
+                    freight: old.freight,
+                    freight_template: old.freight_template,
+                    contact_information: old.contact_information,
+                    items: old.items,
+                    carrier_code: BoundedVec::default(),
+                    express_number: old.express_number,
+                    creator: old.creator,
+                    legacy_express_company,
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after = Orders::<T>::iter_values().count() as u32;
+            ensure!(before == after, "orders lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v1 迁移到 v2，仅在链上存储版本为 1 时执行
+    pub type MigrateV1ToV2<T> = VersionedMigration<
+        1,
+        2,
+        InnerMigrateV1ToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
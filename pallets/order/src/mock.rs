@@ -0,0 +1,260 @@
+use crate as pallet_order;
+use frame_support::{derive_impl, parameter_types, traits::{ConstU32, ConstU64}};
+use frame_system::EnsureRoot;
+use sp_runtime::{BuildStorage, Perbill};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+    #[runtime::runtime]
+    #[runtime::derive(
+        RuntimeCall,
+        RuntimeEvent,
+        RuntimeError,
+        RuntimeOrigin,
+        RuntimeFreezeReason,
+        RuntimeHoldReason,
+        RuntimeSlashReason,
+        RuntimeLockId,
+        RuntimeTask,
+        RuntimeViewFunction
+    )]
+    pub struct Test;
+
+    #[runtime::pallet_index(0)]
+    pub type System = frame_system::Pallet<Test>;
+
+    #[runtime::pallet_index(1)]
+    pub type Institution = pallet_institution::Pallet<Test>;
+
+    #[runtime::pallet_index(2)]
+    pub type Balances = pallet_balances::Pallet<Test>;
+
+    #[runtime::pallet_index(3)]
+    pub type Region = pallet_region::Pallet<Test>;
+
+    #[runtime::pallet_index(4)]
+    pub type InstitutionFreightTemplate = pallet_institution_freight_template::Pallet<Test>;
+
+    #[runtime::pallet_index(5)]
+    pub type Carrier = pallet_carrier::Pallet<Test>;
+
+    #[runtime::pallet_index(6)]
+    pub type Product = pallet_product::Pallet<Test>;
+
+    #[runtime::pallet_index(7)]
+    pub type InstitutionReputation = pallet_institution_reputation::Pallet<Test>;
+
+    #[runtime::pallet_index(8)]
+    pub type Order = pallet_order::Pallet<Test>;
+}
+
+parameter_types! {
+    /// 测试中信誉分不衰减
+    pub const DecayRetention: Perbill = Perbill::one();
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type FreezeIdentifier = ();
+    type MaxFreezes = ConstU32<1>;
+}
+
+impl pallet_institution::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxInstitutionIdLength = ConstU32<64>;
+    type MaxNameLength = ConstU32<256>;
+    type MaxResponsiblePersonLength = ConstU32<128>;
+    type MaxBusinessScopeLength = ConstU32<512>;
+    type MaxContractLength = ConstU32<1024>;
+    type MaxBeneficiaries = ConstU32<4>;
+    type PlatformAccount = ConstU64<99>;
+    type CertifyOrigin = EnsureRoot<u64>;
+    type MaxRevocationReasonLength = ConstU32<256>;
+    type RegistrarOrigin = EnsureRoot<u64>;
+    type MaxRegistrars = ConstU32<5>;
+    type ApprovalThreshold = ConstU32<2>;
+    type RejectionThreshold = ConstU32<2>;
+    type MaxAuditCommentLength = ConstU32<256>;
+    type MaxAuditHistory = ConstU32<2>;
+    type OwnerTransferPeriod = ConstU64<10>;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type DepositBase = ConstU64<100>;
+    type DepositPerByte = ConstU64<1>;
+    type OnInstitutionRemoved = (Order, Product, InstitutionFreightTemplate, InstitutionReputation);
+    type MaxAuthorityCodeLength = ConstU32<32>;
+    type MaxRegistrationNumberLength = ConstU32<64>;
+    type LicenseExpiryWarningPeriod = ConstU64<5>;
+    type MaxLicenseExpiriesPerBlock = ConstU32<2>;
+    type MaxHierarchyDepth = ConstU32<2>;
+    type MaxBranches = ConstU32<4>;
+    type MaxRevisions = ConstU32<2>;
+    type SuspendOrigin = EnsureRoot<u64>;
+    type MaxSuspensionReasonLength = ConstU32<128>;
+    type MaxSuspensionsPerBlock = ConstU32<2>;
+    type AttestorOrigin = EnsureRoot<u64>;
+    type MaxAttestors = ConstU32<2>;
+    type MaxDirectoryPageSize = ConstU32<3>;
+    type MaxDirectoryScan = ConstU32<4>;
+    type CleanupBatchSize = ConstU32<100>;
+    type WeightInfo = ();
+}
+
+impl pallet_region::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxRegionCodeLength = ConstU32<12>;
+    type MaxRegionNameLength = ConstU32<64>;
+    type RegionOrigin = EnsureRoot<u64>;
+}
+
+impl pallet_institution_freight_template::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxInstitutionIdLength = ConstU32<64>;
+    type MaxTemplateIdLength = ConstU32<32>;
+    type MaxTemplateNameLength = ConstU32<64>;
+    type MaxRegionCodeLength = ConstU32<12>;
+    type MaxRegionsPerRule = ConstU32<4>;
+    type MaxRegionalRules = ConstU32<4>;
+    type MaxFreeShippingConditions = ConstU32<2>;
+    type MaxExcludedRegions = ConstU32<4>;
+    type MaxSurchargeRules = ConstU32<4>;
+    type Institutions = Institution;
+    type Regions = Region;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type DepositBase = ConstU64<10>;
+    type DepositPerByte = ConstU64<1>;
+    type WeightInfo = ();
+}
+
+impl pallet_carrier::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxCarrierCodeLength = ConstU32<16>;
+    type MaxCarrierNameLength = ConstU32<64>;
+    type MaxTrackingPrefixLength = ConstU32<4>;
+    type MaxTrackingPrefixes = ConstU32<3>;
+    type CarrierOrigin = EnsureRoot<u64>;
+}
+
+impl pallet_product::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxProductCodeLength = ConstU32<64>;
+    type MaxInstitutionCodeLength = ConstU32<64>;
+    type MaxNameLength = ConstU32<64>;
+    type MaxCategoryLength = ConstU32<32>;
+    type MaxBrandLength = ConstU32<32>;
+    type MaxAuthorizedMemberGroup = ConstU32<32>;
+    type MaxAuthorizedGroups = ConstU32<4>;
+    type MaxDescriptionLength = ConstU32<256>;
+    type MaxImageUrlLength = ConstU32<128>;
+    type MaxDetailImages = ConstU32<4>;
+    type Institutions = Institution;
+    type OnCommerceOutcome = InstitutionReputation;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type DepositBase = ConstU64<10>;
+    type DepositPerByte = ConstU64<1>;
+}
+
+impl pallet_institution_reputation::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxInstitutionIdLength = ConstU32<64>;
+    type DecayPeriod = ConstU64<0>;
+    type DecayRetention = DecayRetention;
+    type Institutions = Institution;
+}
+
+impl pallet_order::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxOrderCodeLength = ConstU32<64>;
+    type MaxMemberCodeLength = ConstU32<64>;
+    type MaxInstitutionIdLength = ConstU32<64>;
+    type MaxOrderItems = ConstU32<4>;
+    type MaxCarrierCodeLength = ConstU32<16>;
+    type MaxExpressNumberLength = ConstU32<32>;
+    type Institutions = Institution;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type AmountUnit = ConstU64<10>;
+    type ProfitDistributor = Institution;
+    type OnCommerceOutcome = InstitutionReputation;
+    type FreightCalculator = InstitutionFreightTemplate;
+    type Products = Product;
+    type Carriers = Carrier;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: (1..=5).map(|who| (who, 10_000)).collect(),
+        ..Default::default()
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    pallet_region::GenesisConfig::<Test> {
+        regions: vec![
+            (b"CN".to_vec(), b"China".to_vec(), None),
+            (b"440000".to_vec(), b"Guangdong".to_vec(), Some(b"CN".to_vec())),
+            (b"440300".to_vec(), b"Shenzhen".to_vec(), Some(b"440000".to_vec())),
+            (b"540000".to_vec(), b"Tibet".to_vec(), Some(b"CN".to_vec())),
+        ],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    // 顺丰：SF 开头的 12 至 15 位大写字母及数字
+    pallet_carrier::GenesisConfig::<Test> {
+        carriers: vec![(b"SF".to_vec(), b"SF Express".to_vec(), 12, 15, vec![b"SF".to_vec()], 1, false)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = storage.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// 以指定账户创建一个测试机构
+pub fn create_institution(who: u64, id: &[u8]) {
+    frame_support::assert_ok!(Institution::create_institution(
+        RuntimeOrigin::signed(who),
+        id.to_vec(),
+        b"Shop".to_vec(),
+        b"Shop Co., Ltd.".to_vec(),
+        b"https://example.com/license.png".to_vec(),
+        b"Zhang San".to_vec(),
+        b"Retail".to_vec(),
+        None,
+    ));
+}
+
+/// 由机构所有者上架一件测试商品 [单价, 单件重量]
+pub fn create_product(institution_owner: u64, institution_id: &[u8], product_code: &[u8], price: u64, weight: u32) {
+    frame_support::assert_ok!(Product::create_product(
+        RuntimeOrigin::signed(institution_owner),
+        product_code.to_vec(),
+        institution_id.to_vec(),
+        b"Tea".to_vec(),
+        b"Food".to_vec(),
+        b"Brand".to_vec(),
+        vec![],
+        price,
+        price,
+        b"Green tea".to_vec(),
+        b"https://example.com/tea.png".to_vec(),
+        vec![],
+        100,
+        weight,
+        Perbill::zero(),
+    ));
+}
@@ -0,0 +1,457 @@
+use crate::{mock::*, Error, Event, HoldReason, OrderEscrows, OrderStatus, Orders};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{
+        fungible::{InspectHold, MutateFreeze},
+        ConstU32,
+    },
+    BoundedVec,
+};
+use pallet_institution::{InstitutionRole, ProfitShare, RevenueSplit};
+use pallet_institution_freight_template::{FreightRule, PricingMode};
+use sp_runtime::Perbill;
+
+fn bounded(code: &[u8]) -> BoundedVec<u8, ConstU32<64>> {
+    BoundedVec::try_from(code.to_vec()).unwrap()
+}
+
+/// 账户 1 的机构 INST_001 上架单价 10、重量 500 的商品 TEA，账户 2 为订单处理人员
+fn setup_shop() {
+    create_institution(1, b"INST_001");
+    create_product(1, b"INST_001", b"TEA", 10, 500);
+    assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2, InstitutionRole::OrderClerk));
+}
+
+/// 账户 3 下单购买商品，不指定运费模板
+fn place_order(code: &[u8], items: Vec<(Vec<u8>, u32)>) -> frame_support::dispatch::DispatchResult {
+    Order::create_order(
+        RuntimeOrigin::signed(3),
+        code.to_vec(),
+        b"MEMBER_3".to_vec(),
+        b"INST_001".to_vec(),
+        None,
+        None,
+        None,
+        Some(b"Shenzhen".to_vec()),
+        items,
+    )
+}
+
+/// 账户 3 下单并按指定运费模板及目的地区计算运费
+fn place_shipped_order(code: &[u8], region: &[u8], items: Vec<(Vec<u8>, u32)>) -> frame_support::dispatch::DispatchResult {
+    Order::create_order(
+        RuntimeOrigin::signed(3),
+        code.to_vec(),
+        b"MEMBER_3".to_vec(),
+        b"INST_001".to_vec(),
+        Some((b"standard".to_vec(), region.to_vec())),
+        None,
+        None,
+        Some(b"Shenzhen".to_vec()),
+        items,
+    )
+}
+
+/// 机构所有者创建按重量计费的运费模板：首重 1000 运费 10，每续重 500 加 3
+fn create_freight_template(pricing_mode: PricingMode) {
+    assert_ok!(InstitutionFreightTemplate::create_freight_template(
+        RuntimeOrigin::signed(1),
+        b"INST_001".to_vec(),
+        b"standard".to_vec(),
+        b"Standard".to_vec(),
+        pricing_mode,
+        FreightRule { first_unit: 1_000, first_fee: 10, additional_unit: 500, additional_fee: 3, free_shipping: BoundedVec::default() },
+    ));
+}
+
+fn set_status(who: u64, code: &[u8], status: OrderStatus) -> frame_support::dispatch::DispatchResult {
+    Order::update_order_status(RuntimeOrigin::signed(who), code.to_vec(), status as u8)
+}
+
+fn pay(code: &[u8]) -> frame_support::dispatch::DispatchResult {
+    Order::pay_order(RuntimeOrigin::signed(3), code.to_vec())
+}
+
+fn escrow_on_hold(who: u64) -> u64 {
+    Balances::balance_on_hold(&HoldReason::OrderEscrow.into(), &who)
+}
+
+/// 机构信誉记录中的 [已完成, 已取消, 已退款] 订单数
+fn order_outcomes() -> (u32, u32, u32) {
+    InstitutionReputation::reputation(b"INST_001")
+        .map_or((0, 0, 0), |record| (record.completed_orders, record.cancelled_orders, record.refunded_orders))
+}
+
+fn update_express(who: u64, carrier: &[u8], number: &[u8]) -> frame_support::dispatch::DispatchResult {
+    Order::update_express_info(RuntimeOrigin::signed(who), b"ORDER_1".to_vec(), carrier.to_vec(), number.to_vec())
+}
+
+#[test]
+fn express_info_requires_registered_carrier_and_valid_number() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        assert_ok!(place_order(b"ORDER_1", vec![(b"TEA".to_vec(), 1)]));
+
+        assert_noop!(update_express(2, b"YTO", b"YT1234567890"), Error::<Test>::CarrierNotFound);
+        // 前缀、长度及字符集均须符合承运商登记的单号规则
+        assert_noop!(update_express(2, b"SF", b"JD1234567890"), Error::<Test>::InvalidTrackingNumber);
+        assert_noop!(update_express(2, b"SF", b"SF123"), Error::<Test>::InvalidTrackingNumber);
+        assert_noop!(update_express(2, b"SF", b"SF12345abcde"), Error::<Test>::InvalidTrackingNumber);
+        assert_noop!(update_express(4, b"SF", b"SF1234567890"), Error::<Test>::NotAuthorized);
+
+        assert_ok!(update_express(2, b"SF", b"SF1234567890"));
+        System::assert_last_event(Event::OrderExpressInfoUpdated(bounded(b"ORDER_1")).into());
+        let order = Orders::<Test>::get(bounded(b"ORDER_1")).unwrap();
+        assert_eq!(order.carrier_code.into_inner(), b"SF".to_vec());
+        assert_eq!(order.express_number.into_inner(), b"SF1234567890".to_vec());
+    });
+}
+
+#[test]
+fn v2_migration_keeps_legacy_express_company() {
+    use crate::migrations::{v1::OrderV1, v2::InnerMigrateV1ToV2};
+    use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        assert_ok!(place_order(b"ORDER_1", vec![(b"TEA".to_vec(), 1)]));
+        let order = Orders::<Test>::get(bounded(b"ORDER_1")).unwrap();
+        let old = OrderV1::<Test> {
+            order_code: order.order_code,
+            member_code: order.member_code,
+            institution_code: order.institution_code,
+            status: order.status,
+            created_time: order.created_time,
+            updated_time: order.updated_time,
+            total_amount: order.total_amount,
+            total_weight: order.total_weight,
+            freight: order.freight,
+            freight_template: order.freight_template,
+            contact_information: order.contact_information,
+            items: order.items,
+            express_company: BoundedVec::try_from(b"SF Express".to_vec()).unwrap(),
+            express_number: BoundedVec::try_from(b"SF1234567890".to_vec()).unwrap(),
+            creator: order.creator,
+        };
+        frame_support::storage::unhashed::put(&Orders::<Test>::hashed_key_for(bounded(b"ORDER_1")), &old);
+
+        // 迁移时承运商名录尚未启用，原名称单独保留
+        InnerMigrateV1ToV2::<Test>::on_runtime_upgrade();
+        let order = Orders::<Test>::get(bounded(b"ORDER_1")).unwrap();
+        assert!(order.carrier_code.is_empty());
+        assert_eq!(order.express_number.to_vec(), b"SF1234567890".to_vec());
+        assert_eq!(order.legacy_express_company.map(|name| name.into_inner()), Some(b"SF Express".to_vec()));
+
+        // 按承运商名录重新登记快递信息后清除原名称
+        assert_ok!(update_express(2, b"SF", b"SF1234567890"));
+        assert_eq!(Orders::<Test>::get(bounded(b"ORDER_1")).unwrap().legacy_express_company, None);
+    });
+}
+
+#[test]
+fn paying_holds_order_total_in_escrow() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        assert_ok!(place_order(b"ORDER_1", vec![(b"TEA".to_vec(), 2)]));
+
+        // 仅创建者可以支付，订单状态不能直接改为已支付
+        assert_noop!(Order::pay_order(RuntimeOrigin::signed(4), b"ORDER_1".to_vec()), Error::<Test>::NotAuthorized);
+        assert_noop!(set_status(2, b"ORDER_1", OrderStatus::Paid), Error::<Test>::InvalidStatusTransition);
+
+        // 订单金额 20，每单位订单金额折合 10 个余额单位
+        assert_ok!(pay(b"ORDER_1"));
+        System::assert_last_event(Event::OrderPaid(bounded(b"ORDER_1"), 3, 200).into());
+        assert_eq!(escrow_on_hold(3), 200);
+        assert_eq!(Balances::free_balance(3), 10_000 - 200);
+        assert_eq!(OrderEscrows::<Test>::get(bounded(b"ORDER_1")), Some((3, 200)));
+        assert_eq!(Orders::<Test>::get(bounded(b"ORDER_1")).unwrap().status, OrderStatus::Paid);
+
+        assert_noop!(pay(b"ORDER_1"), Error::<Test>::InvalidStatusTransition);
+    });
+}
+
+#[test]
+fn completion_settles_escrow_through_profit_split() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        // 平台分成 10%，其余 70% 归账户 4，30% 归账户 5
+        let split = RevenueSplit::<Test> {
+            shares: BoundedVec::try_from(vec![
+                ProfitShare { beneficiary: 4, share: Perbill::from_percent(70) },
+                ProfitShare { beneficiary: 5, share: Perbill::from_percent(30) },
+            ])
+            .unwrap(),
+            platform_cut: Some(Perbill::from_percent(10)),
+            effective_from: 1,
+            effective_until: Some(100),
+        };
+        assert_ok!(Institution::update_institution_info(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(split),
+        ));
+        let owner_balance = Balances::free_balance(1);
+
+        assert_ok!(place_order(b"ORDER_1", vec![(b"TEA".to_vec(), 2)]));
+        assert_ok!(pay(b"ORDER_1"));
+
+        // 买家不能发货或确认完成以触发结算
+        assert_noop!(set_status(3, b"ORDER_1", OrderStatus::Delivered), Error::<Test>::NotAuthorized);
+        assert_ok!(set_status(2, b"ORDER_1", OrderStatus::Delivered));
+        assert_noop!(set_status(3, b"ORDER_1", OrderStatus::Completed), Error::<Test>::NotAuthorized);
+
+        assert_ok!(set_status(2, b"ORDER_1", OrderStatus::Completed));
+        System::assert_has_event(Event::OrderSettled(bounded(b"ORDER_1"), 200).into());
+        assert_eq!(escrow_on_hold(3), 0);
+        assert_eq!(Balances::free_balance(3), 10_000 - 200);
+        assert_eq!(Balances::free_balance(99), 20);
+        assert_eq!(Balances::free_balance(4), 10_000 + 126);
+        assert_eq!(Balances::free_balance(5), 10_000 + 54);
+        assert_eq!(Balances::free_balance(1), owner_balance);
+        assert!(!OrderEscrows::<Test>::contains_key(bounded(b"ORDER_1")));
+        assert_eq!(order_outcomes(), (1, 0, 0));
+        // 完成的订单为买家累计一次评价机会
+        assert_eq!(
+            pallet_product::PendingReviews::<Test>::get((
+                BoundedVec::try_from(b"INST_001".to_vec()).unwrap(),
+                BoundedVec::try_from(b"TEA".to_vec()).unwrap(),
+                3,
+            )),
+            1
+        );
+
+        // 已结算的订单不能再退款
+        assert_noop!(set_status(2, b"ORDER_1", OrderStatus::Refunded), Error::<Test>::InvalidStatusTransition);
+    });
+}
+
+#[test]
+fn completion_without_split_pays_institution_owner() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        let owner_balance = Balances::free_balance(1);
+
+        assert_ok!(place_order(b"ORDER_1", vec![(b"TEA".to_vec(), 2)]));
+        assert_ok!(pay(b"ORDER_1"));
+        assert_ok!(set_status(2, b"ORDER_1", OrderStatus::Delivered));
+        assert_ok!(set_status(2, b"ORDER_1", OrderStatus::Completed));
+
+        assert_eq!(Balances::free_balance(1), owner_balance + 200);
+        assert_eq!(Balances::free_balance(3), 10_000 - 200);
+    });
+}
+
+#[test]
+fn settlement_pays_from_escrow_despite_buyer_freeze() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        let owner_balance = Balances::free_balance(1);
+        assert_ok!(place_order(b"ORDER_1", vec![(b"TEA".to_vec(), 2)]));
+        assert_ok!(pay(b"ORDER_1"));
+
+        // 买家全部余额被冻结，托管款项仍直接结算给机构
+        assert_ok!(Balances::set_freeze(&(), &3, 10_000));
+        assert_ok!(set_status(2, b"ORDER_1", OrderStatus::Delivered));
+        assert_ok!(set_status(2, b"ORDER_1", OrderStatus::Completed));
+
+        assert_eq!(escrow_on_hold(3), 0);
+        assert_eq!(Balances::free_balance(3), 10_000 - 200);
+        assert_eq!(Balances::free_balance(1), owner_balance + 200);
+    });
+}
+
+#[test]
+fn refunds_and_cancellations_return_escrow() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        for code in [&b"ORDER_1"[..], b"ORDER_2", b"ORDER_3"] {
+            assert_ok!(place_order(code, vec![(b"TEA".to_vec(), 2)]));
+            assert_ok!(pay(code));
+        }
+        assert_eq!(escrow_on_hold(3), 600);
+
+        // 机构退款
+        assert_ok!(set_status(2, b"ORDER_1", OrderStatus::Refunded));
+        System::assert_has_event(Event::OrderEscrowRefunded(bounded(b"ORDER_1"), 200).into());
+        assert_eq!(order_outcomes(), (0, 0, 1));
+
+        // 买家自行取消不影响机构信誉
+        assert_ok!(Order::cancel_order(RuntimeOrigin::signed(3), b"ORDER_2".to_vec()));
+        System::assert_has_event(Event::OrderEscrowRefunded(bounded(b"ORDER_2"), 200).into());
+        assert_eq!(order_outcomes(), (0, 0, 1));
+
+        // 机构取消已支付订单计入信誉
+        assert_ok!(set_status(2, b"ORDER_3", OrderStatus::Cancelled));
+        assert_eq!(order_outcomes(), (0, 1, 1));
+
+        assert_eq!(escrow_on_hold(3), 0);
+        assert_eq!(Balances::free_balance(3), 10_000);
+        assert_eq!(OrderEscrows::<Test>::iter().count(), 0);
+
+        // 未支付订单取消时没有托管款项，也不影响信誉
+        assert_ok!(place_order(b"ORDER_4", vec![(b"TEA".to_vec(), 1)]));
+        assert_ok!(set_status(2, b"ORDER_4", OrderStatus::Cancelled));
+        assert_eq!(order_outcomes(), (0, 1, 1));
+    });
+}
+
+#[test]
+fn escrowed_order_cannot_be_deleted() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        assert_ok!(place_order(b"ORDER_1", vec![(b"TEA".to_vec(), 2)]));
+        assert_ok!(pay(b"ORDER_1"));
+
+        assert_noop!(Order::delete_order(RuntimeOrigin::signed(3), b"ORDER_1".to_vec()), Error::<Test>::OrderInEscrow);
+
+        assert_ok!(Order::cancel_order(RuntimeOrigin::signed(3), b"ORDER_1".to_vec()));
+        assert_ok!(Order::delete_order(RuntimeOrigin::signed(3), b"ORDER_1".to_vec()));
+        System::assert_last_event(Event::OrderDeleted(bounded(b"ORDER_1")).into());
+        assert!(!Orders::<Test>::contains_key(bounded(b"ORDER_1")));
+    });
+}
+
+#[test]
+fn order_total_is_priced_from_product_catalogue() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        create_product(1, b"INST_001", b"CUP", 25, 300);
+
+        assert_ok!(place_order(b"ORDER_1", vec![(b"TEA".to_vec(), 2), (b"CUP".to_vec(), 3)]));
+        let order = Orders::<Test>::get(bounded(b"ORDER_1")).unwrap();
+        assert_eq!(order.total_amount, 10 * 2 + 25 * 3);
+        assert_eq!(order.total_weight, 500 * 2 + 300 * 3);
+        assert_eq!(order.freight, 0);
+        assert_eq!(order.freight_template, None);
+        assert_eq!(order.items[1].price_per_unit, 25);
+
+        assert_noop!(place_order(b"ORDER_2", vec![(b"TEA".to_vec(), 0)]), Error::<Test>::InvalidQuantity);
+        assert_noop!(place_order(b"ORDER_2", vec![(b"MUG".to_vec(), 1)]), Error::<Test>::ProductNotFound);
+        assert_noop!(place_order(b"ORDER_2", vec![]), Error::<Test>::EmptyOrderItems);
+
+        // 下架的商品不能下单
+        assert_ok!(Product::update_product_status(RuntimeOrigin::signed(1), b"CUP".to_vec(), b"INST_001".to_vec(), 1));
+        assert_noop!(place_order(b"ORDER_2", vec![(b"CUP".to_vec(), 1)]), Error::<Test>::ProductNotFound);
+    });
+}
+
+#[test]
+fn freight_is_added_to_order_total() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        create_freight_template(PricingMode::ByWeight);
+
+        // 机构设置运费模板后必须指定模板及目的地区
+        assert_noop!(place_order(b"ORDER_1", vec![(b"TEA".to_vec(), 2)]), Error::<Test>::ShippingRequired);
+
+        // 总重 2000：首重 10 + 续重两步 6
+        assert_ok!(place_shipped_order(b"ORDER_1", b"440300", vec![(b"TEA".to_vec(), 4)]));
+        let order = Orders::<Test>::get(bounded(b"ORDER_1")).unwrap();
+        assert_eq!(order.freight, 16);
+        assert_eq!(order.total_amount, 10 * 4 + 16);
+        assert_eq!(order.freight_template, Some((BoundedVec::try_from(b"standard".to_vec()).unwrap(), 1)));
+
+        // 托管金额包含运费
+        assert_ok!(pay(b"ORDER_1"));
+        assert_eq!(escrow_on_hold(3), 10 * 56);
+    });
+}
+
+#[test]
+fn freight_errors_reject_order() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        create_freight_template(PricingMode::ByWeight);
+        assert_ok!(InstitutionFreightTemplate::set_delivery_restrictions(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            b"standard".to_vec(),
+            vec![b"540000".to_vec()],
+            vec![],
+        ));
+
+        assert_noop!(
+            place_shipped_order(b"ORDER_1", b"XX", vec![(b"TEA".to_vec(), 1)]),
+            pallet_institution_freight_template::Error::<Test>::UnknownRegion
+        );
+        assert_noop!(
+            place_shipped_order(b"ORDER_1", b"540000", vec![(b"TEA".to_vec(), 1)]),
+            pallet_institution_freight_template::Error::<Test>::RegionNotDeliverable
+        );
+        assert_noop!(
+            Order::create_order(
+                RuntimeOrigin::signed(3),
+                b"ORDER_1".to_vec(),
+                b"MEMBER_3".to_vec(),
+                b"INST_001".to_vec(),
+                Some((b"express".to_vec(), b"440300".to_vec())),
+                None,
+                None,
+                Some(b"Shenzhen".to_vec()),
+                vec![(b"TEA".to_vec(), 1)],
+            ),
+            pallet_institution_freight_template::Error::<Test>::FreightTemplateNotFound
+        );
+    });
+}
+
+#[test]
+fn order_retains_priced_template_version() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        create_freight_template(PricingMode::ByWeight);
+        assert_ok!(place_shipped_order(b"ORDER_1", b"440300", vec![(b"TEA".to_vec(), 2)]));
+        let priced = InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 1).unwrap();
+
+        // 模板更新后订单计价所用的版本仍可读取
+        assert_ok!(InstitutionFreightTemplate::update_freight_template(
+            RuntimeOrigin::signed(1),
+            b"INST_001".to_vec(),
+            b"standard".to_vec(),
+            None,
+            None,
+            Some(FreightRule { first_unit: 1_000, first_fee: 12, additional_unit: 500, additional_fee: 3, free_shipping: BoundedVec::default() }),
+        ));
+        assert_eq!(InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 1), Some(priced));
+
+        // 订单结束后释放引用，历史版本随之删除
+        assert_ok!(Order::cancel_order(RuntimeOrigin::signed(3), b"ORDER_1".to_vec()));
+        assert_eq!(InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 1), None);
+        assert!(InstitutionFreightTemplate::template_version(b"INST_001", b"standard", 2).is_some());
+    });
+}
+
+#[test]
+fn product_dimensions_drive_volumetric_freight() {
+    new_test_ext().execute_with(|| {
+        setup_shop();
+        // 体积除以 6 得到体积重量，与实际重量取较大者
+        create_freight_template(PricingMode::ByVolume { divisor: 6 });
+
+        // 未设置包装尺寸时按实际重量 1000 计费
+        assert_ok!(place_shipped_order(b"ORDER_1", b"440300", vec![(b"TEA".to_vec(), 2)]));
+        assert_eq!(Orders::<Test>::get(bounded(b"ORDER_1")).unwrap().freight, 10);
+
+        // 包装体积 6000，两件体积重量 2000 超过实际重量
+        assert_ok!(Product::set_product_dimensions(
+            RuntimeOrigin::signed(1),
+            b"TEA".to_vec(),
+            b"INST_001".to_vec(),
+            Some(pallet_product::ProductDimensions { length: 30, width: 20, height: 10 }),
+        ));
+        assert_ok!(place_shipped_order(b"ORDER_2", b"440300", vec![(b"TEA".to_vec(), 2)]));
+        let order = Orders::<Test>::get(bounded(b"ORDER_2")).unwrap();
+        assert_eq!(order.freight, 16);
+        assert_eq!(order.total_amount, 10 * 2 + 16);
+
+        // 清除包装尺寸后恢复按实际重量计费
+        assert_ok!(Product::set_product_dimensions(RuntimeOrigin::signed(1), b"TEA".to_vec(), b"INST_001".to_vec(), None));
+        assert_ok!(place_shipped_order(b"ORDER_3", b"440300", vec![(b"TEA".to_vec(), 2)]));
+        assert_eq!(Orders::<Test>::get(bounded(b"ORDER_3")).unwrap().freight, 10);
+    });
+}
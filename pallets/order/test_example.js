@@ -20,24 +20,24 @@ async function main() {
     const orderCode = 'ORDER-001';
     const memberCode = 'MEMBER-001';
     const institutionCode = 'INST-001';
-    const freight = 50; // 50元运费
+    const shipping = ['standard', '110000']; // 运费模板ID及目的地区编码，运费由链上计算
     
     // Contact information
     const phone = '+86 13800138000';
     const email = 'customer@example.com';
     const address = '北京市朝阳区某某街道1号';
     
-    // Order items: (product_code, quantity, price_per_unit, weight)
+    // Order items: (product_code, quantity); price and weight are read from the product pallet
     const items = [
-        ['PROD-001', 2, 100, 500],  // 商品1: 2个，单价100元，单重500克
-        ['PROD-002', 1, 200, 1000], // 商品2: 1个，单价200元，单重1000克
+        ['PROD-001', 2], // 商品1: 2个
+        ['PROD-002', 1], // 商品2: 1个
     ];
     
     const createOrderTx = api.tx.order.createOrder(
         orderCode,
         memberCode,
         institutionCode,
-        freight,
+        shipping,
         phone,
         email,
         address,
@@ -56,24 +56,24 @@ async function main() {
         }
     });
     
-    // Example 2: Update order status (e.g., mark as paid)
-    console.log('\nUpdating order status to Paid...');
-    const updateStatusTx = api.tx.order.updateOrderStatus(orderCode, 1); // 1 = Paid
+    // Example 2: Pay the order (funds are held in escrow until the institution settles or refunds it)
+    console.log('\nPaying order...');
+    const payOrderTx = api.tx.order.payOrder(orderCode);
     
-    await updateStatusTx.signAndSend(alice, ({ status }) => {
+    await payOrderTx.signAndSend(alice, ({ status }) => {
         if (status.isInBlock) {
-            console.log('Order status updated successfully');
+            console.log('Order paid successfully');
         }
     });
     
     // Example 3: Update express information
     console.log('\nUpdating express information...');
-    const expressCompany = '顺丰快递';
+    const carrierCode = 'SF';
     const expressNumber = 'SF1234567890';
     
     const updateExpressTx = api.tx.order.updateExpressInfo(
         orderCode,
-        expressCompany,
+        carrierCode,
         expressNumber
     );
     
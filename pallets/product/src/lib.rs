@@ -3,15 +3,65 @@
 /// 商品管理模块
 pub use pallet::*;
 
+pub mod migrations;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// 下单时从链上商品数据读取的计价及物流属性
+#[derive(Clone, Copy, Eq, PartialEq, frame_support::pallet_prelude::RuntimeDebug)]
+pub struct ProductSaleInfo {
+    pub unit_price: u64,  // 现价
+    pub unit_weight: u32, // 单件重量
+    pub unit_volume: u64, // 单件包装体积（立方厘米），未登记尺寸时为 0
+}
+
+/// 供其他业务模块查询商品计价及物流属性的接口
+pub trait ProductInspect {
+    /// 可售商品的现价、重量及包装体积，商品不存在或已下架时为 None
+    fn sale_info(product_code: &[u8], institution_code: &[u8]) -> Option<ProductSaleInfo>;
+}
+
+impl ProductInspect for () {
+    fn sale_info(_product_code: &[u8], _institution_code: &[u8]) -> Option<ProductSaleInfo> {
+        None
+    }
+}
+
+/// 订单结算后记录买家购买的接口，每次已付款并结算的购买授予一次评价资格
+pub trait PurchaseRecorder<AccountId> {
+    /// 记录买家一次已结算的购买，商品不存在时忽略
+    fn record_purchase(product_code: &[u8], institution_code: &[u8], buyer: &AccountId);
+}
+
+impl<AccountId> PurchaseRecorder<AccountId> for () {
+    fn record_purchase(_product_code: &[u8], _institution_code: &[u8], _buyer: &AccountId) {}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::{pallet_prelude::*, traits::Get};
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{
+            fungible::{Inspect, MutateHold},
+            tokens::Precision,
+            Get,
+        },
+    };
     use frame_system::pallet_prelude::*;
+    use pallet_institution::{CommerceOutcome, InstitutionInspect, InstitutionRole, OnCommerceOutcome};
     use scale_info::TypeInfo;
-    use sp_runtime::Perbill;
+    use sp_runtime::{traits::Saturating, Perbill};
     use sp_std::prelude::*;
     use sp_std::vec::Vec;
-    use codec::{Decode, Encode};
+    use codec::{Decode, DecodeWithMemTracking, Encode};
+
+    /// 押金余额类型
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
     #[pallet::config]
     pub trait Config: frame_system::Config + scale_info::TypeInfo {
@@ -57,12 +107,44 @@ pub mod pallet {
         /// 详情图最大数量
         #[pallet::constant]
         type MaxDetailImages: Get<u32>;
+        
+        /// 机构信息及成员权限查询
+        type Institutions: InstitutionInspect<Self::AccountId>;
+        
+        /// 商品评价时通知信誉模块
+        type OnCommerceOutcome: OnCommerceOutcome;
+        
+        /// 用于冻结存储押金的货币
+        type Currency: MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+        
+        /// 运行时冻结原因
+        type RuntimeHoldReason: From<HoldReason>;
+        
+        /// 创建商品的基础押金
+        #[pallet::constant]
+        type DepositBase: Get<BalanceOf<Self>>;
+        
+        /// 商品记录每字节押金
+        #[pallet::constant]
+        type DepositPerByte: Get<BalanceOf<Self>>;
     }
 
+    /// 当前存储版本
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
+    /// 本模块的资金冻结原因
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// 商品记录存储押金
+        #[codec(index = 0)]
+        ProductDeposit,
+    }
+
     /// 商品状态枚举
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[repr(u8)]
@@ -71,6 +153,23 @@ pub mod pallet {
         Unavailable = 1, // 下架
     }
 
+    /// 商品包装尺寸（厘米）
+    #[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ProductDimensions {
+        pub length: u32, // 长
+        pub width: u32,  // 宽
+        pub height: u32, // 高
+    }
+
+    impl ProductDimensions {
+        /// 包装体积（立方厘米）
+        pub fn volume(&self) -> u64 {
+            u64::from(self.length)
+                .saturating_mul(self.width.into())
+                .saturating_mul(self.height.into())
+        }
+    }
+
     /// 商品信息结构体
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     pub struct ProductInfo<T: Config> {
@@ -86,6 +185,7 @@ pub mod pallet {
         pub stock_quantity: u32,                                                                                         // 库存数量
         pub sales_quantity: u32,                                                                                         // 销售数量
         pub weight: u32,                                                                                                 // 重量
+        pub dimensions: Option<ProductDimensions>,                                                                       // 包装尺寸
         pub status: ProductStatus,                                                                                       // 商品状态
         pub profit_ratio: Perbill,                                                                                       // 分润比例
         pub created_date: BlockNumberFor<T>,                                                                             // 创建日期
@@ -116,6 +216,131 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 商品存储押金 [押金账户, 押金金额]
+    #[pallet::storage]
+    #[pallet::getter(fn product_deposits)]
+    pub type ProductDeposits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxProductCodeLength>,     // 商品代码
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionCodeLength>, // 所属机构代码
+        (T::AccountId, BalanceOf<T>),                // 押金账户及金额
+        OptionQuery,
+    >;
+
+    /// 待评价的购买次数，每次已结算的订单购买可评价一次
+    #[pallet::storage]
+    #[pallet::storage_prefix = "PendingReviews"]
+    pub type PendingReviews<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, BoundedVec<u8, T::MaxInstitutionCodeLength>>, // 所属机构代码
+            NMapKey<Blake2_128Concat, BoundedVec<u8, T::MaxProductCodeLength>>,     // 商品代码
+            NMapKey<Blake2_128Concat, T::AccountId>,                                // 购买者
+        ),
+        u32, // 未评价的购买次数
+        ValueQuery,
+    >;
+
+    /// 商品评价汇总 [评价数, 总评分]
+    #[pallet::storage]
+    #[pallet::storage_prefix = "ProductRatings"]
+    pub type ProductRatings<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxProductCodeLength>,     // 商品代码
+        Blake2_128Concat,
+        BoundedVec<u8, T::MaxInstitutionCodeLength>, // 所属机构代码
+        (u32, u32),                                  // 评价数及总评分
+        ValueQuery,
+    >;
+
+    /// 创世配置
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// 初始商品 [商品代码, 机构代码, 创建者, 名称, 分类, 品牌, 原价, 现价, 描述, 主图, 库存数量, 重量, 包装尺寸（长, 宽, 高）]
+        #[allow(clippy::type_complexity)]
+        pub products: Vec<(
+            Vec<u8>,
+            Vec<u8>,
+            T::AccountId,
+            Vec<u8>,
+            Vec<u8>,
+            Vec<u8>,
+            u64,
+            u64,
+            Vec<u8>,
+            Vec<u8>,
+            u32,
+            u32,
+            Option<(u32, u32, u32)>,
+        )>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        /// 写入初始商品（上架状态），押金从创建者余额中冻结
+        fn build(&self) {
+            for (
+                product_code,
+                institution_code,
+                creator,
+                product_name,
+                category,
+                brand,
+                original_price,
+                current_price,
+                description,
+                main_image,
+                stock_quantity,
+                weight,
+                dimensions,
+            ) in &self.products
+            {
+                let bounded_product_code = BoundedVec::<u8, T::MaxProductCodeLength>::try_from(product_code.clone())
+                    .expect("genesis product code too long");
+                let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_code.clone())
+                    .expect("genesis institution code too long");
+
+                assert!(T::Institutions::exists(&bounded_institution_code), "genesis product references unknown institution");
+                assert!(
+                    !Products::<T>::contains_key(&bounded_product_code, &bounded_institution_code),
+                    "duplicate genesis product"
+                );
+                assert!(current_price <= original_price, "genesis product current price exceeds original price");
+
+                let product_info = ProductInfo::<T> {
+                    product_name: product_name.clone().try_into().expect("genesis product name too long"),
+                    category: category.clone().try_into().expect("genesis product category too long"),
+                    brand: brand.clone().try_into().expect("genesis product brand too long"),
+                    authorized_member_groups: BoundedVec::default(),
+                    original_price: *original_price,
+                    current_price: *current_price,
+                    description: description.clone().try_into().expect("genesis product description too long"),
+                    main_image: main_image.clone().try_into().expect("genesis product image url too long"),
+                    detail_images: BoundedVec::default(),
+                    stock_quantity: *stock_quantity,
+                    sales_quantity: 0,
+                    weight: *weight,
+                    dimensions: dimensions.map(|(length, width, height)| ProductDimensions { length, width, height }),
+                    status: ProductStatus::Available,
+                    profit_ratio: Perbill::from_parts(0),
+                    created_date: frame_system::Pallet::<T>::block_number(),
+                    creator: creator.clone(),
+                };
+
+                Pallet::<T>::update_deposit(&bounded_product_code, &bounded_institution_code, creator, product_info.encoded_size())
+                    .expect("genesis product creator cannot cover the deposit");
+                Products::<T>::insert(&bounded_product_code, &bounded_institution_code, &product_info);
+                InstitutionProducts::<T>::mutate(&bounded_institution_code, |products| {
+                    products.try_push(bounded_product_code).expect("genesis institution product list full");
+                });
+            }
+        }
+    }
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -129,6 +354,10 @@ pub mod pallet {
         ProductStockUpdated(BoundedVec<u8, T::MaxProductCodeLength>, BoundedVec<u8, T::MaxInstitutionCodeLength>, u32),
         /// 商品已删除 [商品代码, 机构代码]
         ProductDeleted(BoundedVec<u8, T::MaxProductCodeLength>, BoundedVec<u8, T::MaxInstitutionCodeLength>),
+        /// 商品已评价 [商品代码, 机构代码, 评价者, 评分]
+        ProductReviewed(BoundedVec<u8, T::MaxProductCodeLength>, BoundedVec<u8, T::MaxInstitutionCodeLength>, T::AccountId, u8),
+        /// 商品包装尺寸已更新 [商品代码, 机构代码]
+        ProductDimensionsUpdated(BoundedVec<u8, T::MaxProductCodeLength>, BoundedVec<u8, T::MaxInstitutionCodeLength>),
     }
 
     #[pallet::error]
@@ -155,6 +384,18 @@ pub mod pallet {
         InsufficientStock,
         /// 无效的状态
         InvalidStatus,
+        /// 机构不存在
+        InstitutionNotFound,
+        /// 机构已暂停营业
+        InstitutionSuspended,
+        /// 无效的评分（应为 1-5）
+        InvalidRating,
+        /// 没有可评价的购买记录
+        NoPurchaseToReview,
+        /// 无效的包装尺寸（各边应大于0）
+        InvalidDimensions,
+        /// 无效的购买数量（应大于0）
+        InvalidQuantity,
     }
 
     #[pallet::call]
@@ -189,6 +430,10 @@ pub mod pallet {
             let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_code)
                 .map_err(|_| Error::<T>::StringConversionError)?;
             
+            // 检查机构是否存在及调用者权限
+            ensure!(T::Institutions::exists(&bounded_institution_code), Error::<T>::InstitutionNotFound);
+            Self::ensure_product_manager(&bounded_institution_code, &who)?;
+            
             // 检查商品是否已存在
             ensure!(!Products::<T>::contains_key(&bounded_product_code, &bounded_institution_code), 
                 Error::<T>::ProductAlreadyExists);
@@ -233,7 +478,7 @@ pub mod pallet {
                 .map_err(|_| Error::<T>::TooManyDetailImages)?;
             
             // 创建商品信息
-            let product_info = ProductInfo {
+            let product_info = ProductInfo::<T> {
                 product_name: bounded_product_name,
                 category: bounded_category,
                 brand: bounded_brand,
@@ -246,12 +491,16 @@ pub mod pallet {
                 stock_quantity,
                 sales_quantity: 0,
                 weight,
+                dimensions: None,
                 status: ProductStatus::Available,
                 profit_ratio,
                 created_date: frame_system::Pallet::<T>::block_number(),
                 creator: who.clone(),
             };
             
+            // 按记录大小冻结存储押金
+            Self::update_deposit(&bounded_product_code, &bounded_institution_code, &who, product_info.encoded_size())?;
+            
             // 存储商品信息
             Products::<T>::insert(&bounded_product_code, &bounded_institution_code, &product_info);
             
@@ -299,8 +548,8 @@ pub mod pallet {
             Products::<T>::try_mutate(&bounded_product_code, &bounded_institution_code, |maybe_product| -> DispatchResult {
                 let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
                 
-                // 检查权限
-                ensure!(product.creator == who, Error::<T>::NotAuthorized);
+                // 检查权限（机构商品管理人员）
+                Self::ensure_product_manager(&bounded_institution_code, &who)?;
                 
                 // 更新各字段（如果提供）
                 if let Some(name) = product_name {
@@ -347,6 +596,9 @@ pub mod pallet {
                     product.profit_ratio = pr;
                 }
                 
+                // 按新的记录大小调整押金
+                Self::update_deposit(&bounded_product_code, &bounded_institution_code, &who, product.encoded_size())?;
+                
                 // 发出事件
                 Self::deposit_event(Event::ProductUpdated(bounded_product_code.clone(), bounded_institution_code.clone()));
                 
@@ -377,8 +629,8 @@ pub mod pallet {
             Products::<T>::try_mutate(&bounded_product_code, &bounded_institution_code, |maybe_product| -> DispatchResult {
                 let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
                 
-                // 检查权限
-                ensure!(product.creator == who, Error::<T>::NotAuthorized);
+                // 检查权限（机构商品管理人员）
+                Self::ensure_product_manager(&bounded_institution_code, &who)?;
                 
                 // 转换状态
                 let new_status = match status {
@@ -424,8 +676,8 @@ pub mod pallet {
             Products::<T>::try_mutate(&bounded_product_code, &bounded_institution_code, |maybe_product| -> DispatchResult {
                 let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
                 
-                // 检查权限
-                ensure!(product.creator == who, Error::<T>::NotAuthorized);
+                // 检查权限（机构商品管理人员）
+                Self::ensure_product_manager(&bounded_institution_code, &who)?;
                 
                 // 更新库存
                 product.stock_quantity = new_stock;
@@ -460,18 +712,22 @@ pub mod pallet {
                 .map_err(|_| Error::<T>::StringConversionError)?;
             
             // 获取商品并检查权限
-            let product = Products::<T>::get(&bounded_product_code, &bounded_institution_code)
-                .ok_or(Error::<T>::ProductNotFound)?;
+            ensure!(Products::<T>::contains_key(&bounded_product_code, &bounded_institution_code),
+                Error::<T>::ProductNotFound);
             
-            ensure!(product.creator == who, Error::<T>::NotAuthorized);
+            Self::ensure_product_manager(&bounded_institution_code, &who)?;
             
             // 从机构商品索引中移除
             InstitutionProducts::<T>::mutate(&bounded_institution_code, |products| {
                 products.retain(|code| code != &bounded_product_code);
             });
             
-            // 删除商品
+            // 退还存储押金
+            Self::release_deposit(&bounded_product_code, &bounded_institution_code)?;
+            
+            // 删除商品及评价汇总
             Products::<T>::remove(&bounded_product_code, &bounded_institution_code);
+            ProductRatings::<T>::remove(&bounded_product_code, &bounded_institution_code);
             
             // 发出事件
             Self::deposit_event(Event::ProductDeleted(bounded_product_code, bounded_institution_code));
@@ -479,7 +735,7 @@ pub mod pallet {
             Ok(())
         }
         
-        /// 购买商品（减少库存，增加销售数量）
+        /// 购买商品（减少库存，增加销售数量），评价资格由已结算的订单授予
         #[pallet::call_index(5)]
         #[pallet::weight(5_000)]
         pub fn purchase_product(
@@ -489,7 +745,7 @@ pub mod pallet {
             quantity: u32,
         ) -> DispatchResult {
             // 确认调用者身份
-            let _who = ensure_signed(origin)?;
+            ensure_signed(origin)?;
             
             // 转换为边界向量
             let bounded_product_code = BoundedVec::<u8, T::MaxProductCodeLength>::try_from(product_code)
@@ -498,6 +754,11 @@ pub mod pallet {
             let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_code)
                 .map_err(|_| Error::<T>::StringConversionError)?;
             
+            ensure!(quantity > 0, Error::<T>::InvalidQuantity);
+            
+            // 暂停营业的机构不能交易
+            ensure!(!T::Institutions::is_suspended(&bounded_institution_code), Error::<T>::InstitutionSuspended);
+            
             // 获取并更新商品信息
             Products::<T>::try_mutate(&bounded_product_code, &bounded_institution_code, |maybe_product| -> DispatchResult {
                 let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
@@ -515,5 +776,217 @@ pub mod pallet {
                 Ok(())
             })
         }
+        
+        /// 评价已购买的商品（评分 1-5），评分计入机构信誉
+        #[pallet::call_index(6)]
+        #[pallet::weight(10_000)]
+        pub fn review_product(
+            origin: OriginFor<T>,
+            product_code: Vec<u8>,
+            institution_code: Vec<u8>,
+            rating: u8,
+        ) -> DispatchResult {
+            // 确认调用者身份
+            let who = ensure_signed(origin)?;
+            
+            // 转换为边界向量
+            let bounded_product_code = BoundedVec::<u8, T::MaxProductCodeLength>::try_from(product_code)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_code)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            ensure!((1..=5).contains(&rating), Error::<T>::InvalidRating);
+            ensure!(Products::<T>::contains_key(&bounded_product_code, &bounded_institution_code),
+                Error::<T>::ProductNotFound);
+            
+            // 消耗一次可评价的购买
+            PendingReviews::<T>::try_mutate_exists((&bounded_institution_code, &bounded_product_code, &who), |pending| -> DispatchResult {
+                let remaining = pending.unwrap_or_default().checked_sub(1).ok_or(Error::<T>::NoPurchaseToReview)?;
+                *pending = (remaining > 0).then_some(remaining);
+                Ok(())
+            })?;
+            
+            ProductRatings::<T>::mutate(&bounded_product_code, &bounded_institution_code, |(count, total)| {
+                *count = count.saturating_add(1);
+                *total = total.saturating_add(rating.into());
+            });
+            
+            T::OnCommerceOutcome::on_commerce_outcome(&bounded_institution_code, CommerceOutcome::ProductReviewed(rating));
+            
+            // 发出事件
+            Self::deposit_event(Event::ProductReviewed(bounded_product_code, bounded_institution_code, who, rating));
+            
+            Ok(())
+        }
+        
+        /// 设置或清除商品包装尺寸，用于按体积计算运费
+        #[pallet::call_index(7)]
+        #[pallet::weight(5_000)]
+        pub fn set_product_dimensions(
+            origin: OriginFor<T>,
+            product_code: Vec<u8>,
+            institution_code: Vec<u8>,
+            dimensions: Option<ProductDimensions>,
+        ) -> DispatchResult {
+            // 确认调用者身份
+            let who = ensure_signed(origin)?;
+            
+            // 转换为边界向量
+            let bounded_product_code = BoundedVec::<u8, T::MaxProductCodeLength>::try_from(product_code)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_code)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            
+            // 尺寸各边必须大于0
+            if let Some(d) = &dimensions {
+                ensure!(d.length > 0 && d.width > 0 && d.height > 0, Error::<T>::InvalidDimensions);
+            }
+            
+            Products::<T>::try_mutate(&bounded_product_code, &bounded_institution_code, |maybe_product| -> DispatchResult {
+                let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
+                
+                // 检查权限（机构商品管理人员）
+                Self::ensure_product_manager(&bounded_institution_code, &who)?;
+                
+                product.dimensions = dimensions;
+                
+                // 按新的记录大小调整押金
+                Self::update_deposit(&bounded_product_code, &bounded_institution_code, &who, product.encoded_size())?;
+                
+                Ok(())
+            })?;
+            
+            // 发出事件
+            Self::deposit_event(Event::ProductDimensionsUpdated(bounded_product_code, bounded_institution_code));
+            
+            Ok(())
+        }
+    }
+    
+    // 辅助函数
+    impl<T: Config> Pallet<T> {
+        /// 确认调用者拥有机构的商品管理权限
+        fn ensure_product_manager(
+            institution_code: &BoundedVec<u8, T::MaxInstitutionCodeLength>,
+            who: &T::AccountId,
+        ) -> DispatchResult {
+            ensure!(
+                T::Institutions::has_role(institution_code, who, InstitutionRole::ProductManager),
+                Error::<T>::NotAuthorized
+            );
+            Ok(())
+        }
+        
+        /// 计算存储押金：基础押金 + 每字节押金 × 编码长度
+        fn deposit_for(encoded_len: usize) -> BalanceOf<T> {
+            T::DepositBase::get()
+                .saturating_add(T::DepositPerByte::get().saturating_mul((encoded_len as u32).into()))
+        }
+        
+        /// 按记录的编码长度冻结或调整押金；调用者不是原押金账户时，退还原押金并由调用者重新缴纳
+        fn update_deposit(
+            product_code: &BoundedVec<u8, T::MaxProductCodeLength>,
+            institution_code: &BoundedVec<u8, T::MaxInstitutionCodeLength>,
+            who: &T::AccountId,
+            encoded_len: usize,
+        ) -> DispatchResult {
+            let reason = HoldReason::ProductDeposit.into();
+            let new_deposit = Self::deposit_for(encoded_len);
+            
+            match ProductDeposits::<T>::get(product_code, institution_code) {
+                Some((depositor, old_deposit)) if &depositor == who => {
+                    if new_deposit > old_deposit {
+                        T::Currency::hold(&reason, who, new_deposit.saturating_sub(old_deposit))?;
+                    } else if old_deposit > new_deposit {
+                        T::Currency::release(&reason, who, old_deposit.saturating_sub(new_deposit), Precision::BestEffort)?;
+                    }
+                },
+                Some((depositor, old_deposit)) => {
+                    T::Currency::release(&reason, &depositor, old_deposit, Precision::BestEffort)?;
+                    T::Currency::hold(&reason, who, new_deposit)?;
+                },
+                None => T::Currency::hold(&reason, who, new_deposit)?,
+            }
+            
+            ProductDeposits::<T>::insert(product_code, institution_code, (who.clone(), new_deposit));
+            Ok(())
+        }
+        
+        /// 退还商品的存储押金
+        fn release_deposit(
+            product_code: &BoundedVec<u8, T::MaxProductCodeLength>,
+            institution_code: &BoundedVec<u8, T::MaxInstitutionCodeLength>,
+        ) -> DispatchResult {
+            if let Some((depositor, deposit)) = ProductDeposits::<T>::take(product_code, institution_code) {
+                T::Currency::release(&HoldReason::ProductDeposit.into(), &depositor, deposit, Precision::BestEffort)?;
+            }
+            Ok(())
+        }
+    }
+    
+    impl<T: Config> crate::ProductInspect for Pallet<T> {
+        fn sale_info(product_code: &[u8], institution_code: &[u8]) -> Option<crate::ProductSaleInfo> {
+            let bounded_product_code = BoundedVec::<u8, T::MaxProductCodeLength>::try_from(product_code.to_vec()).ok()?;
+            let bounded_institution_code = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_code.to_vec()).ok()?;
+            let product = Products::<T>::get(&bounded_product_code, &bounded_institution_code)
+                .filter(|product| product.status == ProductStatus::Available)?;
+            Some(crate::ProductSaleInfo {
+                unit_price: product.current_price,
+                unit_weight: product.weight,
+                unit_volume: product.dimensions.map_or(0, |dimensions| dimensions.volume()),
+            })
+        }
+    }
+    
+    impl<T: Config> crate::PurchaseRecorder<T::AccountId> for Pallet<T> {
+        fn record_purchase(product_code: &[u8], institution_code: &[u8], buyer: &T::AccountId) {
+            let Ok(bounded_product_code) = BoundedVec::<u8, T::MaxProductCodeLength>::try_from(product_code.to_vec()) else {
+                return;
+            };
+            let Ok(bounded_institution_code) = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_code.to_vec()) else {
+                return;
+            };
+            if !Products::<T>::contains_key(&bounded_product_code, &bounded_institution_code) {
+                return;
+            }
+            PendingReviews::<T>::mutate((&bounded_institution_code, &bounded_product_code, buyer), |pending| {
+                *pending = pending.saturating_add(1);
+            });
+        }
+    }
+    
+    impl<T: Config> pallet_institution::OnInstitutionRemoved for Pallet<T> {
+        fn can_remove(_institution_id: &[u8]) -> DispatchResult {
+            Ok(())
+        }
+        
+        /// 分批删除机构的商品并退还押金，商品删除完毕后清除待评价记录
+        fn on_institution_removed(institution_id: &[u8], limit: u32) -> u32 {
+            let Ok(bounded_institution_code) = BoundedVec::<u8, T::MaxInstitutionCodeLength>::try_from(institution_id.to_vec()) else {
+                return 0;
+            };
+            
+            let mut product_codes = InstitutionProducts::<T>::get(&bounded_institution_code);
+            let mut removed = 0u32;
+            while removed < limit {
+                let Some(product_code) = product_codes.pop() else {
+                    break;
+                };
+                let _ = Self::release_deposit(&product_code, &bounded_institution_code);
+                Products::<T>::remove(&product_code, &bounded_institution_code);
+                ProductRatings::<T>::remove(&product_code, &bounded_institution_code);
+                Self::deposit_event(Event::ProductDeleted(product_code, bounded_institution_code.clone()));
+                removed += 1;
+            }
+            
+            if product_codes.is_empty() {
+                InstitutionProducts::<T>::remove(&bounded_institution_code);
+            } else {
+                InstitutionProducts::<T>::insert(&bounded_institution_code, product_codes);
+            }
+            removed + PendingReviews::<T>::drain_prefix((&bounded_institution_code,)).take((limit - removed) as usize).count() as u32
+        }
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
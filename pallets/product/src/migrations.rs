@@ -0,0 +1,91 @@
+//! 商品模块存储迁移
+
+/// v1：商品信息增加包装尺寸字段，已有商品均未登记尺寸
+pub mod v1 {
+    use crate::pallet::{Config, Pallet, ProductInfo, ProductStatus, Products};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        traits::UncheckedOnRuntimeUpgrade,
+    };
+    use frame_system::pallet_prelude::BlockNumberFor;
+    use sp_runtime::Perbill;
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec;
+
+    /// v0 版本的商品信息结构
+    #[derive(Encode, Decode)]
+    pub struct OldProductInfo<T: Config> {
+        pub product_name: BoundedVec<u8, T::MaxNameLength>,
+        pub category: BoundedVec<u8, T::MaxCategoryLength>,
+        pub brand: BoundedVec<u8, T::MaxBrandLength>,
+        pub authorized_member_groups: BoundedVec<BoundedVec<u8, T::MaxAuthorizedMemberGroup>, T::MaxAuthorizedGroups>,
+        pub original_price: u64,
+        pub current_price: u64,
+        pub description: BoundedVec<u8, T::MaxDescriptionLength>,
+        pub main_image: BoundedVec<u8, T::MaxImageUrlLength>,
+        pub detail_images: BoundedVec<BoundedVec<u8, T::MaxImageUrlLength>, T::MaxDetailImages>,
+        pub stock_quantity: u32,
+        pub sales_quantity: u32,
+        pub weight: u32,
+        pub status: ProductStatus,
+        pub profit_ratio: Perbill,
+        pub created_date: BlockNumberFor<T>,
+        pub creator: T::AccountId,
+    }
+
+    /// 未检查存储版本的迁移逻辑
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            Ok((Products::<T>::iter_keys().count() as u32).encode())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            Products::<T>::translate::<OldProductInfo<T>, _>(|_, _, old| {
+                translated += 1;
+                Some(ProductInfo {
+                    product_name: old.product_name,
+                    category: old.category,
+                    brand: old.brand,
+                    authorized_member_groups: old.authorized_member_groups,
+                    original_price: old.original_price,
+                    current_price: old.current_price,
+                    description: old.description,
+                    main_image: old.main_image,
+                    detail_images: old.detail_images,
+                    stock_quantity: old.stock_quantity,
+                    sales_quantity: old.sales_quantity,
+                    weight: old.weight,
+                    dimensions: None,
+                    status: old.status,
+                    profit_ratio: old.profit_ratio,
+                    created_date: old.created_date,
+                    creator: old.creator,
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+            let after = Products::<T>::iter_values().count() as u32;
+            ensure!(before == after, "products lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 从 v0 迁移到 v1，仅在链上存储版本为 0 时执行
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
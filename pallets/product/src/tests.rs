@@ -0,0 +1,265 @@
+use crate::{
+    mock::*, Error, Event, HoldReason, PendingReviews, ProductDeposits, ProductDimensions, ProductInspect, ProductRatings, Products,
+    ProductSaleInfo, PurchaseRecorder,
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{fungible::InspectHold, ConstU32},
+    BoundedVec,
+};
+use pallet_institution::InstitutionRole;
+use sp_runtime::Perbill;
+
+fn bounded(id: &[u8]) -> BoundedVec<u8, ConstU32<64>> {
+    BoundedVec::try_from(id.to_vec()).unwrap()
+}
+
+/// 由指定账户在机构 INST_001 上架商品，单价 10
+fn create(who: u64, code: &[u8]) -> frame_support::dispatch::DispatchResult {
+    Product::create_product(
+        RuntimeOrigin::signed(who),
+        code.to_vec(),
+        b"INST_001".to_vec(),
+        b"Tea".to_vec(),
+        b"Food".to_vec(),
+        b"Brand".to_vec(),
+        vec![],
+        12,
+        10,
+        b"Green tea".to_vec(),
+        b"https://example.com/tea.png".to_vec(),
+        vec![],
+        100,
+        500,
+        Perbill::zero(),
+    )
+}
+
+fn update_description(who: u64, code: &[u8], description: &[u8]) -> frame_support::dispatch::DispatchResult {
+    Product::update_product_info(
+        RuntimeOrigin::signed(who),
+        code.to_vec(),
+        b"INST_001".to_vec(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(description.to_vec()),
+        None,
+        None,
+        None,
+    )
+}
+
+fn review(who: u64, code: &[u8], rating: u8) -> frame_support::dispatch::DispatchResult {
+    Product::review_product(RuntimeOrigin::signed(who), code.to_vec(), b"INST_001".to_vec(), rating)
+}
+
+fn pending_reviews(code: &[u8], who: u64) -> u32 {
+    PendingReviews::<Test>::get((bounded(b"INST_001"), bounded(code), who))
+}
+
+fn set_dimensions(who: u64, code: &[u8], dimensions: Option<ProductDimensions>) -> frame_support::dispatch::DispatchResult {
+    Product::set_product_dimensions(RuntimeOrigin::signed(who), code.to_vec(), b"INST_001".to_vec(), dimensions)
+}
+
+fn deposit_on_hold(who: u64) -> u64 {
+    Balances::balance_on_hold(&HoldReason::ProductDeposit.into(), &who)
+}
+
+/// 商品记录当前应缴的押金：基础押金 10 + 每字节 1
+fn expected_deposit(code: &[u8]) -> u64 {
+    10 + Products::<Test>::get(bounded(code), bounded(b"INST_001")).unwrap().encoded_size() as u64
+}
+
+#[test]
+fn product_deposit_follows_record_size() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"TEA"));
+        System::assert_last_event(Event::ProductCreated(bounded(b"TEA"), bounded(b"INST_001"), 1).into());
+
+        let deposit = expected_deposit(b"TEA");
+        assert_eq!(deposit_on_hold(1), deposit);
+        assert_eq!(ProductDeposits::<Test>::get(bounded(b"TEA"), bounded(b"INST_001")), Some((1, deposit)));
+
+        // 记录变长时补缴，变短时退还差额
+        assert_ok!(update_description(1, b"TEA", b"Green tea from the Pearl River Delta"));
+        assert_eq!(deposit_on_hold(1), deposit + 27);
+        assert_ok!(update_description(1, b"TEA", b"Tea"));
+        assert_eq!(deposit_on_hold(1), deposit - 6);
+        assert_eq!(deposit_on_hold(1), expected_deposit(b"TEA"));
+    });
+}
+
+#[test]
+fn product_deposit_moves_to_last_editor() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2, InstitutionRole::ProductManager));
+        assert_ok!(create(1, b"TEA"));
+        let institution_deposit = Balances::total_balance_on_hold(&1) - deposit_on_hold(1);
+
+        // 其他商品管理人员修改后由其缴纳押金，原押金退还
+        assert_ok!(update_description(2, b"TEA", b"Green tea"));
+        assert_eq!(deposit_on_hold(1), 0);
+        assert_eq!(deposit_on_hold(2), expected_deposit(b"TEA"));
+        assert_eq!(Balances::total_balance_on_hold(&1), institution_deposit);
+        assert_eq!(ProductDeposits::<Test>::get(bounded(b"TEA"), bounded(b"INST_001")).unwrap().0, 2);
+
+        // 无权限的账户不能修改，押金不变
+        assert_noop!(update_description(3, b"TEA", b"Fake tea"), Error::<Test>::NotAuthorized);
+    });
+}
+
+#[test]
+fn deleting_product_releases_deposit() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"TEA"));
+        assert_ok!(create(1, b"CUP"));
+        let cup_deposit = expected_deposit(b"CUP");
+
+        assert_ok!(Product::delete_product(RuntimeOrigin::signed(1), b"TEA".to_vec(), b"INST_001".to_vec()));
+        System::assert_last_event(Event::ProductDeleted(bounded(b"TEA"), bounded(b"INST_001")).into());
+        assert_eq!(deposit_on_hold(1), cup_deposit);
+        assert!(!ProductDeposits::<Test>::contains_key(bounded(b"TEA"), bounded(b"INST_001")));
+        assert_noop!(
+            Product::delete_product(RuntimeOrigin::signed(1), b"TEA".to_vec(), b"INST_001".to_vec()),
+            Error::<Test>::ProductNotFound
+        );
+    });
+}
+
+#[test]
+fn removing_institution_releases_product_deposits() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(Institution::grant_role(RuntimeOrigin::signed(1), b"INST_001".to_vec(), 2, InstitutionRole::ProductManager));
+        assert_ok!(create(1, b"TEA"));
+        assert_ok!(create(2, b"CUP"));
+
+        assert_ok!(Institution::delete_institution(RuntimeOrigin::signed(1), b"INST_001".to_vec()));
+        assert_eq!(Balances::total_balance_on_hold(&1), 0);
+        assert_eq!(deposit_on_hold(2), 0);
+        assert_eq!(ProductDeposits::<Test>::iter().count(), 0);
+        assert_eq!(Products::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn purchase_updates_stock_without_review_credit() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"TEA"));
+
+        assert_noop!(
+            Product::purchase_product(RuntimeOrigin::signed(3), b"TEA".to_vec(), b"INST_001".to_vec(), 0),
+            Error::<Test>::InvalidQuantity
+        );
+        assert_noop!(
+            Product::purchase_product(RuntimeOrigin::signed(3), b"TEA".to_vec(), b"INST_001".to_vec(), 101),
+            Error::<Test>::InsufficientStock
+        );
+
+        assert_ok!(Product::purchase_product(RuntimeOrigin::signed(3), b"TEA".to_vec(), b"INST_001".to_vec(), 2));
+        let product = Products::<Test>::get(bounded(b"TEA"), bounded(b"INST_001")).unwrap();
+        assert_eq!((product.stock_quantity, product.sales_quantity), (98, 2));
+
+        // 直接购买不授予评价资格，评价资格只由已结算的订单授予
+        assert_eq!(pending_reviews(b"TEA", 3), 0);
+        assert_noop!(review(3, b"TEA", 5), Error::<Test>::NoPurchaseToReview);
+    });
+}
+
+#[test]
+fn settled_purchase_grants_one_review() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"TEA"));
+
+        // 不存在的商品不授予评价资格
+        Product::record_purchase(b"MUG", b"INST_001", &3);
+        assert_eq!(pending_reviews(b"MUG", 3), 0);
+
+        Product::record_purchase(b"TEA", b"INST_001", &3);
+        Product::record_purchase(b"TEA", b"INST_001", &3);
+        assert_eq!(pending_reviews(b"TEA", 3), 2);
+
+        assert_noop!(review(3, b"TEA", 0), Error::<Test>::InvalidRating);
+        assert_noop!(review(3, b"TEA", 6), Error::<Test>::InvalidRating);
+        assert_noop!(review(4, b"TEA", 5), Error::<Test>::NoPurchaseToReview);
+
+        assert_ok!(review(3, b"TEA", 5));
+        System::assert_last_event(Event::ProductReviewed(bounded(b"TEA"), bounded(b"INST_001"), 3, 5).into());
+        assert_ok!(review(3, b"TEA", 2));
+        assert_noop!(review(3, b"TEA", 4), Error::<Test>::NoPurchaseToReview);
+        assert!(!PendingReviews::<Test>::contains_key((bounded(b"INST_001"), bounded(b"TEA"), 3)));
+
+        // 评分汇总并计入机构信誉
+        assert_eq!(ProductRatings::<Test>::get(bounded(b"TEA"), bounded(b"INST_001")), (2, 7));
+        let record = InstitutionReputation::reputation(b"INST_001").unwrap();
+        assert_eq!((record.review_count, record.review_total), (2, 7));
+    });
+}
+
+#[test]
+fn deleting_product_clears_ratings() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"TEA"));
+        Product::record_purchase(b"TEA", b"INST_001", &3);
+        assert_ok!(review(3, b"TEA", 4));
+
+        assert_ok!(Product::delete_product(RuntimeOrigin::signed(1), b"TEA".to_vec(), b"INST_001".to_vec()));
+        assert!(!ProductRatings::<Test>::contains_key(bounded(b"TEA"), bounded(b"INST_001")));
+        assert_noop!(review(3, b"TEA", 4), Error::<Test>::ProductNotFound);
+    });
+}
+
+#[test]
+fn dimensions_feed_sale_info_volume() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"TEA"));
+        assert_eq!(
+            Product::sale_info(b"TEA", b"INST_001"),
+            Some(ProductSaleInfo { unit_price: 10, unit_weight: 500, unit_volume: 0 })
+        );
+
+        assert_ok!(set_dimensions(1, b"TEA", Some(ProductDimensions { length: 30, width: 20, height: 10 })));
+        System::assert_last_event(Event::ProductDimensionsUpdated(bounded(b"TEA"), bounded(b"INST_001")).into());
+        assert_eq!(Product::sale_info(b"TEA", b"INST_001").unwrap().unit_volume, 6_000);
+        assert_eq!(deposit_on_hold(1), expected_deposit(b"TEA"));
+
+        // 清除尺寸后体积归零
+        assert_ok!(set_dimensions(1, b"TEA", None));
+        assert_eq!(Product::sale_info(b"TEA", b"INST_001").unwrap().unit_volume, 0);
+        assert_eq!(deposit_on_hold(1), expected_deposit(b"TEA"));
+
+        // 下架的商品不可售
+        assert_ok!(Product::update_product_status(RuntimeOrigin::signed(1), b"TEA".to_vec(), b"INST_001".to_vec(), 1));
+        assert_eq!(Product::sale_info(b"TEA", b"INST_001"), None);
+    });
+}
+
+#[test]
+fn invalid_dimensions_are_rejected() {
+    new_test_ext().execute_with(|| {
+        create_institution(1, b"INST_001");
+        assert_ok!(create(1, b"TEA"));
+
+        for dimensions in [
+            ProductDimensions { length: 0, width: 20, height: 10 },
+            ProductDimensions { length: 30, width: 0, height: 10 },
+            ProductDimensions { length: 30, width: 20, height: 0 },
+        ] {
+            assert_noop!(set_dimensions(1, b"TEA", Some(dimensions)), Error::<Test>::InvalidDimensions);
+        }
+        let dimensions = Some(ProductDimensions { length: 30, width: 20, height: 10 });
+        assert_noop!(set_dimensions(3, b"TEA", dimensions), Error::<Test>::NotAuthorized);
+        assert_noop!(set_dimensions(1, b"MUG", dimensions), Error::<Test>::ProductNotFound);
+    });
+}
@@ -0,0 +1,305 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// 行政区划地区库模块
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use sp_std::vec::Vec;
+
+/// 供其他业务模块调用的地区查询接口
+pub trait RegionInspect {
+    /// 地区编码是否已登记
+    fn exists(code: &[u8]) -> bool;
+
+    /// 地区编码及其各级上级编码，由具体到粗略排列；未登记的编码只返回自身
+    fn lineage(code: &[u8]) -> Vec<Vec<u8>>;
+}
+
+/// 未接入地区库时任何编码都视为未登记
+impl RegionInspect for () {
+    fn exists(_code: &[u8]) -> bool {
+        false
+    }
+
+    fn lineage(code: &[u8]) -> Vec<Vec<u8>> {
+        sp_std::vec![code.to_vec()]
+    }
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use codec::DecodeWithMemTracking;
+    use frame_support::{pallet_prelude::*, traits::Get};
+    use frame_system::pallet_prelude::*;
+    use scale_info::TypeInfo;
+    use sp_std::prelude::*;
+    use sp_std::vec::Vec;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + scale_info::TypeInfo {
+        /// 事件类型
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// 地区编码最大长度
+        #[pallet::constant]
+        type MaxRegionCodeLength: Get<u32>;
+
+        /// 地区名称最大长度
+        #[pallet::constant]
+        type MaxRegionNameLength: Get<u32>;
+
+        /// 维护地区库的特权来源
+        type RegionOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// 地区编码
+    pub type RegionCode<T> = BoundedVec<u8, <T as Config>::MaxRegionCodeLength>;
+
+    /// 行政区划层级
+    #[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum RegionLevel {
+        /// 国家
+        Country,
+        /// 省级
+        Province,
+        /// 地级市
+        City,
+        /// 区县
+        District,
+    }
+
+    impl RegionLevel {
+        /// 下一级层级，区县没有下级
+        pub fn child(self) -> Option<RegionLevel> {
+            match self {
+                RegionLevel::Country => Some(RegionLevel::Province),
+                RegionLevel::Province => Some(RegionLevel::City),
+                RegionLevel::City => Some(RegionLevel::District),
+                RegionLevel::District => None,
+            }
+        }
+    }
+
+    /// 地区信息
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Region<T: Config> {
+        pub name: BoundedVec<u8, T::MaxRegionNameLength>, // 地区名称
+        pub level: RegionLevel,                           // 行政区划层级
+        pub parent: Option<RegionCode<T>>,                // 上级地区编码，国家为空
+    }
+
+    /// 地区存储映射，主键为标准行政区划编码
+    #[pallet::storage]
+    #[pallet::storage_prefix = "Regions"]
+    pub type Regions<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RegionCode<T>, // 主键：地区编码
+        Region<T>,     // 值：地区信息
+        OptionQuery,   // 查询策略：如果键不存在，返回 None
+    >;
+
+    /// 下级地区索引
+    #[pallet::storage]
+    #[pallet::storage_prefix = "RegionChildren"]
+    pub type RegionChildren<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        RegionCode<T>, // 主键：上级地区编码
+        Blake2_128Concat,
+        RegionCode<T>, // 次键：下级地区编码
+        (),
+        OptionQuery,
+    >;
+
+    /// 创世地区 [地区编码, 地区名称, 上级地区编码]
+    pub type GenesisRegion = (Vec<u8>, Vec<u8>, Option<Vec<u8>>);
+
+    /// 创世配置
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// 初始地区 [地区编码, 地区名称, 上级地区编码]，上级地区须排在下级之前
+        pub regions: Vec<GenesisRegion>,
+        #[serde(skip)]
+        pub _config: core::marker::PhantomData<T>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        /// 按顺序登记初始地区
+        fn build(&self) {
+            for (code, name, parent) in &self.regions {
+                let code = RegionCode::<T>::try_from(code.clone()).expect("genesis region code too long");
+                let name = BoundedVec::<u8, T::MaxRegionNameLength>::try_from(name.clone())
+                    .expect("genesis region name too long");
+                let parent = parent
+                    .clone()
+                    .map(|parent| RegionCode::<T>::try_from(parent).expect("genesis parent region code too long"));
+                Pallet::<T>::do_add_region(code, name, parent).expect("invalid genesis region");
+            }
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// 地区已登记 [地区编码, 行政区划层级]
+        RegionAdded(RegionCode<T>, RegionLevel),
+        /// 地区名称已更新 [地区编码]
+        RegionRenamed(RegionCode<T>),
+        /// 地区已删除 [地区编码]
+        RegionRemoved(RegionCode<T>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// 字符串转换错误
+        StringConversionError,
+        /// 地区已存在
+        RegionAlreadyExists,
+        /// 地区不存在
+        RegionNotFound,
+        /// 上级地区不存在
+        ParentNotFound,
+        /// 区县下不能再登记下级地区
+        MaxDepthReached,
+        /// 地区仍有下级地区，不能删除
+        RegionHasChildren,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// 登记地区，未指定上级时为国家，否则层级为上级的下一级
+        #[pallet::call_index(0)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn add_region(
+            origin: OriginFor<T>,
+            code: Vec<u8>,
+            name: Vec<u8>,
+            parent: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            // 检查特权来源
+            T::RegionOrigin::ensure_origin(origin)?;
+
+            // 转换为边界向量
+            let bounded_code = RegionCode::<T>::try_from(code).map_err(|_| Error::<T>::StringConversionError)?;
+            let bounded_name = BoundedVec::<u8, T::MaxRegionNameLength>::try_from(name)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+            let bounded_parent = match parent {
+                Some(p) => Some(RegionCode::<T>::try_from(p).map_err(|_| Error::<T>::StringConversionError)?),
+                None => None,
+            };
+
+            let level = Self::do_add_region(bounded_code.clone(), bounded_name, bounded_parent)?;
+
+            // 发出事件
+            Self::deposit_event(Event::RegionAdded(bounded_code, level));
+
+            Ok(())
+        }
+
+        /// 更新地区名称
+        #[pallet::call_index(1)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn rename_region(origin: OriginFor<T>, code: Vec<u8>, name: Vec<u8>) -> DispatchResult {
+            // 检查特权来源
+            T::RegionOrigin::ensure_origin(origin)?;
+
+            // 转换为边界向量
+            let bounded_code = RegionCode::<T>::try_from(code).map_err(|_| Error::<T>::StringConversionError)?;
+            let bounded_name = BoundedVec::<u8, T::MaxRegionNameLength>::try_from(name)
+                .map_err(|_| Error::<T>::StringConversionError)?;
+
+            Regions::<T>::try_mutate(&bounded_code, |maybe_region| -> DispatchResult {
+                let region = maybe_region.as_mut().ok_or(Error::<T>::RegionNotFound)?;
+                region.name = bounded_name;
+                Ok(())
+            })?;
+
+            // 发出事件
+            Self::deposit_event(Event::RegionRenamed(bounded_code));
+
+            Ok(())
+        }
+
+        /// 删除没有下级地区的地区
+        #[pallet::call_index(2)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn remove_region(origin: OriginFor<T>, code: Vec<u8>) -> DispatchResult {
+            // 检查特权来源
+            T::RegionOrigin::ensure_origin(origin)?;
+
+            // 转换为边界向量
+            let bounded_code = RegionCode::<T>::try_from(code).map_err(|_| Error::<T>::StringConversionError)?;
+
+            let region = Regions::<T>::get(&bounded_code).ok_or(Error::<T>::RegionNotFound)?;
+            ensure!(RegionChildren::<T>::iter_prefix(&bounded_code).next().is_none(), Error::<T>::RegionHasChildren);
+
+            if let Some(parent) = region.parent {
+                RegionChildren::<T>::remove(&parent, &bounded_code);
+            }
+            Regions::<T>::remove(&bounded_code);
+
+            // 发出事件
+            Self::deposit_event(Event::RegionRemoved(bounded_code));
+
+            Ok(())
+        }
+    }
+
+    // 辅助函数
+    impl<T: Config> Pallet<T> {
+        /// 登记地区并维护下级索引，返回地区层级
+        fn do_add_region(
+            code: RegionCode<T>,
+            name: BoundedVec<u8, T::MaxRegionNameLength>,
+            parent: Option<RegionCode<T>>,
+        ) -> Result<RegionLevel, DispatchError> {
+            ensure!(!Regions::<T>::contains_key(&code), Error::<T>::RegionAlreadyExists);
+
+            let level = match &parent {
+                Some(parent_code) => {
+                    let parent_region = Regions::<T>::get(parent_code).ok_or(Error::<T>::ParentNotFound)?;
+                    parent_region.level.child().ok_or(Error::<T>::MaxDepthReached)?
+                },
+                None => RegionLevel::Country,
+            };
+
+            if let Some(parent_code) = &parent {
+                RegionChildren::<T>::insert(parent_code, &code, ());
+            }
+            Regions::<T>::insert(&code, Region { name, level, parent });
+
+            Ok(level)
+        }
+    }
+
+    impl<T: Config> crate::RegionInspect for Pallet<T> {
+        fn exists(code: &[u8]) -> bool {
+            RegionCode::<T>::try_from(code.to_vec()).is_ok_and(|bounded| Regions::<T>::contains_key(&bounded))
+        }
+
+        fn lineage(code: &[u8]) -> Vec<Vec<u8>> {
+            let mut lineage = sp_std::vec![code.to_vec()];
+            let mut current = RegionCode::<T>::try_from(code.to_vec())
+                .ok()
+                .and_then(|bounded| Regions::<T>::get(&bounded))
+                .and_then(|region| region.parent);
+            while let Some(parent) = current {
+                current = Regions::<T>::get(&parent).and_then(|region| region.parent);
+                lineage.push(parent.into_inner());
+            }
+            lineage
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{mock::*, Error, Event, RegionChildren, RegionCode, RegionInspect, RegionLevel, Regions};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+fn code(code: &[u8]) -> RegionCode<Test> {
+    RegionCode::<Test>::try_from(code.to_vec()).unwrap()
+}
+
+fn add(code: &[u8], parent: Option<&[u8]>) -> frame_support::dispatch::DispatchResult {
+    Region::add_region(RuntimeOrigin::root(), code.to_vec(), b"Region".to_vec(), parent.map(|p| p.to_vec()))
+}
+
+#[test]
+fn genesis_builds_region_tree() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Regions::<Test>::get(code(b"CN")).unwrap().level, RegionLevel::Country);
+        assert_eq!(Regions::<Test>::get(code(b"440000")).unwrap().level, RegionLevel::Province);
+        let city = Regions::<Test>::get(code(b"440300")).unwrap();
+        assert_eq!(city.level, RegionLevel::City);
+        assert_eq!(city.parent, Some(code(b"440000")));
+        assert!(RegionChildren::<Test>::contains_key(code(b"440000"), code(b"440300")));
+    });
+}
+
+#[test]
+fn level_follows_parent() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(add(b"440305", Some(b"440300")));
+        System::assert_last_event(Event::RegionAdded(code(b"440305"), RegionLevel::District).into());
+
+        assert_ok!(add(b"US", None));
+        assert_eq!(Regions::<Test>::get(code(b"US")).unwrap().level, RegionLevel::Country);
+    });
+}
+
+#[test]
+fn invalid_tree_is_rejected() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(add(b"CN", None), Error::<Test>::RegionAlreadyExists);
+        assert_noop!(add(b"510000", Some(b"XX")), Error::<Test>::ParentNotFound);
+        assert_noop!(add(b"REGION_CODE_TOO_LONG", None), Error::<Test>::StringConversionError);
+
+        // 区县下不能再登记下级地区
+        assert_ok!(add(b"440305", Some(b"440300")));
+        assert_noop!(add(b"44030501", Some(b"440305")), Error::<Test>::MaxDepthReached);
+    });
+}
+
+#[test]
+fn only_region_origin_maintains_regions() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Region::add_region(RuntimeOrigin::signed(1), b"US".to_vec(), b"USA".to_vec(), None),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Region::rename_region(RuntimeOrigin::signed(1), b"CN".to_vec(), b"PRC".to_vec()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(Region::remove_region(RuntimeOrigin::signed(1), b"440300".to_vec()), DispatchError::BadOrigin);
+    });
+}
+
+#[test]
+fn rename_and_remove_region() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Region::rename_region(RuntimeOrigin::root(), b"440300".to_vec(), b"Pengcheng".to_vec()));
+        assert_eq!(Regions::<Test>::get(code(b"440300")).unwrap().name.into_inner(), b"Pengcheng".to_vec());
+        assert_noop!(
+            Region::rename_region(RuntimeOrigin::root(), b"XX".to_vec(), b"Nowhere".to_vec()),
+            Error::<Test>::RegionNotFound
+        );
+
+        // 仍有下级地区时不能删除
+        assert_noop!(Region::remove_region(RuntimeOrigin::root(), b"440000".to_vec()), Error::<Test>::RegionHasChildren);
+
+        assert_ok!(Region::remove_region(RuntimeOrigin::root(), b"440300".to_vec()));
+        System::assert_last_event(Event::RegionRemoved(code(b"440300")).into());
+        assert!(!Regions::<Test>::contains_key(code(b"440300")));
+        assert!(!RegionChildren::<Test>::contains_key(code(b"440000"), code(b"440300")));
+        assert_ok!(Region::remove_region(RuntimeOrigin::root(), b"440000".to_vec()));
+        assert_noop!(Region::remove_region(RuntimeOrigin::root(), b"440000".to_vec()), Error::<Test>::RegionNotFound);
+    });
+}
+
+#[test]
+fn lineage_runs_from_region_to_country() {
+    new_test_ext().execute_with(|| {
+        assert!(Region::exists(b"440300"));
+        assert!(!Region::exists(b"XX"));
+        assert!(!Region::exists(b"REGION_CODE_TOO_LONG"));
+
+        assert_eq!(Region::lineage(b"440300"), vec![b"440300".to_vec(), b"440000".to_vec(), b"CN".to_vec()]);
+        assert_eq!(Region::lineage(b"CN"), vec![b"CN".to_vec()]);
+        // 未登记的编码只返回自身
+        assert_eq!(Region::lineage(b"XX"), vec![b"XX".to_vec()]);
+    });
+}
+
+#[test]
+fn unit_inspector_knows_no_regions() {
+    assert!(!<() as RegionInspect>::exists(b"CN"));
+    assert_eq!(<() as RegionInspect>::lineage(b"CN"), vec![b"CN".to_vec()]);
+}
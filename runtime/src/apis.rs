@@ -42,8 +42,9 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Aura, Balance, Block, Executive, Grandpa, InherentDataExt, Nonce, Runtime,
-	RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment, VERSION,
+	AccountId, Aura, Balance, Block, BlockNumber, Executive, Grandpa, InherentDataExt, Institution,
+	InstitutionFreightTemplate, InstitutionReputation, Nonce, Runtime, RuntimeCall,
+	RuntimeGenesisConfig, SessionKeys, System, TransactionPayment, VERSION,
 };
 
 impl_runtime_apis! {
@@ -220,6 +221,48 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_institution::runtime_api::InstitutionDirectoryApi<
+		Block,
+		AccountId,
+		pallet_institution::InstitutionInfo<Runtime>,
+	> for Runtime {
+		fn institution(institution_id: Vec<u8>) -> Option<pallet_institution::InstitutionInfo<Runtime>> {
+			Institution::institution(institution_id)
+		}
+
+		fn institution_page(
+			filter: pallet_institution::InstitutionFilter<AccountId>,
+			cursor: Option<Vec<u8>>,
+			limit: u32,
+		) -> pallet_institution::InstitutionPage<pallet_institution::InstitutionInfo<Runtime>> {
+			Institution::institution_page(filter, cursor, limit)
+		}
+	}
+
+	impl pallet_institution_reputation::runtime_api::InstitutionReputationApi<Block, BlockNumber> for Runtime {
+		fn reputation_score(institution_id: Vec<u8>) -> u32 {
+			InstitutionReputation::reputation_score(&institution_id)
+		}
+
+		fn reputation(
+			institution_id: Vec<u8>,
+		) -> Option<pallet_institution_reputation::ReputationRecord<BlockNumber>> {
+			InstitutionReputation::reputation(&institution_id)
+		}
+	}
+
+	impl pallet_institution_freight_template::runtime_api::FreightApi<Block> for Runtime {
+		fn calculate_freight(
+			institution_id: Vec<u8>,
+			template_id: Vec<u8>,
+			destination_region: Vec<u8>,
+			items: Vec<pallet_institution_freight_template::FreightItem>,
+		) -> Result<u64, sp_runtime::DispatchError> {
+			InstitutionFreightTemplate::calculate_freight(&institution_id, &template_id, &destination_region, &items)
+				.map(|quote| quote.fee)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -25,24 +25,30 @@
 
 // Substrate and Polkadot dependencies
 use frame_support::{
-	derive_impl, parameter_types,
+	derive_impl, parameter_types, PalletId,
 	traits::{ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, VariantCountOf},
 	weights::{
 		constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND},
 		IdentityFee, Weight,
 	},
 };
-use frame_system::limits::{BlockLength, BlockWeights};
+use frame_system::{
+	limits::{BlockLength, BlockWeights},
+	EnsureRoot,
+};
 use pallet_transaction_payment::{ConstFeeMultiplier, FungibleAdapter, Multiplier};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_runtime::{traits::One, Perbill};
+use sp_runtime::{
+	traits::{AccountIdConversion, One},
+	Perbill,
+};
 use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Aura, Balance, Balances, Block, BlockNumber, Hash, Institution, InstitutionFreightTemplate, InstitutionPaymentMethod, Order, Product, C2cToken, C2cOrder, Nonce, PalletInfo, Runtime,
+	AccountId, Aura, Balance, Balances, Block, BlockNumber, Hash, Institution, InstitutionFreightTemplate, InstitutionPaymentMethod, InstitutionReputation, Order, Product, Region, Carrier, C2cToken, C2cOrder, Nonce, PalletInfo, Runtime,
 	RuntimeCall, RuntimeEvent, RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask,
-	System, EXISTENTIAL_DEPOSIT, SLOT_DURATION, VERSION,
+	System, DAYS, EXISTENTIAL_DEPOSIT, MICRO_UNIT, SLOT_DURATION, UNIT, VERSION,
 };
 
 const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
@@ -163,6 +169,16 @@ impl pallet_template::Config for Runtime {
 	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const InstitutionDepositBase: Balance = 10 * UNIT;
+	pub const ProductDepositBase: Balance = UNIT;
+	pub const FreightTemplateDepositBase: Balance = UNIT;
+	pub const StorageDepositPerByte: Balance = 10 * MICRO_UNIT;
+	pub const OrderAmountUnit: Balance = UNIT;
+	pub PlatformAccount: AccountId = PalletId(*b"fm/pltfm").into_account_truncating();
+	pub const ReputationDecayRetention: Perbill = Perbill::from_percent(90);
+}
+
 /// Configure the pallet-institution in pallets/institution.
 impl pallet_institution::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -171,12 +187,67 @@ impl pallet_institution::Config for Runtime {
 	type MaxResponsiblePersonLength = ConstU32<128>;
 	type MaxBusinessScopeLength = ConstU32<512>;
 	type MaxContractLength = ConstU32<1024>;
+	type MaxBeneficiaries = ConstU32<16>;
+	type PlatformAccount = PlatformAccount;
+	type CertifyOrigin = EnsureRoot<AccountId>;
+	type MaxRevocationReasonLength = ConstU32<256>;
+	type RegistrarOrigin = EnsureRoot<AccountId>;
+	type MaxRegistrars = ConstU32<16>;
+	type ApprovalThreshold = ConstU32<2>;
+	type RejectionThreshold = ConstU32<2>;
+	type MaxAuditCommentLength = ConstU32<256>;
+	type MaxAuditHistory = ConstU32<10>;
+	type OwnerTransferPeriod = ConstU32<{ 7 * DAYS }>;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type DepositBase = InstitutionDepositBase;
+	type DepositPerByte = StorageDepositPerByte;
+	type OnInstitutionRemoved = (
+		Product,
+		Order,
+		C2cToken,
+		C2cOrder,
+		InstitutionFreightTemplate,
+		InstitutionPaymentMethod,
+		InstitutionReputation,
+	);
+	type MaxAuthorityCodeLength = ConstU32<32>;
+	type MaxRegistrationNumberLength = ConstU32<64>;
+	type LicenseExpiryWarningPeriod = ConstU32<{ 30 * DAYS }>;
+	type MaxLicenseExpiriesPerBlock = ConstU32<64>;
+	type MaxHierarchyDepth = ConstU32<4>;
+	type MaxBranches = ConstU32<256>;
+	type MaxRevisions = ConstU32<20>;
+	type SuspendOrigin = EnsureRoot<AccountId>;
+	type MaxSuspensionReasonLength = ConstU32<256>;
+	type MaxSuspensionsPerBlock = ConstU32<64>;
+	type AttestorOrigin = EnsureRoot<AccountId>;
+	type MaxAttestors = ConstU32<16>;
+	type MaxDirectoryPageSize = ConstU32<100>;
+	type MaxDirectoryScan = ConstU32<1000>;
+	type CleanupBatchSize = ConstU32<100>;
+	type WeightInfo = pallet_institution::weights::SubstrateWeight<Runtime>;
 }
 
 /// Configure the pallet-institution-freight-template in pallets/institution-freight-template.
 impl pallet_institution_freight_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type MaxCustomAreaLength = ConstU32<128>;
+	type MaxInstitutionIdLength = ConstU32<64>;
+	type MaxTemplateIdLength = ConstU32<128>;
+	type MaxTemplateNameLength = ConstU32<128>;
+	type MaxRegionCodeLength = ConstU32<16>;
+	type MaxRegionsPerRule = ConstU32<64>;
+	type MaxRegionalRules = ConstU32<32>;
+	type MaxFreeShippingConditions = ConstU32<4>;
+	type MaxExcludedRegions = ConstU32<64>;
+	type MaxSurchargeRules = ConstU32<16>;
+	type Institutions = Institution;
+	type Regions = Region;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type DepositBase = FreightTemplateDepositBase;
+	type DepositPerByte = StorageDepositPerByte;
+	type WeightInfo = pallet_institution_freight_template::weights::SubstrateWeight<Runtime>;
 }
 
 /// Configure the pallet-institution-payment-method in pallets/institution-payment-method.
@@ -184,6 +255,10 @@ impl pallet_institution_payment_method::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type MaxInstitutionIdLength = ConstU32<64>;
 	type MaxPaymentLength = ConstU32<256>;
+	type MaxPaymentEntries = ConstU32<16>;
+	type MaxPaymentLabelLength = ConstU32<64>;
+	type Institutions = Institution;
+	type WeightInfo = pallet_institution_payment_method::weights::SubstrateWeight<Runtime>;
 }
 
 /// Configure the pallet-order in pallets/order.
@@ -193,8 +268,17 @@ impl pallet_order::Config for Runtime {
 	type MaxMemberCodeLength = ConstU32<64>;
 	type MaxInstitutionIdLength = ConstU32<64>;
 	type MaxOrderItems = ConstU32<100>;
-	type MaxExpressCompanyLength = ConstU32<128>;
+	type MaxCarrierCodeLength = ConstU32<32>;
 	type MaxExpressNumberLength = ConstU32<128>;
+	type Institutions = Institution;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type AmountUnit = OrderAmountUnit;
+	type ProfitDistributor = Institution;
+	type OnCommerceOutcome = InstitutionReputation;
+	type FreightCalculator = InstitutionFreightTemplate;
+	type Products = Product;
+	type Carriers = Carrier;
 }
 
 /// Configure the pallet-product in pallets/product.
@@ -210,6 +294,12 @@ impl pallet_product::Config for Runtime {
 	type MaxDescriptionLength = ConstU32<1024>;
 	type MaxImageUrlLength = ConstU32<512>;
 	type MaxDetailImages = ConstU32<10>;
+	type Institutions = Institution;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type DepositBase = ProductDepositBase;
+	type DepositPerByte = StorageDepositPerByte;
+	type OnCommerceOutcome = InstitutionReputation;
 }
 
 /// Configure the pallet-c2c-token in pallets/c2c-token.
@@ -219,6 +309,9 @@ impl pallet_c2c_token::Config for Runtime {
 	type MaxInstitutionCodeLength = ConstU32<64>;
 	type MaxNameLength = ConstU32<256>;
 	type MaxCategoryLength = ConstU32<128>;
+	type Institutions = Institution;
+	type Reputation = InstitutionReputation;
+	type MinListingScore = ConstU32<0>;
 }
 
 /// Configure the pallet-c2c-order in pallets/c2c-order.
@@ -227,4 +320,34 @@ impl pallet_c2c_order::Config for Runtime {
 	type MaxOrderCodeLength = ConstU32<64>;
 	type MaxMemberCodeLength = ConstU32<64>;
 	type MaxInstitutionIdLength = ConstU32<64>;
+	type Institutions = Institution;
+	type OnCommerceOutcome = InstitutionReputation;
+	type ArbitrationOrigin = EnsureRoot<AccountId>;
+}
+
+/// Configure the pallet-institution-reputation in pallets/institution-reputation.
+impl pallet_institution_reputation::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MaxInstitutionIdLength = ConstU32<64>;
+	type DecayPeriod = ConstU32<{ 30 * DAYS }>;
+	type DecayRetention = ReputationDecayRetention;
+	type Institutions = Institution;
+}
+
+/// Configure the pallet-region in pallets/region.
+impl pallet_region::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MaxRegionCodeLength = ConstU32<16>;
+	type MaxRegionNameLength = ConstU32<64>;
+	type RegionOrigin = EnsureRoot<AccountId>;
+}
+
+/// Configure the pallet-carrier in pallets/carrier.
+impl pallet_carrier::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MaxCarrierCodeLength = ConstU32<32>;
+	type MaxCarrierNameLength = ConstU32<64>;
+	type MaxTrackingPrefixLength = ConstU32<8>;
+	type MaxTrackingPrefixes = ConstU32<8>;
+	type CarrierOrigin = EnsureRoot<AccountId>;
 }
@@ -15,7 +15,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{AccountId, BalancesConfig, RuntimeGenesisConfig, SudoConfig};
+use crate::{
+	AccountId, BalancesConfig, C2cTokenConfig, CarrierConfig, InstitutionConfig, InstitutionFreightTemplateConfig,
+	InstitutionPaymentMethodConfig, ProductConfig, RegionConfig, RuntimeGenesisConfig, SudoConfig,
+};
 use alloc::{vec, vec::Vec};
 use frame_support::build_struct_json_patch;
 use serde_json::Value;
@@ -24,6 +27,9 @@ use sp_consensus_grandpa::AuthorityId as GrandpaId;
 use sp_genesis_builder::{self, PresetId};
 use sp_keyring::Sr25519Keyring;
 
+/// Preset seeding the development chain with a demo marketplace catalog.
+pub const FISSIONMALL_DEMO_PRESET: &str = "fissionmall_demo";
+
 // Returns the genesis config presets populated with given parameters.
 fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
@@ -86,11 +92,254 @@ pub fn local_config_genesis() -> Value {
 	)
 }
 
+/// Return the development genesis config with a seeded marketplace catalog.
+///
+/// Alice runs a certified fresh-produce store and Bob a certified electronics store, each
+/// with products, freight templates and payment methods; Charlie's store is still awaiting
+/// review by the registrars Dave and Eve.
+pub fn fissionmall_demo_genesis() -> Value {
+	let alice = Sr25519Keyring::Alice.to_account_id();
+	let bob = Sr25519Keyring::Bob.to_account_id();
+	let charlie = Sr25519Keyring::Charlie.to_account_id();
+	let text = |s: &str| s.as_bytes().to_vec();
+
+	let mut patch = testnet_genesis(
+		vec![(
+			sp_keyring::Sr25519Keyring::Alice.public().into(),
+			sp_keyring::Ed25519Keyring::Alice.public().into(),
+		)],
+		Sr25519Keyring::iter()
+			.filter(|v| v != &Sr25519Keyring::One && v != &Sr25519Keyring::Two)
+			.map(|v| v.to_account_id())
+			.collect::<Vec<_>>(),
+		alice.clone(),
+	);
+
+	let marketplace = build_struct_json_patch!(RuntimeGenesisConfig {
+		institution: InstitutionConfig {
+			registrars: vec![
+				Sr25519Keyring::Dave.to_account_id(),
+				Sr25519Keyring::Eve.to_account_id(),
+			],
+			institutions: vec![
+				(
+					text("HZ-FRESH-001"),
+					alice.clone(),
+					text("西湖鲜果"),
+					text("杭州西湖鲜果贸易有限公司"),
+					text("https://img.fissionmall.example/license/hz-fresh-001.png"),
+					text("张晓"),
+					text("水果、蔬菜及农副产品零售"),
+					true,
+				),
+				(
+					text("SZ-DIGI-002"),
+					bob.clone(),
+					text("南山数码"),
+					text("深圳南山数码科技有限公司"),
+					text("https://img.fissionmall.example/license/sz-digi-002.png"),
+					text("李明"),
+					text("电子产品及配件销售"),
+					true,
+				),
+				(
+					text("CD-TEA-003"),
+					charlie.clone(),
+					text("蜀山茶社"),
+					text("成都蜀山茶业有限公司"),
+					text("https://img.fissionmall.example/license/cd-tea-003.png"),
+					text("王芳"),
+					text("茶叶及茶具销售"),
+					false,
+				),
+			],
+		},
+		institution_freight_template: InstitutionFreightTemplateConfig {
+			templates: vec![
+				(
+					text("HZ-FRESH-001"),
+					text("cold-chain"),
+					alice.clone(),
+					text("生鲜冷链"),
+					2,
+					6,
+					(1_000, 1_200, 1_000, 500, Some(9_900), None),
+					vec![
+						(vec![text("330000"), text("310000"), text("320000")], (1_000, 800, 1_000, 300, Some(5_900), None)),
+						(vec![text("650000")], (1_000, 3_000, 1_000, 1_500, None, None)),
+					],
+					vec![text("540000")],
+					vec![],
+				),
+				(
+					text("SZ-DIGI-002"),
+					text("standard"),
+					bob.clone(),
+					text("数码标准快递"),
+					1,
+					0,
+					(1, 1_000, 1, 500, Some(9_900), Some(3)),
+					vec![
+						(vec![text("440000")], (1, 600, 1, 300, Some(4_900), Some(2))),
+						(vec![text("650000"), text("540000")], (1, 2_500, 1, 1_500, None, None)),
+					],
+					vec![],
+					vec![(vec![text("650000"), text("540000")], 1, 50)],
+				),
+			],
+		},
+		institution_payment_method: InstitutionPaymentMethodConfig {
+			payment_methods: vec![
+				(
+					text("HZ-FRESH-001"),
+					vec![
+						(1, vec![text("wx-hzfresh")], 0, text("微信支付"), true, 0),
+						(2, vec![text("hzfresh@alipay")], 0, text("支付宝"), true, 1),
+						(4, vec![], 0, text("货到付款"), true, 2),
+					],
+				),
+				(
+					text("SZ-DIGI-002"),
+					vec![
+						(2, vec![text("szdigi@alipay")], 0, text("支付宝"), true, 0),
+						(
+							0,
+							vec![text("招商银行"), text("深圳南山支行"), text("6225880123456789")],
+							0,
+							text("对公转账"),
+							true,
+							1,
+						),
+						(3, vec![text("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")], 1, text("FMT"), false, 2),
+					],
+				),
+			],
+		},
+		product: ProductConfig {
+			products: vec![
+				(
+					text("APPLE-5KG"),
+					text("HZ-FRESH-001"),
+					alice.clone(),
+					text("烟台红富士苹果 5kg"),
+					text("水果"),
+					text("西湖鲜果"),
+					8_900,
+					6_900,
+					text("产地直发，单果 80mm 以上"),
+					text("https://img.fissionmall.example/product/apple-5kg.png"),
+					500,
+					5_200,
+					Some((40, 30, 20)),
+				),
+				(
+					text("TEA-LONGJING"),
+					text("HZ-FRESH-001"),
+					alice.clone(),
+					text("明前西湖龙井 250g"),
+					text("茶叶"),
+					text("西湖鲜果"),
+					38_800,
+					32_800,
+					text("一级明前茶，罐装"),
+					text("https://img.fissionmall.example/product/tea-longjing.png"),
+					120,
+					400,
+					Some((12, 12, 15)),
+				),
+				(
+					text("EARBUDS-PRO"),
+					text("SZ-DIGI-002"),
+					bob.clone(),
+					text("降噪蓝牙耳机"),
+					text("数码配件"),
+					text("南山数码"),
+					59_900,
+					49_900,
+					text("主动降噪，续航 30 小时"),
+					text("https://img.fissionmall.example/product/earbuds-pro.png"),
+					300,
+					250,
+					None,
+				),
+				(
+					text("CHARGER-65W"),
+					text("SZ-DIGI-002"),
+					bob.clone(),
+					text("65W 氮化镓充电器"),
+					text("数码配件"),
+					text("南山数码"),
+					19_900,
+					14_900,
+					text("双 USB-C 口快充"),
+					text("https://img.fissionmall.example/product/charger-65w.png"),
+					800,
+					150,
+					None,
+				),
+			],
+		},
+		c_2c_token: C2cTokenConfig {
+			tokens: vec![
+				(
+					text("FRESH-COUPON"),
+					text("HZ-FRESH-001"),
+					alice.clone(),
+					text("西湖鲜果 50 元代金券"),
+					text("代金券"),
+					4_500,
+					0,
+					1_000,
+				),
+				(
+					text("DIGI-POINTS"),
+					text("SZ-DIGI-002"),
+					bob.clone(),
+					text("南山数码积分回收"),
+					text("积分"),
+					90,
+					1,
+					10_000,
+				),
+			],
+		},
+		carrier: CarrierConfig {
+			carriers: vec![
+				(text("SF"), text("顺丰速运"), 12, 15, vec![text("SF")], 0, true),
+				(text("JD"), text("京东物流"), 12, 15, vec![text("JD")], 1, true),
+				(text("YTO"), text("圆通速递"), 12, 15, vec![text("YT")], 0, false),
+				(text("EMS"), text("中国邮政 EMS"), 13, 13, vec![], 1, true),
+			],
+		},
+		region: RegionConfig {
+			regions: vec![
+				(text("CN"), text("中国"), None),
+				(text("310000"), text("上海市"), Some(text("CN"))),
+				(text("320000"), text("江苏省"), Some(text("CN"))),
+				(text("330000"), text("浙江省"), Some(text("CN"))),
+				(text("440000"), text("广东省"), Some(text("CN"))),
+				(text("540000"), text("西藏自治区"), Some(text("CN"))),
+				(text("650000"), text("新疆维吾尔自治区"), Some(text("CN"))),
+				(text("330100"), text("杭州市"), Some(text("330000"))),
+				(text("440300"), text("深圳市"), Some(text("440000"))),
+				(text("330106"), text("西湖区"), Some(text("330100"))),
+				(text("440305"), text("南山区"), Some(text("440300"))),
+			],
+		},
+	});
+
+	if let (Value::Object(patch), Value::Object(marketplace)) = (&mut patch, marketplace) {
+		patch.extend(marketplace);
+	}
+	patch
+}
+
 /// Provides the JSON representation of predefined genesis config for given `id`.
 pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
 	let patch = match id.as_ref() {
 		sp_genesis_builder::DEV_RUNTIME_PRESET => development_config_genesis(),
 		sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => local_config_genesis(),
+		FISSIONMALL_DEMO_PRESET => fissionmall_demo_genesis(),
 		_ => return None,
 	};
 	Some(
@@ -105,5 +354,6 @@ pub fn preset_names() -> Vec<PresetId> {
 	vec![
 		PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
 		PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+		PresetId::from(FISSIONMALL_DEMO_PRESET),
 	]
 }
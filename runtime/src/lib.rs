@@ -69,10 +69,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: apis::RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	system_version: 1,
 };
 
@@ -171,7 +171,22 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, TxExtension>;
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+	pallet_institution::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_institution::migrations::v2::MigrateV1ToV2<Runtime>,
+	pallet_institution::migrations::v3::MigrateV2ToV3<Runtime>,
+	pallet_institution::migrations::v4::MigrateV3ToV4<Runtime>,
+	pallet_institution_freight_template::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_institution_freight_template::migrations::v2::MigrateV1ToV2<Runtime>,
+	pallet_institution_freight_template::migrations::v3::MigrateV2ToV3<Runtime>,
+	pallet_institution_freight_template::migrations::v4::MigrateV3ToV4<Runtime>,
+	pallet_institution_freight_template::migrations::v5::MigrateV4ToV5<Runtime>,
+	pallet_institution_freight_template::migrations::v6::MigrateV5ToV6<Runtime>,
+	pallet_institution_payment_method::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_product::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_order::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_order::migrations::v2::MigrateV1ToV2<Runtime>,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -253,4 +268,16 @@ mod runtime {
 	// Include the c2c-order pallet in the runtime.
 	#[runtime::pallet_index(14)]
 	pub type C2cOrder = pallet_c2c_order;
+
+	// Include the institution reputation pallet in the runtime.
+	#[runtime::pallet_index(15)]
+	pub type InstitutionReputation = pallet_institution_reputation;
+
+	// Include the region registry pallet in the runtime.
+	#[runtime::pallet_index(16)]
+	pub type Region = pallet_region;
+
+	// Include the carrier registry pallet in the runtime.
+	#[runtime::pallet_index(17)]
+	pub type Carrier = pallet_carrier;
 }
@@ -37,13 +37,13 @@ async function main() {
     const orderCode = 'TEST_ORDER_001';
     const memberCode = 'MEMBER_001';
     const institutionCode = 'INST_001';
-    const freight = 10;
+    const shipping = ['standard', '110000']; // [运费模板ID, 目的地区编码]
     const phone = '13800138000';
     const email = 'test@example.com';
     const address = '测试地址123号';
     const items = [
-      ['PRODUCT_001', 2, 100, 500], // [产品代码, 数量, 单价, 重量]
-      ['PRODUCT_002', 1, 200, 300]
+      ['PRODUCT_001', 2], // [产品代码, 数量]，单价及重量取自链上商品信息
+      ['PRODUCT_002', 1]
     ];
     
     // 创建订单交易
@@ -51,7 +51,7 @@ async function main() {
       orderCode,
       memberCode,
       institutionCode,
-      freight,
+      shipping,
       phone,
       email,
       address,